@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-use sdl2::{pixels::Color, rect::Rect, render::{Texture, TextureCreator}, ttf::{Font, Sdl2TtfContext}, video::WindowContext};
+use sdl2::{pixels::Color, rect::Rect, render::{Texture, TextureCreator, TextureQuery}, ttf::{Font, Sdl2TtfContext}, video::WindowContext};
 use tiled::Map;
 
-use crate::{engine_types::{animation::Animation, sprite_data::SpriteData}, game_logic::{characters::Attack, inputs::game_inputs::GameAction}, rendering::renderer_ui::text_gen};
+use crate::{engine_types::{animation::Animation, sprite_data::SpriteData}, game_logic::{characters::Attack, inputs::{game_inputs::GameAction, motion_inputs::MotionInput}}, rendering::renderer_ui::text_gen};
 
 use super::{asset_loader::{asset_loader, load_tiled_map::load_level, my_spritesheet_format::load_spritesheet}, rng_tables::load_item_table};
 
@@ -37,9 +37,10 @@ impl DirectionalAttack {
 }
 
 pub struct EntityData {
-    pub auto_combo_strings: HashMap<i32, Vec<&'static str>>,
+    pub auto_combo_strings: HashMap<i32, Vec<String>>,
     pub directional_variation_anims: Vec<DirectionalAttack>,    //mask, is_airborne, inputs, name_of_attack 
     pub attacks: HashMap<String, Attack>,
+    pub motions: Vec<MotionInput>,
 }
 
 pub struct OverworldAssets<'a>{
@@ -96,22 +97,14 @@ impl<'a> UIAssets<'a> {
     }
 }
 
-pub struct LevelAssets<'a>{
-    pub level_tiles: HashMap<String,Texture<'a>>,
-    pub level_rooms: HashMap<i32, Map>,
-    
-    pub shadow: Texture<'a>,
-
-    //hit effects
-    pub hit_effect_textures: HashMap<String, Texture<'a>>,
-    pub hit_effect_animations: HashMap<String, Animation>,
+//hit effects data that the simulation needs, kept apart from the textures so it can run without a renderer
+pub struct VfxData {
+    pub animations: HashMap<String, Animation>,
+    pub texture_sizes: HashMap<String, (u32, u32)>,
 }
 
-impl<'a> LevelAssets<'a> {
-    pub fn load(texture_creator: &'a TextureCreator<WindowContext>, ttf_context: &'a Sdl2TtfContext) -> Self {
-
-        let (textures, _) = asset_loader::load_textures_for_character(&texture_creator, "assets/vfx");
-
+impl VfxData {
+    pub fn load_animations() -> HashMap<String, Animation> {
         let hit_anim = 
             asset_loader::load_anim_from_dir("assets/vfx/normal_hit", "normal_hit");
         let hit2_anim =
@@ -133,21 +126,60 @@ impl<'a> LevelAssets<'a> {
         vfx.insert(jumping_ground_anim.name.clone(),jumping_ground_anim);
         vfx.insert(feet_dust_ground_anim.name.clone(),feet_dust_ground_anim);
 
+        vfx
+    }
+
+    pub fn texture_size(&self, texture_id: &str) -> (u32, u32) {
+        match self.texture_sizes.get(texture_id) {
+            Some(&size) => size,
+            None => (0, 0),
+        }
+    }
+}
+
+pub fn load_level_rooms() -> HashMap<i32, Map> {
+    let mut level_rooms = HashMap::new();
+    level_rooms.insert(0, load_level("assets/level/level1.tmx".to_string()));
+    level_rooms.insert(1, load_level("assets/level/level2.tmx".to_string()));
+    level_rooms.insert(2, load_level("assets/level/level3.tmx".to_string()));
+    level_rooms.insert(3, load_level("assets/level/level4.tmx".to_string()));
+
+    level_rooms
+}
+
+pub struct LevelAssets<'a>{
+    pub level_tiles: HashMap<String,Texture<'a>>,
+    pub level_rooms: HashMap<i32, Map>,
+    
+    pub shadow: Texture<'a>,
+
+    //hit effects
+    pub hit_effect_textures: HashMap<String, Texture<'a>>,
+    pub vfx: VfxData,
+}
+
+impl<'a> LevelAssets<'a> {
+    pub fn load(texture_creator: &'a TextureCreator<WindowContext>, ttf_context: &'a Sdl2TtfContext) -> Self {
+
+        let (textures, _) = asset_loader::load_textures_for_character(&texture_creator, "assets/vfx");
+
+        let texture_sizes = textures.iter().map(|(key, texture)| {
+            let TextureQuery { width, height, .. } = texture.query();
+            (key.clone(), (width, height))
+        }).collect::<HashMap<String, (u32, u32)>>();
+
         let mut level_tiles = HashMap::new();
 
         level_tiles.insert("room_tileset".to_string(), asset_loader::load_texture(&texture_creator, "assets/level/hyptosis_tile-art-batch-1.png"));
 
-        let mut level_rooms = HashMap::new();
-        level_rooms.insert(0, load_level("assets/level/level1.tmx".to_string()));
-        level_rooms.insert(1, load_level("assets/level/level2.tmx".to_string()));
-        level_rooms.insert(2, load_level("assets/level/level3.tmx".to_string()));
-        level_rooms.insert(3, load_level("assets/level/level4.tmx".to_string()));
-
         LevelAssets {
             hit_effect_textures: textures,
-            hit_effect_animations: vfx,
+            vfx: VfxData {
+                animations: VfxData::load_animations(),
+                texture_sizes,
+            },
             level_tiles,
-            level_rooms,
+            level_rooms: load_level_rooms(),
             shadow: asset_loader::load_texture(&texture_creator, "assets/vfx/shadow/29492.png"),
         }
     }
@@ -61,6 +61,31 @@ fn look_for_textures<'a>(
     }
 }
 
+//same as load_textures_for_character but without touching the renderer, only the .scon data
+pub fn load_sprite_data_for_character(dir: &str) -> HashMap<String, SpriteData> {
+    let mut sprite_data = HashMap::new();
+    look_for_sprite_data(dir, &mut sprite_data);
+    sprite_data
+}
+
+fn look_for_sprite_data(dir: &str, sprite_data_dictionary: &mut HashMap<String, SpriteData>) {
+    let paths = fs::read_dir(dir).unwrap();
+
+    for entry in paths {
+        let entry = entry.unwrap();
+        let path = entry.path();
+
+        if path.is_dir() {
+            look_for_sprite_data(path.to_str().unwrap(), sprite_data_dictionary);
+        } else if path.is_file() && path.extension().unwrap() == "scon" {
+            let sprites_data: Vec<SpriteData> = load_frame_data(path);
+            for data in sprites_data {
+                sprite_data_dictionary.insert(data.sprite_name.clone(), data);
+            }
+        }
+    }
+}
+
 pub fn load_anim_from_dir(dir: &str, name: &str) -> Animation {
     println!("Loading animation: {:?}", dir);
     let paths = fs::read_dir(dir).unwrap();
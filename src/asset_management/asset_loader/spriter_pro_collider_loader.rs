@@ -190,6 +190,10 @@ pub fn load_animation_data(
 
         let collider_type = if boxes[j].name.contains("hit") {
             ColliderType::Hitbox
+        } else if boxes[j].name.contains("grabbable") {
+            ColliderType::Grabbablebox
+        } else if boxes[j].name.contains("grab") {
+            ColliderType::Grabbox
         } else if boxes[j].name.contains("push") {
             ColliderType::Pushbox
         } else {
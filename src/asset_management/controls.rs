@@ -1,54 +1,167 @@
 use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::string::String;
 
 use directories::ProjectDirs;
 
 use crate::input::translated_inputs::TranslatedInput;
 
-//TODO: with controllers vs joysticks it might be needed to implement custom serialization
-//TODO into strings and you cannot use them as hashes unless they are stringified
-pub fn load_controls() -> HashMap<String, TranslatedInput> {
+const CONTROLS_FILE: &str = "config_file.prefs.json";
+
+//inputs that can be rebound in each profile, gamepad directions always come from the stick
+pub const KEYBOARD_REBINDABLE: [TranslatedInput; 8] = [
+    TranslatedInput::Punch,
+    TranslatedInput::Kick,
+    TranslatedInput::Jump,
+    TranslatedInput::Block,
+    TranslatedInput::Vertical(1),
+    TranslatedInput::Vertical(-1),
+    TranslatedInput::Horizontal(-1),
+    TranslatedInput::Horizontal(1),
+];
+pub const GAMEPAD_REBINDABLE: [TranslatedInput; 4] = [
+    TranslatedInput::Punch,
+    TranslatedInput::Kick,
+    TranslatedInput::Jump,
+    TranslatedInput::Block,
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ControlsProfile {
+    Keyboard,
+    Gamepad,
+}
+
+//keyboard keys are stored by keycode name, gamepad buttons by their index
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Controls {
+    pub keyboard: HashMap<String, TranslatedInput>,
+    pub gamepad: HashMap<String, TranslatedInput>,
+}
+
+impl Controls {
+    pub fn default_controls() -> Self {
+        let mut keyboard: HashMap<String, TranslatedInput> = HashMap::new();
+        keyboard.insert("U".to_string(), TranslatedInput::Punch);
+        keyboard.insert("I".to_string(), TranslatedInput::Kick);
+        keyboard.insert("Space".to_string(), TranslatedInput::Jump);
+        keyboard.insert("O".to_string(), TranslatedInput::Block);
+
+        keyboard.insert("W".to_string(), TranslatedInput::Vertical(1));
+        keyboard.insert("S".to_string(), TranslatedInput::Vertical(-1));
+        keyboard.insert("A".to_string(), TranslatedInput::Horizontal(-1));
+        keyboard.insert("D".to_string(), TranslatedInput::Horizontal(1));
+
+        let mut gamepad: HashMap<String, TranslatedInput> = HashMap::new();
+        gamepad.insert(1.to_string(), TranslatedInput::Jump);
+        gamepad.insert(0.to_string(), TranslatedInput::Punch);
+        gamepad.insert(3.to_string(), TranslatedInput::Kick);
+        gamepad.insert(2.to_string(), TranslatedInput::Block);
+
+        Self {
+            keyboard,
+            gamepad,
+        }
+    }
+
+    pub fn profile(&self, profile: ControlsProfile) -> &HashMap<String, TranslatedInput> {
+        match profile {
+            ControlsProfile::Keyboard => &self.keyboard,
+            ControlsProfile::Gamepad => &self.gamepad,
+        }
+    }
+
+    fn profile_mut(&mut self, profile: ControlsProfile) -> &mut HashMap<String, TranslatedInput> {
+        match profile {
+            ControlsProfile::Keyboard => &mut self.keyboard,
+            ControlsProfile::Gamepad => &mut self.gamepad,
+        }
+    }
+
+    pub fn keys_bound_to(&self, profile: ControlsProfile, input: TranslatedInput) -> Vec<String> {
+        let mut keys = self.profile(profile).iter()
+            .filter(|(_, bound_input)| {**bound_input == input})
+            .map(|(key, _)| {key.clone()})
+            .collect::<Vec<String>>();
+        keys.sort();
+        keys
+    }
+
+    //binds key to input and returns the input that had the key before, if any
+    //that input gets the replaced keys back so it never ends up unbound
+    pub fn rebind(&mut self, profile: ControlsProfile, key: String, input: TranslatedInput) -> Option<TranslatedInput> {
+        let old_keys = self.keys_bound_to(profile, input);
+        let bindings = self.profile_mut(profile);
+        let conflict = bindings.get(&key).cloned().filter(|bound_input| {*bound_input != input});
+
+        for old_key in old_keys.iter() {
+            bindings.remove(old_key);
+        }
+        bindings.insert(key.clone(), input);
+
+        if let Some(conflict) = conflict {
+            if !bindings.values().any(|bound_input| {*bound_input == conflict}) {
+                for old_key in old_keys {
+                    bindings.insert(old_key, conflict);
+                }
+            }
+        }
+        conflict
+    }
+}
+
+pub fn config_file_path(file_name: &str) -> PathBuf {
     let proj_dir = ProjectDirs::from("com", "FightingGame", "fighting game").unwrap();
     let config_dir = proj_dir.config_dir();
-    let path = Path::new(config_dir).join("config_file.prefs.json");
 
     if let Err(e) = fs::create_dir_all(&config_dir) {
         println!("{:?} will continue with existing file", e)
     }
 
+    Path::new(config_dir).join(file_name)
+}
+
+pub fn load_controls() -> Controls {
+    let path = config_file_path(CONTROLS_FILE);
+
     if !path.exists() {
-        File::create(&path).unwrap();
+        let controls = Controls::default_controls();
+        save_controls(&controls);
+        return controls;
     }
 
     let json_string = fs::read_to_string(&path).unwrap();
 
-    if !json_string.is_empty() {
-        let deserialized: HashMap<String, TranslatedInput> =
-            serde_json::from_str(&json_string).unwrap();
-        deserialized
-    } else {
-        let mut controls: HashMap<String, TranslatedInput> = HashMap::new();
-
-        controls.insert(1.to_string(), TranslatedInput::Jump);
-        controls.insert(0.to_string(), TranslatedInput::Punch);
-        controls.insert(3.to_string(), TranslatedInput::Kick);
-        controls.insert(2.to_string(), TranslatedInput::Block);
-
-        controls.insert("U".to_string(), TranslatedInput::Punch);
-        controls.insert("I".to_string(), TranslatedInput::Kick);
-        controls.insert("Space".to_string(), TranslatedInput::Jump);
-        controls.insert("O".to_string(), TranslatedInput::Block);
-
-        controls.insert("W".to_string(), TranslatedInput::Vertical(1));
-        controls.insert("S".to_string(), TranslatedInput::Vertical(-1));
-        controls.insert("A".to_string(), TranslatedInput::Horizontal(-1));
-        controls.insert("D".to_string(), TranslatedInput::Horizontal(1));
+    if let Ok(controls) = serde_json::from_str::<Controls>(&json_string) {
+        return controls;
+    }
 
-        // let _save_result =  fs::write(dirs::config_dir().unwrap()).unwrap();
+    //older prefs kept keys and buttons in the same map, buttons are the numeric ones
+    match serde_json::from_str::<HashMap<String, TranslatedInput>>(&json_string) {
+        Ok(flat_controls) => {
+            let (gamepad, keyboard): (HashMap<String, TranslatedInput>, HashMap<String, TranslatedInput>) = flat_controls
+                .into_iter()
+                .partition(|(key, _)| {key.parse::<u8>().is_ok()});
+            let controls = Controls {
+                keyboard,
+                gamepad,
+            };
+            save_controls(&controls);
+            controls
+        },
+        Err(e) => {
+            println!("ignoring invalid controls {:?}", e);
+            let controls = Controls::default_controls();
+            save_controls(&controls);
+            controls
+        }
+    }
+}
 
-        controls
+pub fn save_controls(controls: &Controls) {
+    let path = config_file_path(CONTROLS_FILE);
+    if let Err(e) = fs::write(&path, serde_json::to_string_pretty(controls).unwrap()) {
+        println!("failed to save controls {:?}", e);
     }
 }
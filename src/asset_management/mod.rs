@@ -2,6 +2,9 @@ pub mod asset_loader;
 pub mod asset_holders;
 pub mod common_assets;
 pub mod controls;
+pub mod profile;
+pub mod run_save;
+pub mod settings;
 pub mod sound;
 pub mod vfx;
 pub mod cast_point;
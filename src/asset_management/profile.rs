@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::game_logic::meta_progression::{DEFAULT_LOADOUT, MilestoneCounter};
+
+use super::controls::config_file_path;
+
+const PROFILE_FILE: &str = "profile.json";
+
+//everything that carries over between runs
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    pub meta_currency: u32,
+    pub unlocked_items: HashSet<i32>,
+    pub unlocked_loadouts: HashSet<String>,
+    //the loadout new runs start with
+    pub loadout: String,
+    //the ascension last picked for a new run
+    pub ascension: u32,
+    pub milestone_progress: HashMap<MilestoneCounter, u32>,
+    pub completed_milestones: HashSet<String>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            meta_currency: 0,
+            unlocked_items: HashSet::new(),
+            unlocked_loadouts: HashSet::new(),
+            loadout: DEFAULT_LOADOUT.to_string(),
+            ascension: 0,
+            milestone_progress: HashMap::new(),
+            completed_milestones: HashSet::new(),
+        }
+    }
+}
+
+impl Profile {
+    pub fn progress(&self, counter: MilestoneCounter) -> u32 {
+        *self.milestone_progress.get(&counter).unwrap_or(&0)
+    }
+
+    pub fn add_progress(&mut self, counter: MilestoneCounter, amount: u32) {
+        *self.milestone_progress.entry(counter).or_insert(0) += amount;
+    }
+
+    pub fn record_best(&mut self, counter: MilestoneCounter, value: u32) {
+        let best = self.milestone_progress.entry(counter).or_insert(0);
+        *best = (*best).max(value);
+    }
+
+    pub fn has_loadout(&self, loadout_id: &str) -> bool {
+        loadout_id == DEFAULT_LOADOUT || self.unlocked_loadouts.contains(loadout_id)
+    }
+
+    //false when it cant be paid for
+    pub fn spend(&mut self, cost: u32) -> bool {
+        if self.meta_currency < cost {
+            return false;
+        }
+        self.meta_currency -= cost;
+        true
+    }
+}
+
+pub fn load_profile() -> Profile {
+    let path = config_file_path(PROFILE_FILE);
+    if !path.exists() {
+        return Profile::default();
+    }
+
+    let json_string = fs::read_to_string(&path).unwrap();
+    match serde_json::from_str::<Profile>(&json_string) {
+        Ok(profile) => profile,
+        Err(e) => {
+            println!("ignoring invalid profile {:?}", e);
+            Profile::default()
+        }
+    }
+}
+
+pub fn save_profile(profile: &Profile) {
+    let path = config_file_path(PROFILE_FILE);
+    if let Err(e) = fs::write(&path, serde_json::to_string_pretty(profile).unwrap()) {
+        println!("failed to save profile {:?}", e);
+    }
+}
@@ -1,5 +1,8 @@
 use std::{collections::HashMap, fs};
 
+//item tags roll from this table instead of their own with the difficulty's rare item chance
+pub const BOSS_TABLE: &str = "boss_table";
+
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Root {
@@ -20,7 +23,7 @@ pub struct Item {
     pub rarity: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
 pub struct LootTable {
     pub acc: u64,
     pub items: Vec<Item>,
@@ -49,7 +52,7 @@ pub fn load_item_table(dir: String) -> HashMap<String, LootTable> {
 
 
     loot_tables.insert("normal_table".to_string(), LootTable{acc: normal_table_items.iter().map(|i|{i.rarity}).sum(), items: normal_table_items});
-    loot_tables.insert("boss_table".to_string(), LootTable{acc: boss_table_items.iter().map(|i|{i.rarity}).sum(), items: boss_table_items});
+    loot_tables.insert(BOSS_TABLE.to_string(), LootTable{acc: boss_table_items.iter().map(|i|{i.rarity}).sum(), items: boss_table_items});
     loot_tables.insert("store_table".to_string(), LootTable{acc: store_table_items.iter().map(|i|{i.rarity}).sum(), items: store_table_items});
     loot_tables.insert("event_table".to_string(), LootTable{acc: event_table_items.iter().map(|i|{i.rarity}).sum(), items: event_table_items});
 
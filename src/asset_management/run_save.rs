@@ -0,0 +1,242 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::rc::Rc;
+
+use sdl2::rect::Point;
+
+use crate::{GameStateData, asset_management::rng_tables::LootTable, game_logic::{characters::player::Player, coop::PLAYER_2_ID, factories::character_factory::{character_definition, load_character}, rng_streams::RngStream, run_seed::SeedMode, run_stats::RunStats}, overworld::node::{WorldNode, WorldNodeType}, scenes::overworld_scene::OverworldScene};
+
+use super::controls::config_file_path;
+
+const RUN_SAVE_FILE: &str = "run_save.json";
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSave {
+    pub seed: u64,
+    #[serde(default)]
+    pub seed_mode: SeedMode,
+    //the rng streams are rebuilt from the seed and how far each one got
+    #[serde(default)]
+    pub rng_draws: HashMap<RngStream, u64>,
+    pub curr_level: i32,
+    #[serde(default)]
+    pub curr_act: u32,
+    pub player: PlayerSave,
+    //only there once someone dropped in as player 2
+    #[serde(default)]
+    pub player2: Option<PlayerSave>,
+    pub overworld: OverworldSave,
+    pub loot_tables: HashMap<String, LootTable>,
+    #[serde(default)]
+    pub run_stats: RunStats,
+    //keeps the telemetry of a continued run under the same id
+    #[serde(default)]
+    pub run_id: u64,
+    #[serde(default)]
+    pub ascension: u32,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSave {
+    pub character_name: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub currency: u32,
+    pub item_ids: Vec<i32>,
+
+    pub speed: f64,
+    pub dash_speed: f64,
+    pub jump_height: f64,
+    pub jump_distance: f64,
+    pub can_double_jump: bool,
+    pub can_air_dash: bool,
+
+    pub punch_string_curr: i8,
+    pub kick_string_curr: i8,
+    pub airborne_punch_string_curr: i8,
+    pub airborne_kick_string_curr: i8,
+    pub directional_attacks_mask_curr: u32,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverworldSave {
+    pub full_conection: bool,
+    pub player_node_pos: usize,
+    pub nodes: Vec<NodeSave>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSave {
+    pub node_type: WorldNodeType,
+    pub x: i32,
+    pub y: i32,
+    pub connect_to: HashSet<usize>,
+}
+
+impl PlayerSave {
+    pub fn new(player: &Player) -> Self {
+        let character = &player.character;
+        Self {
+            character_name: character.name.clone(),
+            hp: player.hp.0,
+            max_hp: character.hp,
+            currency: player.currency,
+            item_ids: player.item_ids.clone(),
+
+            speed: character.speed,
+            dash_speed: character.dash_speed,
+            jump_height: character.jump_height,
+            jump_distance: character.jump_distance,
+            can_double_jump: character.can_double_jump,
+            can_air_dash: character.can_air_dash,
+
+            punch_string_curr: character.punch_string_curr,
+            kick_string_curr: character.kick_string_curr,
+            airborne_punch_string_curr: character.airborne_punch_string_curr,
+            airborne_kick_string_curr: character.airborne_kick_string_curr,
+            directional_attacks_mask_curr: character.directional_attacks_mask_curr,
+        }
+    }
+
+    //items have to be equipped first so their effects get registered, then the saved values overwrite whatever the effects changed
+    pub fn apply(&self, player: &mut Player) {
+        let character = &mut player.character;
+        character.hp = self.max_hp;
+        character.speed = self.speed;
+        character.dash_speed = self.dash_speed;
+        character.jump_height = self.jump_height;
+        character.jump_distance = self.jump_distance;
+        character.can_double_jump = self.can_double_jump;
+        character.can_air_dash = self.can_air_dash;
+
+        character.punch_string_curr = self.punch_string_curr;
+        character.kick_string_curr = self.kick_string_curr;
+        character.airborne_punch_string_curr = self.airborne_punch_string_curr;
+        character.airborne_kick_string_curr = self.airborne_kick_string_curr;
+        character.directional_attacks_mask_curr = self.directional_attacks_mask_curr;
+
+        player.hp.0 = self.hp;
+        player.currency = self.currency;
+    }
+}
+
+impl OverworldSave {
+    pub fn new(overworld: &OverworldScene) -> Self {
+        Self {
+            full_conection: overworld.full_conection,
+            player_node_pos: overworld.player_node_pos,
+            nodes: overworld.nodes.iter().map(|node| {
+                NodeSave {
+                    node_type: node.node_type,
+                    x: node.position.x,
+                    y: node.position.y,
+                    connect_to: node.connect_to.clone(),
+                }
+            }).collect(),
+        }
+    }
+
+    pub fn to_nodes(&self) -> Vec<WorldNode> {
+        self.nodes.iter().map(|node| {
+            WorldNode {
+                node_type: node.node_type,
+                position: Point::new(node.x, node.y),
+                connect_to: node.connect_to.clone(),
+            }
+        }).collect()
+    }
+}
+
+impl RunSave {
+    pub fn new(game_state_data: &mut GameStateData, overworld: &OverworldScene) -> Self {
+        Self {
+            seed: game_state_data.seed.unwrap(),
+            seed_mode: game_state_data.seed_mode,
+            rng_draws: game_state_data.rng_streams.draws.clone(),
+            curr_level: game_state_data.curr_level,
+            curr_act: game_state_data.curr_act,
+            player: PlayerSave::new(game_state_data.player.as_ref().unwrap()),
+            player2: game_state_data.player2.as_ref().map(|player2| {PlayerSave::new(player2)}),
+            overworld: OverworldSave::new(overworld),
+            loot_tables: game_state_data.general_assets.loot_tables.clone(),
+            run_stats: game_state_data.run_stats.clone(),
+            run_id: game_state_data.telemetry.run_id,
+            ascension: game_state_data.ascension,
+        }
+    }
+}
+
+//needs the player animations and the hp/energy bars already initialized
+pub fn restore_player(player_save: &PlayerSave, game_state_data: &mut GameStateData) -> Player {
+    let player = load_saved_player(player_save, 1, game_state_data);
+
+    game_state_data.hp_bar.as_mut().unwrap().update(player.character.hp, player.hp.0);
+    game_state_data.energy_bar.as_mut().unwrap().update_width(player.active_item_cost as i32, player.currency as i32);
+
+    player
+}
+
+//the player 2 bars are created by the match once it sees the player
+pub fn restore_player2(player_save: &PlayerSave, game_state_data: &mut GameStateData) -> Player {
+    load_saved_player(player_save, PLAYER_2_ID, game_state_data)
+}
+
+fn load_saved_player(player_save: &PlayerSave, id: i32, game_state_data: &mut GameStateData) -> Player {
+    let mut player = load_character(
+        character_definition(&mut game_state_data.character_definitions, &player_save.character_name),
+        Point::new(200, 50),
+        id,
+        Rc::clone(game_state_data.enemy_animations.get("player").unwrap())
+    );
+
+    for item_id in player_save.item_ids.iter() {
+        if let Some(item) = game_state_data.items.get(item_id) {
+            player.equip_item(&mut item.clone(), &game_state_data.effects);
+        }
+    }
+    player_save.apply(&mut player);
+
+    player
+}
+
+pub fn save_run(run_save: &RunSave) {
+    let path = config_file_path(RUN_SAVE_FILE);
+    if let Err(e) = fs::write(&path, serde_json::to_string(run_save).unwrap()) {
+        println!("failed to save run {:?}", e);
+    }
+}
+
+pub fn load_run() -> Option<RunSave> {
+    let path = config_file_path(RUN_SAVE_FILE);
+    if !path.exists() {
+        return None;
+    }
+
+    let json_string = match fs::read_to_string(&path) {
+        Ok(json_string) => json_string,
+        Err(e) => {
+            println!("failed to read run save {:?}", e);
+            return None;
+        }
+    };
+    match serde_json::from_str::<RunSave>(&json_string) {
+        Ok(run_save) => Some(run_save),
+        Err(e) => {
+            println!("ignoring invalid run save {:?}", e);
+            None
+        }
+    }
+}
+
+pub fn delete_run() {
+    let path = config_file_path(RUN_SAVE_FILE);
+    if path.exists() {
+        if let Err(e) = fs::remove_file(&path) {
+            println!("failed to delete run save {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::fs;
+
+use sdl2::{mixer::MAX_VOLUME, render::Canvas, video::{FullscreenType, Window, WindowPos}};
+
+use crate::game_logic::coop::CoopLoot;
+
+use super::{controls::config_file_path, sound::sound_bank::Bus};
+
+const SETTINGS_FILE: &str = "settings.prefs.json";
+
+//everything is drawn at this size and scaled to whatever the window is
+pub const RENDER_RESOLUTION: (u32, u32) = (1280, 720);
+pub const WINDOW_SIZES: [(u32, u32); 4] = [(1280, 720), (1366, 768), (1600, 900), (1920, 1080)];
+pub const VOLUME_STEP: i32 = 10;
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    //volumes go from 0 to 100, every bus is scaled by master
+    pub master_volume: i32,
+    pub music_volume: i32,
+    pub sfx_volume: i32,
+    pub ui_volume: i32,
+    pub voice_volume: i32,
+
+    pub window_width: u32,
+    pub window_height: u32,
+    pub fullscreen: bool,
+    //only read when the renderer is created
+    pub vsync: bool,
+
+    pub screen_shake: bool,
+    pub show_hitboxes: bool,
+
+    //co-op only, players can hurt each other
+    pub friendly_fire: bool,
+    pub coop_loot: CoopLoot,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 80,
+            music_volume: 10,
+            sfx_volume: 80,
+            ui_volume: 80,
+            voice_volume: 80,
+
+            window_width: RENDER_RESOLUTION.0,
+            window_height: RENDER_RESOLUTION.1,
+            fullscreen: false,
+            vsync: true,
+
+            screen_shake: true,
+            show_hitboxes: false,
+
+            friendly_fire: false,
+            coop_loot: CoopLoot::Shared,
+        }
+    }
+}
+
+impl Settings {
+    pub fn bus_volume(&self, bus: Bus) -> i32 {
+        let volume = match bus {
+            Bus::Sfx => self.sfx_volume,
+            Bus::Ui => self.ui_volume,
+            Bus::Voice => self.voice_volume,
+            Bus::Music => self.music_volume,
+        };
+        MAX_VOLUME * self.master_volume * volume / 10000
+    }
+
+    pub fn window_size_index(&self) -> usize {
+        WINDOW_SIZES.iter()
+            .position(|&size| {size == (self.window_width, self.window_height)})
+            .unwrap_or(0)
+    }
+
+    pub fn set_window_size(&mut self, index: usize) {
+        let (width, height) = WINDOW_SIZES[index % WINDOW_SIZES.len()];
+        self.window_width = width;
+        self.window_height = height;
+    }
+}
+
+pub fn load_settings() -> Settings {
+    let path = config_file_path(SETTINGS_FILE);
+
+    if !path.exists() {
+        let settings = Settings::default();
+        save_settings(&settings);
+        return settings;
+    }
+
+    let json_string = fs::read_to_string(&path).unwrap();
+    match serde_json::from_str::<Settings>(&json_string) {
+        Ok(settings) => settings,
+        Err(e) => {
+            println!("ignoring invalid settings {:?}", e);
+            Settings::default()
+        }
+    }
+}
+
+pub fn save_settings(settings: &Settings) {
+    let path = config_file_path(SETTINGS_FILE);
+    if let Err(e) = fs::write(&path, serde_json::to_string_pretty(settings).unwrap()) {
+        println!("failed to save settings {:?}", e);
+    }
+}
+
+pub fn apply_video_settings(settings: &Settings, canvas: &mut Canvas<Window>) {
+    let window = canvas.window_mut();
+    let fullscreen = if settings.fullscreen { FullscreenType::Desktop } else { FullscreenType::Off };
+    if let Err(e) = window.set_fullscreen(fullscreen) {
+        println!("failed to change fullscreen {:?}", e);
+    }
+    if !settings.fullscreen {
+        if let Err(e) = window.set_size(settings.window_width, settings.window_height) {
+            println!("failed to resize window {:?}", e);
+        }
+        window.set_position(WindowPos::Centered, WindowPos::Centered);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rand::{Rng, SeedableRng, prelude::SmallRng};
+use sdl2::mixer::{AUDIO_S16LSB, Channel, Chunk, MAX_VOLUME, query_spec};
+
+use crate::asset_management::settings::Settings;
+
+use super::{MIXER_CHANNELS, audio_player, music_director::MusicDirector, sound_bank::{Bus, SoundDefinition, load_sound_bank}};
+
+//resampled copies spread evenly over the pitch variation, the middle one is the original
+const PITCH_VARIANTS: usize = 5;
+
+struct Sound {
+    definition: SoundDefinition,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Clone, Default)]
+struct Voice {
+    sound_id: Option<String>,
+    priority: i32,
+    //play counter when it started, lower is older
+    started: u64,
+}
+
+//plays sounds by id, every mixer channel is a voice that gets reused or stolen when all are busy
+pub struct AudioManager {
+    pub music: MusicDirector,
+    sounds: HashMap<String, Sound>,
+    voices: Vec<Voice>,
+    bus_volumes: HashMap<Bus, i32>,
+    plays: u64,
+    //only picks pitch and volume, nothing in the simulation depends on it
+    rng: SmallRng,
+}
+
+impl AudioManager {
+    pub fn load(sound_bank_dir: String, music_dir: String) -> Self {
+        let sounds = load_sound_bank(sound_bank_dir).into_iter()
+            .filter_map(|(id, definition)| {
+                let mut chunk = match audio_player::load_from_file(Path::new(&definition.file)) {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        println!("Cannot load sound file {}: {:?}", definition.file, e);
+                        return None;
+                    }
+                };
+                chunk.set_volume(definition.volume);
+
+                let mut chunks = Vec::new();
+                if definition.pitch_variation > 0f64 {
+                    for i in 0..PITCH_VARIANTS {
+                        let offset = i as f64 / (PITCH_VARIANTS - 1) as f64 * 2f64 - 1f64;
+                        if offset != 0f64 {
+                            if let Some(variant) = pitched_copy(&chunk, 1f64 + offset * definition.pitch_variation) {
+                                chunks.push(variant);
+                            }
+                        }
+                    }
+                }
+                chunks.push(chunk);
+
+                Some((id, Sound { definition, chunks }))
+            })
+            .collect::<HashMap<String, Sound>>();
+
+        let mut bus_volumes = HashMap::new();
+        for bus in [Bus::Sfx, Bus::Ui, Bus::Voice, Bus::Music].iter() {
+            bus_volumes.insert(*bus, MAX_VOLUME);
+        }
+
+        Self {
+            music: MusicDirector::load(music_dir),
+            sounds,
+            voices: vec![Voice::default(); MIXER_CHANNELS as usize],
+            bus_volumes,
+            plays: 0,
+            rng: SmallRng::from_entropy(),
+        }
+    }
+
+    pub fn set_volumes(&mut self, settings: &Settings) {
+        for bus in [Bus::Sfx, Bus::Ui, Bus::Voice, Bus::Music].iter() {
+            self.bus_volumes.insert(*bus, settings.bus_volume(*bus));
+        }
+        self.music.set_volume(self.bus_volumes[&Bus::Music]);
+    }
+
+    pub fn play(&mut self, sound_id: &str) {
+        self.release_finished();
+
+        let sound = match self.sounds.get(sound_id) {
+            Some(sound) => sound,
+            None => return,
+        };
+        let definition = &sound.definition;
+
+        let channel = match self.pick_voice(sound_id, definition) {
+            Some(channel) => channel,
+            None => return,
+        };
+
+        let chunk = &sound.chunks[self.rng.gen_range(0..sound.chunks.len())];
+        let variation = 1f64 - self.rng.gen::<f64>() * definition.volume_variation;
+        let volume = (self.bus_volumes[&definition.bus] as f64 * variation) as i32;
+
+        let mixer_channel = Channel(channel as i32);
+        mixer_channel.halt();
+        mixer_channel.set_volume(volume);
+        if let Err(e) = mixer_channel.play(chunk, 0) {
+            println!("failed to play {} {:?}", sound_id, e);
+            return;
+        }
+
+        self.plays += 1;
+        self.voices[channel] = Voice {
+            sound_id: Some(sound_id.to_string()),
+            priority: definition.priority,
+            started: self.plays,
+        };
+    }
+
+    fn release_finished(&mut self) {
+        for (channel, voice) in self.voices.iter_mut().enumerate() {
+            if voice.sound_id.is_some() && !Channel(channel as i32).is_playing() {
+                *voice = Voice::default();
+            }
+        }
+    }
+
+    fn pick_voice(&self, sound_id: &str, definition: &SoundDefinition) -> Option<usize> {
+        let playing_voices = self.voices.iter().enumerate()
+            .filter(|(_, voice)| {voice.sound_id.is_some()});
+
+        //at the instance limit the oldest copy is restarted instead of stacking another one
+        let copies = playing_voices.clone()
+            .filter(|(_, voice)| {voice.sound_id.as_deref() == Some(sound_id)})
+            .collect::<Vec<(usize, &Voice)>>();
+        if copies.len() >= definition.max_instances.max(1) {
+            return copies.iter().min_by_key(|(_, voice)| {voice.started}).map(|(channel, _)| {*channel});
+        }
+
+        if let Some(free) = self.voices.iter().position(|voice| {voice.sound_id.is_none()}) {
+            return Some(free);
+        }
+
+        //steal the least important and then oldest voice, never one that matters more than the new sound
+        playing_voices
+            .filter(|(_, voice)| {voice.priority <= definition.priority})
+            .min_by_key(|(_, voice)| {(voice.priority, voice.started)})
+            .map(|(channel, _)| {channel})
+    }
+}
+
+//sdl mixer has no pitch control, variants are resampled copies of the decoded chunk
+//chunks are converted to the device format when loaded, only signed 16 bit devices get variants
+fn pitched_copy(chunk: &Chunk, pitch: f64) -> Option<Chunk> {
+    let (_frequency, format, channels) = query_spec().ok()?;
+    if format != AUDIO_S16LSB || channels <= 0 {
+        return None;
+    }
+    let channels = channels as usize;
+    // SAFETY: abuf holds alen bytes owned by the chunk, which outlives this borrow,
+    // and the device format checked above means they are little-endian i16 samples
+    let samples = unsafe {
+        let raw = &*chunk.raw;
+        std::slice::from_raw_parts(raw.abuf as *const i16, raw.alen as usize / 2)
+    };
+    let frames = samples.len() / channels;
+    if frames == 0 {
+        return None;
+    }
+
+    let new_frames = (frames as f64 / pitch) as usize;
+    let mut resampled: Vec<i16> = Vec::with_capacity(new_frames * channels);
+    for frame in 0..new_frames {
+        let position = frame as f64 * pitch;
+        let index = (position as usize).min(frames - 1);
+        let next = (index + 1).min(frames - 1);
+        let t = position - index as f64;
+        for channel in 0..channels {
+            let a = i16::from_le(samples[index * channels + channel]) as f64;
+            let b = i16::from_le(samples[next * channels + channel]) as f64;
+            resampled.push(((a + (b - a) * t) as i16).to_le());
+        }
+    }
+
+    let mut pitched = Chunk::from_raw_buffer(resampled.into_boxed_slice()).ok()?;
+    pitched.set_volume(chunk.get_volume());
+    Some(pitched)
+}
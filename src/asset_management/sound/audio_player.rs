@@ -1,25 +1,7 @@
 use std::path::Path;
 
-use sdl2::mixer::{Channel, Chunk};
+use sdl2::mixer::Chunk;
 
 pub fn load_from_file(sound_file: &Path) -> Result<Chunk, String> {
     Chunk::from_file(sound_file)
 }
-
-pub fn play_sound(sound_chunk: &Chunk) {
-    let channel_to_play = Channel::all();
-    if !channel_to_play.is_playing(){
-        channel_to_play.play(&sound_chunk, 0).unwrap();
-    } else {
-        channel_to_play.halt();
-        channel_to_play.play(&sound_chunk, 0).unwrap();
-    }
-}
-
-pub fn play_sound_unsafe(sound_chunk: &Chunk) {
-    Channel::all().play(&sound_chunk, 0).unwrap();
-}
-
-pub fn play_sound_skip(sound_chunk: &Chunk, skip: i32) {
-    Channel::all().fade_in(&sound_chunk, 0, skip).unwrap();
-}
\ No newline at end of file
@@ -1,8 +1,15 @@
 use sdl2::mixer::{init, open_audio};
 use sdl2::mixer::{InitFlag as AudioInitFlag, Sdl2MixerContext, AUDIO_S16LSB, DEFAULT_CHANNELS};
 
+pub mod audio_manager;
 pub mod audio_player;
-pub mod music_player;
+pub mod music_director;
+pub mod sound_bank;
+
+//every channel is a voice of the audio manager
+pub const MIXER_CHANNELS: i32 = 24;
+//reserved after the voices, half for the track playing and half for the one fading out
+pub const MUSIC_CHANNELS: i32 = 8;
 
 pub fn init_sound() -> Result<Sdl2MixerContext, String> {
     let frequency = 44_100;
@@ -14,7 +21,7 @@ pub fn init_sound() -> Result<Sdl2MixerContext, String> {
     let mixer_context =
         init(AudioInitFlag::MP3 | AudioInitFlag::FLAC | AudioInitFlag::MOD | AudioInitFlag::OGG);
 
-    sdl2::mixer::allocate_channels(4);
+    sdl2::mixer::allocate_channels(MIXER_CHANNELS + MUSIC_CHANNELS);
 
     mixer_context
 }
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use sdl2::mixer::{Channel, Chunk, Fading, MAX_VOLUME};
+
+use super::{MIXER_CHANNELS, MUSIC_CHANNELS, audio_player};
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct MusicLayer {
+    pub file: String,
+    //the layer comes in once the combat intensity reaches this, the first layer should be at 0
+    #[serde(default)]
+    pub intensity: f64,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct MusicTrack {
+    pub layers: Vec<MusicLayer>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct MusicConfig {
+    pub crossfade_ms: i32,
+    //volume per second a layer moves towards its target when the intensity changes
+    pub layer_fade_speed: f64,
+    pub tracks: HashMap<String, MusicTrack>,
+    //played for scenes whose track is not in the config, so they keep the music going instead of restarting it
+    #[serde(default)]
+    pub fallback_track: Option<String>,
+}
+
+struct DeckLayer {
+    channel: Channel,
+    intensity: f64,
+    //0 to 1, scaled by the music volume
+    volume: f64,
+}
+
+//one track playing, every layer loops on its own channel so they stay in sync
+struct Deck {
+    track_id: String,
+    layers: Vec<DeckLayer>,
+}
+
+//music is played as looping chunks on reserved channels instead of sdl Music,
+//that way two tracks can crossfade and a track can have layers
+pub struct MusicDirector {
+    config: MusicConfig,
+    chunks: HashMap<String, Chunk>,
+    //decks alternate between the two halves of the music channels
+    deck: Option<Deck>,
+    use_upper_half: bool,
+    intensity: f64,
+    volume: i32,
+}
+
+impl MusicDirector {
+    pub fn load(dir: String) -> Self {
+        println!("loading {}", dir);
+        let json_string = fs::read_to_string(dir).unwrap();
+        let config = serde_json::from_str::<MusicConfig>(&json_string).unwrap();
+
+        let mut chunks = HashMap::new();
+        for track in config.tracks.values() {
+            for layer in track.layers.iter() {
+                if chunks.contains_key(&layer.file) {
+                    continue;
+                }
+                match audio_player::load_from_file(Path::new(&layer.file)) {
+                    Ok(chunk) => {chunks.insert(layer.file.clone(), chunk);},
+                    Err(e) => println!("Cannot load music file {}: {:?}", layer.file, e),
+                }
+            }
+        }
+
+        Self {
+            config,
+            chunks,
+            deck: None,
+            use_upper_half: false,
+            intensity: 0f64,
+            volume: MAX_VOLUME,
+        }
+    }
+
+    pub fn set_volume(&mut self, volume: i32) {
+        self.volume = volume;
+        if let Some(deck) = &self.deck {
+            for layer in deck.layers.iter() {
+                if let Fading::NoFading = layer.channel.get_fading() {
+                    layer.channel.set_volume(self.channel_volume(layer.volume));
+                }
+            }
+        }
+    }
+
+    //crossfades into the track, asking for the one already playing does nothing
+    pub fn play_track(&mut self, track_id: &str) {
+        let track_id = match (self.config.tracks.contains_key(track_id), &self.config.fallback_track) {
+            (false, Some(fallback)) => fallback.clone(),
+            _ => track_id.to_string(),
+        };
+        if self.deck.as_ref().map(|deck| {deck.track_id == track_id}).unwrap_or(false) {
+            return;
+        }
+        let track = match self.config.tracks.get(&track_id) {
+            Some(track) => track.clone(),
+            None => {
+                println!("no music track {}", track_id);
+                return;
+            }
+        };
+
+        let fade_ms = self.config.crossfade_ms;
+        if let Some(old_deck) = self.deck.take() {
+            for layer in old_deck.layers.iter() {
+                layer.channel.fade_out(fade_ms);
+            }
+        }
+
+        self.use_upper_half ^= true;
+        self.intensity = 0f64;
+
+        let half = MUSIC_CHANNELS / 2;
+        let first_channel = MIXER_CHANNELS + if self.use_upper_half { half } else { 0 };
+        let mut layers = Vec::new();
+        for (i, layer) in track.layers.iter().enumerate().take(half as usize) {
+            let chunk = match self.chunks.get(&layer.file) {
+                Some(chunk) => chunk,
+                None => continue,
+            };
+            let channel = Channel(first_channel + i as i32);
+            let volume = if layer.intensity <= self.intensity { 1f64 } else { 0f64 };
+            channel.halt();
+            channel.set_volume(self.channel_volume(volume));
+            if let Err(e) = channel.fade_in(chunk, -1, fade_ms) {
+                println!("failed to play music {} {:?}", layer.file, e);
+                continue;
+            }
+            layers.push(DeckLayer {
+                channel,
+                intensity: layer.intensity,
+                volume,
+            });
+        }
+
+        self.deck = Some(Deck {
+            track_id,
+            layers,
+        });
+    }
+
+    pub fn set_intensity(&mut self, intensity: f64) {
+        self.intensity = intensity.max(0f64).min(1f64);
+    }
+
+    //moves the layers towards what the intensity asks for, only needed while the intensity can change
+    pub fn update(&mut self, dt: f64) {
+        let intensity = self.intensity;
+        let step = self.config.layer_fade_speed * dt;
+        let volume = self.volume;
+        let deck = match self.deck.as_mut() {
+            Some(deck) => deck,
+            None => return,
+        };
+
+        for layer in deck.layers.iter_mut() {
+            let target = if layer.intensity <= intensity { 1f64 } else { 0f64 };
+            if layer.volume < target {
+                layer.volume = (layer.volume + step).min(target);
+            } else {
+                layer.volume = (layer.volume - step).max(target);
+            }
+            //sdl owns the volume while a crossfade is running
+            if let Fading::NoFading = layer.channel.get_fading() {
+                layer.channel.set_volume((volume as f64 * layer.volume) as i32);
+            }
+        }
+    }
+
+    fn channel_volume(&self, layer_volume: f64) -> i32 {
+        (self.volume as f64 * layer_volume) as i32
+    }
+}
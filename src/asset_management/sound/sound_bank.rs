@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum Bus {
+    Sfx,
+    Ui,
+    Voice,
+    Music,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SoundDefinition {
+    pub file: String,
+    pub bus: Bus,
+    //chunk volume from 0 to 128, balances the sound against the rest of its bus
+    pub volume: i32,
+    //when every channel is busy a sound can only take the place of one with the same or lower priority
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_max_instances")]
+    pub max_instances: usize,
+    //how far the pitch and volume can drift from the original on each play, 0.1 = 10%
+    #[serde(default)]
+    pub pitch_variation: f64,
+    #[serde(default)]
+    pub volume_variation: f64,
+}
+
+fn default_max_instances() -> usize {
+    2
+}
+
+pub fn load_sound_bank(dir: String) -> HashMap<String, SoundDefinition> {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(&dir).unwrap();
+    serde_json::from_str::<HashMap<String, SoundDefinition>>(&json_string)
+        .unwrap_or_else(|e| panic!("invalid sound bank {}: {}", dir, e))
+}
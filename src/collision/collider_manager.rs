@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use parry2d::na::Vector2;
 
-use crate::engine_types::{animation::ColliderAnimation, animator::Animator, collider::Collider, sprite_data::SpriteData};
+use crate::engine_types::{animation::ColliderAnimation, animator::Animator, collider::{Collider, ColliderType}, sprite_data::SpriteData};
 
 #[derive(Clone)]
 pub struct ColliderManager {
@@ -27,6 +27,15 @@ impl ColliderManager {
                 self.init_colliders(animator); //TODO <- running this everyframe might not be the best idea
                 
                 self.update_colliders_pos(flipped, position, animator, sprite_data.get(&sprite_handle).unwrap());
+
+                //outside the active frames the hitboxes are just drawn, they dont hit
+                if let Some(frame_data) = animation.frame_data.as_ref() {
+                    if !frame_data.is_active(animator.animation_index) {
+                        self.colliders.iter_mut()
+                            .filter(|c| c.collider_type == ColliderType::Hitbox)
+                            .for_each(|c| c.enabled = false);
+                    }
+                }
             }
         }
     }
@@ -1,7 +1,7 @@
 use parry2d::{bounding_volume::BoundingVolume, math::Point, math::Real, na::{Isometry2, Point2, Vector2}, query::{self, Contact}, shape::Cuboid};
-use sdl2::{pixels::Color, rect::Rect, render::TextureQuery};
+use sdl2::{pixels::Color, rect::Rect};
 
-use crate::{asset_management::{asset_holders::LevelAssets, common_assets::CommonAssets, sound::audio_player, vfx::particle::Particle}, ecs_system::enemy_components::{Health}, engine_types::{animator::Animator, collider::{Collider, ColliderType}}, game_logic::{characters::{Attack}, game::Game, movement_controller::MovementController}, utils::math_sign::Sign};
+use crate::{asset_management::{asset_holders::VfxData, vfx::particle::Particle}, ecs_system::enemy_components::{Health}, engine_types::{animator::Animator, collider::{Collider, ColliderType}}, game_logic::{characters::{Attack, player::EntityState}, game::Game, movement_controller::MovementController}, utils::math_sign::Sign};
 
 use crate::ecs_system::enemy_systems::take_damage;
 
@@ -31,6 +31,30 @@ pub fn detect_hit(hitting_colliders: &Vec<Collider>, hurting_colliders: &Vec<Col
     None
 }
  
+pub fn detect_grab(grabbing_colliders: &Vec<Collider>, grabbed_colliders: &Vec<Collider>) -> bool {
+    let has_grabbablebox = grabbed_colliders.iter().any(|c| c.collider_type == ColliderType::Grabbablebox && c.enabled);
+    let grabbable_type = if has_grabbablebox { ColliderType::Grabbablebox } else { ColliderType::Pushbox };
+
+    grabbing_colliders.iter()
+        .filter(|&c| c.collider_type == ColliderType::Grabbox && c.enabled)
+        .any(|grabbox| {
+            grabbed_colliders.iter()
+                .filter(|&c| c.collider_type == grabbable_type && c.enabled)
+                .any(|grabbable| grabbox.aabb.intersects(&grabbable.aabb))
+        })
+}
+
+//a thrown body hits whoever it crashes into
+pub fn detect_body_hit(thrown_colliders: &Vec<Collider>, hurting_colliders: &Vec<Collider>) -> bool {
+    thrown_colliders.iter()
+        .filter(|&c| c.collider_type == ColliderType::Pushbox && c.enabled)
+        .any(|body| {
+            hurting_colliders.iter()
+                .filter(|&c| c.collider_type == ColliderType::Hurtbox && c.enabled)
+                .any(|hurtbox| body.aabb.intersects(&hurtbox.aabb))
+        })
+}
+ 
 fn contact(p1_collider: &Collider, p2_collider: &Collider) -> Option<Contact> {
     let cuboid1 = Cuboid::new(p1_collider.aabb.half_extents());
     let cuboid2 = Cuboid::new(p2_collider.aabb.half_extents());
@@ -52,11 +76,11 @@ fn contact(p1_collider: &Collider, p2_collider: &Collider) -> Option<Contact> {
 pub fn hit_opponent(
     attack: &Attack, 
     time: f64, 
-    general_assets: &CommonAssets, 
+    sounds: &mut Vec<String>, 
     attacker: &MovementController, 
     receiver: (&mut Health, &mut Vector2<f64>, &mut Animator, &mut MovementController)){
     
-    audio_player::play_sound(general_assets.sound_effects.get("hit").unwrap());
+    sounds.push("hit".to_string());
     take_damage(receiver.0, attack.damage, receiver.3, receiver.2);                                               
     receiver.3.state_update(receiver.2, false);     
     
@@ -70,11 +94,11 @@ pub fn hit_opponent(
 
 pub fn opponent_blocked(attack: &Attack, 
     time: f64, 
-    general_assets: &CommonAssets, 
+    sounds: &mut Vec<String>, 
     attacker: &MovementController, 
     receiver: (&mut Vector2<f64>, &mut MovementController)){
     
-    audio_player::play_sound(general_assets.sound_effects.get("block").unwrap());
+    sounds.push("block".to_string());
     let dir_to_push = if attacker.is_airborne {                          
         attacker.direction_at_jump_time
     } else {
@@ -83,13 +107,9 @@ pub fn opponent_blocked(attack: &Attack,
     receiver.1.knock_back(receiver.0, attack.push_back * dir_to_push.sign() as f64, time); 
 }
 
-pub fn hit_particles(particles: &mut Vec<Particle>, point: Point2<f32>, hit_particle: &str, assets: &LevelAssets) {
-    let texture_id = &assets.hit_effect_animations.get(hit_particle).unwrap().sprites[0].1;
-    let TextureQuery { width, height, .. } = assets
-                            .hit_effect_textures
-                            .get(texture_id)
-                            .unwrap()
-                            .query();
+pub fn hit_particles(particles: &mut Vec<Particle>, point: Point2<f32>, hit_particle: &str, vfx: &VfxData) {
+    let texture_id = &vfx.animations.get(hit_particle).unwrap().sprites[0].1;
+    let (width, height) = vfx.texture_size(texture_id);
 
     let texture_width = width * 2;
     let texture_height = height * 2;
@@ -113,7 +133,14 @@ pub fn hit_particles(particles: &mut Vec<Particle>, point: Point2<f32>, hit_part
 pub fn did_sucessfully_block(point: Point2<f32>, blocking_pos: Vector2<f64>, blocking_controller: &MovementController) -> bool {                   //MovementController
     
     let facing_correct_dir = (point.x > blocking_pos.x as f32 && blocking_controller.facing_dir > 0) || 
-    (point.x < blocking_pos.x as f32 && !blocking_controller.facing_dir > 0);
+    (point.x < blocking_pos.x as f32 && blocking_controller.facing_dir < 0);
+
+    blocking_controller.is_blocking && facing_correct_dir && !blocking_controller.is_guard_broken()
+}
 
-    blocking_controller.is_blocking 
+//the attacker gets staggered and the hit does nothing
+pub fn parried(sounds: &mut Vec<String>, attacker: (&mut MovementController, &mut Animator)) {
+    sounds.push("parry".to_string());
+    attacker.0.is_attacking = false;
+    attacker.0.set_entity_state(EntityState::Hurt, attacker.1);
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use crate::{asset_management::{asset_holders::{EntityData, LevelAssets}, common_assets::CommonAssets, vfx::particle::Particle}, challenges::ChallengeManager, ecs_system::{enemy_components::{AIType, Health}, enemy_manager::EnemyManager}, engine_types::animator::Animator, game_logic::{characters::{Attack, player::Player}, combo_string::ComboCounter, movement_controller::MovementController}, rendering::camera::Camera};
+use crate::{asset_management::{asset_holders::{EntityData, VfxData}, vfx::particle::Particle}, challenges::ChallengeManager, ecs_system::{enemy_components::{AIType, Health}, enemy_manager::EnemyManager}, engine_types::animator::Animator, game_logic::{characters::{Attack, player::Player}, combo_string::ComboCounter, movement_controller::MovementController}, rendering::camera::Camera, telemetry::TelemetryEvent};
 
-use super::{collider_manager::ColliderManager, collision_attack_resolution::{detect_hit, did_sucessfully_block, hit_opponent, hit_particles, opponent_blocked}};
+use super::{collider_manager::ColliderManager, collision_attack_resolution::{detect_hit, did_sucessfully_block, hit_opponent, hit_particles, opponent_blocked, parried}};
 
 // zip enumerate + hp + colliders + AIType -> check if hp > 0
 // for collider A if AIType == Enemy
@@ -8,19 +8,24 @@ use super::{collider_manager::ColliderManager, collision_attack_resolution::{det
 // match detect_hit(&colliders.colliders, player_colliders)
 // collect vector of -> ids of entities that collided, collider name to get attack damage
 
-pub fn calculate_hits(player: &mut Player,
+pub fn calculate_hits(players: &mut [&mut Player],
     enemy_manager: &mut EnemyManager,
     particles: &mut Vec<Particle>,
     hit_stop: &mut i32, 
     logic_timestep: f64, 
-    general_assets: &CommonAssets, 
-    level_assets: &LevelAssets, 
+    sounds: &mut Vec<String>, 
+    vfx: &VfxData, 
     player_data: &EntityData,
     combo: &mut ComboCounter,
     challenges: &mut ChallengeManager,
-    camera: &mut Camera) {
+    camera: &mut Camera,
+    friendly_fire: bool,
+    telemetry: &mut Vec<TelemetryEvent>) {
 
     let n_entities = enemy_manager.collider_components.len();
+    //players go after the enemies, entity n_entities + i is players[i]
+    let player_index = |entity: usize| -> Option<usize> { entity.checked_sub(n_entities) };
+
     let zip = enemy_manager.health_components.iter().enumerate()
     .zip(enemy_manager.collider_components.iter_mut())
     .zip(enemy_manager.ai_type_components.iter());
@@ -35,14 +40,17 @@ pub fn calculate_hits(player: &mut Player,
         None
     }).collect::<Vec<(usize, &mut ColliderManager, &AIType)>>();
 
-    entities.push((n_entities, &mut player.collision_manager, &AIType::Allied));
+    for (i, player) in players.iter_mut().enumerate() {
+        entities.push((n_entities + i, &mut player.collision_manager, &AIType::Allied));
+    }
 
     let mut collisions = Vec::new();
     for entity_hitting in entities.iter() {
         for entity_being_hit in entities.iter() {
             let same_entity = entity_hitting.0 == entity_being_hit.0;
             let same_team = entity_hitting.2 == entity_being_hit.2;
-            if same_team || same_entity {
+            let players_fighting = friendly_fire && entity_hitting.0 >= n_entities && entity_being_hit.0 >= n_entities;
+            if (same_team && !players_fighting) || same_entity {
                 continue;
             }
             match detect_hit(&entity_hitting.1.colliders, &entity_being_hit.1.colliders) {
@@ -54,57 +62,49 @@ pub fn calculate_hits(player: &mut Player,
         }
     }
 
-    let mut enemies_hit = Vec::new();
+    let mut players_hurt = Vec::new();
     for collision in collisions.iter() {
-        let is_player_hitting = collision.0 == enemy_manager.collider_components.len();
-        let is_player_hurting = collision.1 == enemy_manager.collider_components.len();
+        let hitting_player = player_index(collision.0);
+        let hurting_player = player_index(collision.1);
 
-        let mut hitting_colliders = if !is_player_hitting {
-            enemy_manager.collider_components[collision.0].clone().unwrap()
-        } else {
-            player.collision_manager.clone()
+        let mut hitting_colliders = match hitting_player {
+            None => enemy_manager.collider_components[collision.0].clone().unwrap(),
+            Some(p) => players[p].collision_manager.clone(),
         };
 
-        let mut hurting_colliders = if !is_player_hurting {
-            enemy_manager.collider_components[collision.1].clone().unwrap()
-        } else {
-            player.collision_manager.clone()
+        let mut hurting_colliders = match hurting_player {
+            None => enemy_manager.collider_components[collision.1].clone().unwrap(),
+            Some(p) => players[p].collision_manager.clone(),
         };
 
-        let mut hitting_mov = if !is_player_hitting {
-            enemy_manager.movement_controller_components[collision.0].clone().unwrap()
-        } else {
-            player.controller.clone()
+        let mut hitting_mov = match hitting_player {
+            None => enemy_manager.movement_controller_components[collision.0].clone().unwrap(),
+            Some(p) => players[p].controller.clone(),
         };
 
-        let mut hurting_mov = if !is_player_hurting {
-            enemy_manager.movement_controller_components[collision.1].clone().unwrap()
-        } else {
-            player.controller.clone()
+        let mut hurting_mov = match hurting_player {
+            None => enemy_manager.movement_controller_components[collision.1].clone().unwrap(),
+            Some(p) => players[p].controller.clone(),
         };
 
-        let mut hitting_animator = if !is_player_hitting {
-            enemy_manager.animator_components[collision.0].clone().unwrap()
-        } else {
-            player.animator.clone()
+        let mut hitting_animator = match hitting_player {
+            None => enemy_manager.animator_components[collision.0].clone().unwrap(),
+            Some(p) => players[p].animator.clone(),
         };
 
-        let mut hurting_animator = if !is_player_hurting {
-            enemy_manager.animator_components[collision.1].clone().unwrap()
-        } else {
-            player.animator.clone()
+        let mut hurting_animator = match hurting_player {
+            None => enemy_manager.animator_components[collision.1].clone().unwrap(),
+            Some(p) => players[p].animator.clone(),
         };
 
-        let mut hurt_pos = if !is_player_hurting {
-            enemy_manager.positions_components[collision.1].as_mut().unwrap().0
-        } else {
-            player.position
+        let mut hurt_pos = match hurting_player {
+            None => enemy_manager.positions_components[collision.1].as_mut().unwrap().0,
+            Some(p) => players[p].position,
         };
 
-        let mut hurt_hp = if !is_player_hurting {
-            enemy_manager.health_components[collision.1].clone().unwrap()
-        } else {
-            player.hp.clone()
+        let mut hurt_hp = match hurting_player {
+            None => enemy_manager.health_components[collision.1].clone().unwrap(),
+            Some(p) => players[p].hp.clone(),
         };
 
         if (hurting_mov.ground_height - hitting_mov.ground_height).abs() > 50 {
@@ -119,11 +119,29 @@ pub fn calculate_hits(player: &mut Player,
                 .attacks
                 .get(&collision.3.replace("?", ""))
                 .unwrap().clone();
+            if hitting_player.is_none() {
+                if let Some(damage_modifier) = &enemy_manager.damage_modifier_components[collision.0] {
+                    attack.damage = (attack.damage as f64 * damage_modifier.0).round() as i32;
+                }
+            }
+            //player vs player hits only hurt, they dont feed combos, challenges or on hit items
+            let player_hitting_enemy = hitting_player.is_some() && hurting_player.is_none();
+
+            let attack_name = collision.3.replace("?", "");
+            //the attacker's frame data can override the stun of the attack
+            let (hit_stun, block_stun) = match hitting_animator.current_animation.as_ref().and_then(|animation| {animation.frame_data.as_ref()}) {
+                Some(frame_data) => (frame_data.hit_stun.unwrap_or(attack.stun_on_hit), frame_data.block_stun.unwrap_or(attack.stun_on_block)),
+                None => (attack.stun_on_hit, attack.stun_on_block),
+            };
             if !did_sucessfully_block(collision.2, hurt_pos, &mut hurting_mov){
                 
-                enemies_hit.push(collision.1 as i32);
+                if let Some(p) = hurting_player {
+                    players_hurt.push((p, if hitting_player.is_none() { collision.0 as i32 } else { -1 }));
+                }
+                let hp_before = hurt_hp.0;
 
-                if is_player_hitting {
+                if let (true, Some(p)) = (player_hitting_enemy, hitting_player) {
+                    let player = &mut *players[p];
                     let mut p_on_hits = player.events.on_hit.clone();
                     for onhit in p_on_hits.iter_mut() {
                         onhit.0(player, enemy_manager, collision.1 as i32, &mut onhit.1, &mut attack);
@@ -133,14 +151,38 @@ pub fn calculate_hits(player: &mut Player,
                 hit_opponent(
                     &attack,
                     logic_timestep,
-                    &general_assets, 
+                    sounds, 
                     &hitting_mov, (&mut hurt_hp, &mut hurt_pos, &mut hurting_animator, &mut hurting_mov));
+                hurting_mov.stun_frames = hit_stun;
 
                 if let Some(on_hit) = attack.on_hit {
                     on_hit(&attack, &mut hurting_colliders, &mut hurting_mov, &mut hurting_animator);
                 }
 
-                if is_player_hitting {
+                match (hitting_player, hurting_player) {
+                    (Some(p), None) => {
+                        if let Some(reward) = enemy_manager.reward_components[collision.1].as_mut() {
+                            reward.last_hit_by = Some(players[p].id);
+                        }
+                        telemetry.push(TelemetryEvent::Hit {
+                            player: players[p].id,
+                            attack: attack_name,
+                            damage: hp_before - hurt_hp.0,
+                            enemy: enemy_name(enemy_manager, collision.1),
+                        })
+                    },
+                    (_, Some(p)) => telemetry.push(TelemetryEvent::DamageTaken {
+                        player: players[p].id,
+                        amount: hp_before - hurt_hp.0,
+                        source: match hitting_player {
+                            Some(_) => "player".to_string(),
+                            None => enemy_name(enemy_manager, collision.0),
+                        },
+                    }),
+                    _ => {},
+                }
+
+                if player_hitting_enemy {
                     camera.shake();
                     combo.increment_combo();
                     
@@ -148,78 +190,107 @@ pub fn calculate_hits(player: &mut Player,
                         on_hit.0(combo.counter as f32, &mut on_hit.1);
                     }
                 }
-                hit_particles(particles, collision.2, "special_hit", &level_assets);
+                hit_particles(particles, collision.2, "special_hit", vfx);
                 *hit_stop = 10;
             } else {
-                opponent_blocked(
-                    &attack,
-                    logic_timestep,
-                    &general_assets, 
-                    &hitting_mov, (&mut hurt_pos, &mut hurting_mov));
-                hit_particles(particles, collision.2, "block", &level_assets);
-                *hit_stop = 5;
+                match (hitting_player, hurting_player) {
+                    (Some(p), None) => telemetry.push(TelemetryEvent::Block {
+                        player: players[p].id,
+                        attack: attack_name,
+                        blocked_by_player: false,
+                        enemy: enemy_name(enemy_manager, collision.1),
+                    }),
+                    (None, Some(p)) => telemetry.push(TelemetryEvent::Block {
+                        player: players[p].id,
+                        attack: attack_name,
+                        blocked_by_player: true,
+                        enemy: enemy_name(enemy_manager, collision.0),
+                    }),
+                    _ => {},
+                }
+                if hurting_mov.parry_window > 0.0 {
+                    parried(sounds, (&mut hitting_mov, &mut hitting_animator));
+                    hurting_mov.parry_landed();
+                    hitting_mov.stun_frames = hit_stun;
+                    hit_particles(particles, collision.2, "block", vfx);
+                    *hit_stop = 12;
+                } else {
+                    opponent_blocked(
+                        &attack,
+                        logic_timestep,
+                        sounds, 
+                        &hitting_mov, (&mut hurt_pos, &mut hurting_mov));
+                    hurting_mov.stun_frames = block_stun;
+                    hit_particles(particles, collision.2, "block", vfx);
+                    *hit_stop = 5;
+
+                    if hurting_mov.drain_guard(attack.guard_damage, &mut hurting_animator) {
+                        sounds.push("guard_break".to_string());
+                        *hit_stop = 10;
+                    }
+                }
             }
 
             //re-save clonned components
             {
-                if !is_player_hitting {
-                    enemy_manager.collider_components[collision.0] = Some(hitting_colliders)
-                } else {
-                    player.collision_manager = hitting_colliders;
+                match hitting_player {
+                    None => enemy_manager.collider_components[collision.0] = Some(hitting_colliders),
+                    Some(p) => players[p].collision_manager = hitting_colliders,
                 };
 
-                if !is_player_hurting {
-                    enemy_manager.collider_components[collision.1] = Some(hurting_colliders)
-                } else {
-                    player.collision_manager = hurting_colliders;
+                match hurting_player {
+                    None => enemy_manager.collider_components[collision.1] = Some(hurting_colliders),
+                    Some(p) => players[p].collision_manager = hurting_colliders,
                 };
 
-                if !is_player_hitting {
-                    enemy_manager.movement_controller_components[collision.0] = Some(hitting_mov);
-                } else {
-                    player.controller = hitting_mov;
-                }
+                match hitting_player {
+                    None => enemy_manager.movement_controller_components[collision.0] = Some(hitting_mov),
+                    Some(p) => players[p].controller = hitting_mov,
+                };
         
-                if !is_player_hurting {
-                    enemy_manager.movement_controller_components[collision.1] = Some(hurting_mov)
-                } else {
-                    player.controller = hurting_mov;
+                match hurting_player {
+                    None => enemy_manager.movement_controller_components[collision.1] = Some(hurting_mov),
+                    Some(p) => players[p].controller = hurting_mov,
                 };
 
-                if !is_player_hitting {
-                    enemy_manager.animator_components[collision.0] = Some(hitting_animator);
-                } else {
-                    player.animator = hitting_animator;
-                }
+                match hitting_player {
+                    None => enemy_manager.animator_components[collision.0] = Some(hitting_animator),
+                    Some(p) => players[p].animator = hitting_animator,
+                };
         
-                if !is_player_hurting {
-                    enemy_manager.animator_components[collision.1] = Some(hurting_animator)
-                } else {
-                    player.animator = hurting_animator;
-                }
+                match hurting_player {
+                    None => enemy_manager.animator_components[collision.1] = Some(hurting_animator),
+                    Some(p) => players[p].animator = hurting_animator,
+                };
 
-                if !is_player_hurting {
-                    enemy_manager.positions_components[collision.1].as_mut().unwrap().0 = hurt_pos;
-                } else {
-                    player.position = hurt_pos;
+                match hurting_player {
+                    None => enemy_manager.positions_components[collision.1].as_mut().unwrap().0 = hurt_pos,
+                    Some(p) => players[p].position = hurt_pos,
                 };
         
-                if !is_player_hurting {
-                    enemy_manager.health_components[collision.1] = Some(hurt_hp);
-                } else {
-                    player.hp = hurt_hp;
+                match hurting_player {
+                    None => enemy_manager.health_components[collision.1] = Some(hurt_hp),
+                    Some(p) => players[p].hp = hurt_hp,
                 };
             }
 
         }
     }
 
-    let mut p_on_hurts = player.events.on_hurt.clone();
-    for &i in enemies_hit.iter() {
+    //on hurt runs on the player that got hit, with the enemy that hit them or -1 for another player
+    for &(p, hit_by) in players_hurt.iter() {
+        let player = &mut *players[p];
+        let mut p_on_hurts = player.events.on_hurt.clone();
         for onhurt in p_on_hurts.iter_mut() {
-            onhurt.0(player, enemy_manager, i, &mut onhurt.1);
+            onhurt.0(player, enemy_manager, hit_by, &mut onhurt.1);
         }
+        player.events.on_hurt = p_on_hurts;
     }
+}
 
-
+fn enemy_name(enemy_manager: &EnemyManager, entity: usize) -> String {
+    match &enemy_manager.reward_components[entity] {
+        Some(reward) => reward.archetype.clone(),
+        None => "unknown".to_string(),
+    }
 }
@@ -1,4 +1,5 @@
 pub mod collider_manager;
 pub mod collision_attack_resolution;
 pub mod collision_detection;
+pub mod pushbox_resolution;
 
@@ -0,0 +1,155 @@
+use sdl2::rect::Rect;
+
+use crate::{collision::collider_manager::ColliderManager, ecs_system::enemy_manager::EnemyManager, engine_types::collider::ColliderType, game_logic::{characters::player::{EntityState, Player}, movement_controller::MovementController}};
+
+//entities further apart than this on the depth axis walk past each other
+const PUSH_DEPTH: f64 = 24.0;
+//enemies only spread out this much of their overlap per step, so groups drift apart instead of snapping
+const CROWD_SEPARATION: f64 = 0.2;
+//share of the overlap the walking side keeps when it pushes someone standing still
+const WALKER_SHARE: f64 = 0.25;
+
+struct Body {
+    entity: usize,
+    mins: f64,
+    maxs: f64,
+    depth: f64,
+    walking: i8,
+    is_player: bool,
+    //how far the center can move left and right, players stay on screen and enemies inside the level
+    bounds: (f64, f64),
+}
+
+impl Body {
+    fn center(&self) -> f64 {
+        (self.mins + self.maxs) / 2.0
+    }
+}
+
+fn can_be_pushed(mov: &MovementController) -> bool {
+    !mov.is_airborne &&
+    mov.state != EntityState::Dead &&
+    mov.state != EntityState::Knocked &&
+    mov.state != EntityState::Dropped &&
+    mov.state != EntityState::Grabbing &&
+    mov.state != EntityState::Grabbed
+}
+
+fn pushbox(colliders: &ColliderManager) -> Option<(f64, f64)> {
+    colliders.colliders.iter()
+        .filter(|c| c.collider_type == ColliderType::Pushbox && c.enabled)
+        .last()
+        .map(|c| (c.aabb.mins.x as f64, c.aabb.maxs.x as f64))
+}
+
+fn shift_colliders(colliders: &mut ColliderManager, dx: f64) {
+    for collider in colliders.colliders.iter_mut() {
+        collider.aabb.mins.coords[0] += dx as f32;
+        collider.aabb.maxs.coords[0] += dx as f32;
+    }
+}
+
+//moves a body as far as its bounds allow and returns what was left over
+fn clamp_push(body: &Body, center: f64, dx: f64) -> f64 {
+    let room = if dx > 0.0 { (body.bounds.1 - center).max(0.0) } else { (body.bounds.0 - center).min(0.0) };
+    if dx.abs() > room.abs() { dx - room } else { 0.0 }
+}
+
+// same entity indexing as calculate_hits, enemies first and then players[i] is n_entities + i
+// overlapping pushboxes on the same depth get pushed apart along x
+pub fn resolve_pushboxes(players: &mut [&mut Player], enemy_manager: &mut EnemyManager, camera_rect: Rect, level_width: i32) {
+    let n_entities = enemy_manager.collider_components.len();
+    let mut bodies = Vec::new();
+
+    for i in 0..n_entities {
+        let alive = enemy_manager.health_components[i].as_ref().map_or(false, |hp| hp.0 > 0);
+        if let (true, Some(colliders), Some(pos), Some(mov)) = (alive, enemy_manager.collider_components[i].as_ref(), enemy_manager.positions_components[i].as_ref(), enemy_manager.movement_controller_components[i].as_ref()) {
+            if let (true, Some((mins, maxs))) = (can_be_pushed(mov), pushbox(colliders)) {
+                let half_width = (maxs - mins) / 2.0;
+                bodies.push(Body {
+                    entity: i,
+                    mins,
+                    maxs,
+                    depth: pos.0.y,
+                    walking: mov.walking_dir.x,
+                    is_player: false,
+                    bounds: (half_width, level_width as f64 - half_width),
+                });
+            }
+        }
+    }
+
+    for (i, player) in players.iter().enumerate() {
+        if let (true, Some((mins, maxs))) = (can_be_pushed(&player.controller), pushbox(&player.collision_manager)) {
+            //the camera keeps the position inside, the pushbox can sit a bit off it
+            let offset = (mins + maxs) / 2.0 - player.position.x;
+            bodies.push(Body {
+                entity: n_entities + i,
+                mins,
+                maxs,
+                depth: player.position.y,
+                walking: player.controller.walking_dir.x,
+                is_player: true,
+                bounds: (
+                    camera_rect.x() as f64 + player.character_width + offset,
+                    (camera_rect.x() + camera_rect.width() as i32) as f64 - player.character_width + offset
+                ),
+            });
+        }
+    }
+
+    let mut pushes = vec![0f64; bodies.len()];
+    for a in 0..bodies.len() {
+        for b in (a + 1)..bodies.len() {
+            let (body_a, body_b) = (&bodies[a], &bodies[b]);
+            let overlap = body_a.maxs.min(body_b.maxs) - body_a.mins.max(body_b.mins);
+            if overlap <= 0.0 || (body_a.depth - body_b.depth).abs() > PUSH_DEPTH {
+                continue;
+            }
+
+            //stacked on the exact same spot, the lower entity goes left
+            let dir_a = if body_a.center() < body_b.center() || (body_a.center() == body_b.center() && body_a.entity < body_b.entity) { -1.0 } else { 1.0 };
+            let amount = if body_a.is_player || body_b.is_player { overlap } else { overlap * CROWD_SEPARATION };
+
+            let a_walks_in = body_a.walking as f64 == -dir_a;
+            let b_walks_in = body_b.walking as f64 == dir_a;
+            let share_a = match (a_walks_in, b_walks_in) {
+                (true, false) => WALKER_SHARE,
+                (false, true) => 1.0 - WALKER_SHARE,
+                _ => 0.5,
+            };
+
+            let mut push_a = dir_a * amount * share_a;
+            let mut push_b = -dir_a * amount * (1.0 - share_a);
+
+            //cornered bodies cant give way, whoever is pressing into them takes the whole push
+            let leftover_a = clamp_push(body_a, body_a.center() + pushes[a], push_a);
+            push_a -= leftover_a;
+            push_b -= leftover_a;
+            let leftover_b = clamp_push(body_b, body_b.center() + pushes[b], push_b);
+            push_b -= leftover_b;
+            push_a -= leftover_b;
+
+            pushes[a] += push_a;
+            pushes[b] += push_b;
+        }
+    }
+
+    for (body, push) in bodies.iter().zip(pushes.into_iter()) {
+        if push == 0.0 {
+            continue;
+        }
+        match body.entity.checked_sub(n_entities) {
+            None => {
+                if let (Some(pos), Some(colliders)) = (enemy_manager.positions_components[body.entity].as_mut(), enemy_manager.collider_components[body.entity].as_mut()) {
+                    pos.0.x += push;
+                    shift_colliders(colliders, push);
+                }
+            },
+            Some(p) => {
+                players[p].position.x += push;
+                shift_colliders(&mut players[p].collision_manager, push);
+            },
+        }
+    }
+}
@@ -3,7 +3,31 @@ use std::{collections::HashMap, rc::Rc};
 use parry2d::na::Vector2;
 use sdl2::{keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, TextureCreator, TextureQuery}, ttf::Font, video::{Window, WindowContext}};
 
-use crate::{GameStateData, game_logic::{game::Game, items::{Item, ItemGround}}};
+use crate::{asset_management::asset_holders::EntityAnimations, game_logic::{factories::enemy_factory::EnemyArchetype, game::Game, items::{Item, ItemGround}}};
+
+//debug keys and console commands change the match outside of the inputs, replays redo them on the same frame
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum DebugAction {
+    SpawnEnemy { archetype: String, offset: f64 },
+    SpawnItem(i32),
+    HurtPlayer(i32),
+    AddCurrency(u32),
+}
+
+pub fn apply_debug_action(game: &mut Game, action: &DebugAction, items: &HashMap<i32, Item>, enemy_archetypes: &HashMap<String, EnemyArchetype>, enemy_animations: &HashMap<String, Rc<EntityAnimations>>) {
+    match action {
+        DebugAction::SpawnEnemy { archetype, offset } => {
+            if let Some(archetype) = enemy_archetypes.get(archetype) {
+                game.enemies.add_enemy(game.player.position + Vector2::new(*offset, 0f64), archetype, Rc::clone(enemy_animations.get(&archetype.sprite_set).unwrap()));
+            }
+        },
+        DebugAction::SpawnItem(item_id) => {
+            game.items_on_ground.push(ItemGround{ position: game.player.position + Vector2::new(200f64, 0f64), item: (*items.get(item_id).unwrap()).clone() });
+        },
+        DebugAction::HurtPlayer(damage) => game.player.hp.0 -= damage,
+        DebugAction::AddCurrency(amount) => game.player.currency += amount,
+    }
+}
 
 pub struct Console{
     pub up: bool,
@@ -24,8 +48,10 @@ impl Console {
         }
     }
 
-    pub fn run(&mut self, game: &mut Game, items: &HashMap<i32, Item>, game_state_data: &GameStateData) {
+    //only parses the command, the match applies and records it like the debug keys
+    pub fn run(&mut self) -> Option<DebugAction> {
         println!("spawn {}", self.command);
+        let mut action = None;
         if self.up {
             let split = self.command.split(" ").collect::<Vec<&str>>();
 
@@ -33,18 +59,18 @@ impl Console {
                 "I" => {
                     println!("spawn {}", self.command);
                     let item_id = split[1].parse::<i32>().unwrap();
-                    game.items_on_ground.push(ItemGround{ position: game.player.position + Vector2::new(200f64, 0f64), item: (*items.get(&item_id).unwrap()).clone() });
+                    action = Some(DebugAction::SpawnItem(item_id));
                 },
                 "E" => {
                     println!("spawn {}", self.command);
-                    let enemy_id = split[1].parse::<i32>().unwrap();
-                    game.enemies.add_enemy(game.player.position, Rc::clone(game_state_data.enemy_animations.get("ryu").unwrap()));
+                    action = Some(DebugAction::SpawnEnemy { archetype: split[1].to_lowercase(), offset: 0f64 });
                 },
                 _ => {}
             }
 
             self.command.clear();
         }
+        action
     }
 
     pub fn toggle(&mut self){
@@ -54,7 +80,7 @@ impl Console {
     pub fn render(&self, texture_creator: &TextureCreator<WindowContext>, canvas: &mut Canvas<Window>, font: &Font) {
 
         if self.up {
-            let screen_res = canvas.output_size().unwrap();
+            let screen_res = canvas.logical_size();
 
             canvas.set_draw_color(Color::RGB(20, 20, 25));
             let position = (0, 50);
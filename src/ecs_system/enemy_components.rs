@@ -1,7 +1,7 @@
 use parry2d::na::Vector2;
 use sdl2::rect::Rect;
 
-use crate::game_logic::inputs::game_inputs::GameAction;
+use crate::enemy_behaviour::behaviour_tree::{EnemyAction, Perception};
 
 
 
@@ -10,13 +10,28 @@ pub struct Health(pub i32);
 
 pub struct Position(pub Vector2<f64>);
 
+//multiplies the damage of every attack the entity lands
+pub struct DamageModifier(pub f64);
+
 pub struct Renderable {
     pub flipped: bool,
     pub rect: Rect,
 }
 
 pub trait Behaviour {
-    fn act(&mut self, delta: f64) -> Option<GameAction>;
+    fn act(&mut self, perception: &Perception) -> Option<EnemyAction>;
+}
+
+//what the player gets when this entity dies, claimed only once
+pub struct Reward {
+    //the archetype it was spawned from, names the enemy in the telemetry
+    pub archetype: String,
+    pub loot_table: Option<String>,
+    pub drop_chance: f64,
+    pub score: u32,
+    pub claimed: bool,
+    //player id of the last hit, credited with the kill
+    pub last_hit_by: Option<i32>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -3,9 +3,9 @@ use std::rc::Rc;
 use parry2d::na::Vector2;
 use sdl2::rect::Rect;
 
-use crate::{asset_management::asset_holders::EntityAnimations, collision::collider_manager::ColliderManager, enemy_behaviour::simple_enemy_behaviour::BasicEnemy, engine_types::{animation::Animation, animator::Animator}, game_logic::{characters::Character, effects::events_pub_sub::EventsPubSub, factories::enemy_factory::load_enemy, movement_controller::MovementController}};
+use crate::{asset_management::asset_holders::EntityAnimations, collision::collider_manager::ColliderManager, engine_types::{animation::Animation, animator::Animator}, game_logic::{characters::Character, effects::events_pub_sub::EventsPubSub, factories::enemy_factory::{EnemyArchetype, load_enemy, load_enemy_behaviour}, movement_controller::MovementController}};
 
-use super::enemy_components::{AIType, Behaviour, Health, Position, Renderable};
+use super::enemy_components::{AIType, Behaviour, Health, Position, Renderable, Reward, DamageModifier};
 
 
 pub const MAX_ENEMIES: usize = 30;
@@ -20,6 +20,8 @@ pub struct EnemyManager {
     pub collider_components: Vec<Option<ColliderManager>>,
     pub renderable_components: Vec<Option<Renderable>>,
     pub events_components: Vec<Option<EventsPubSub>>,
+    pub reward_components: Vec<Option<Reward>>,
+    pub damage_modifier_components: Vec<Option<DamageModifier>>,
 }
 
 impl EnemyManager {
@@ -35,6 +37,8 @@ impl EnemyManager {
             collider_components: Vec::new(),
             renderable_components: Vec::new(),
             events_components: Vec::new(),
+            reward_components: Vec::new(),
+            damage_modifier_components: Vec::new(),
         }
     }
 
@@ -48,8 +52,9 @@ impl EnemyManager {
         animator: Option<Animator>,
         colliders: Option<ColliderManager>,
         events: Option<EventsPubSub>,
+        reward: Option<Reward>,
         entity_animations: Rc<EntityAnimations>
-    ) {
+    ) -> Option<usize> {
         
         
         if self.health_components.len() < MAX_ENEMIES {
@@ -70,37 +75,51 @@ impl EnemyManager {
             self.ai_type_components.push(ai_type);
             self.collider_components.push(colliders);
             self.events_components.push(events);
+            self.reward_components.push(reward);
+            self.damage_modifier_components.push(Some(DamageModifier(1f64)));
 
             let renderable = Renderable {
                 flipped: false,
                 rect: Rect::new(0,0, 100, 100),
             };
             self.renderable_components.push(Some(renderable));
+            Some(self.health_components.len() - 1)
+        } else {
+            None
         }
     }
 
-    pub fn add_enemy(&mut self, player_pos: Vector2<f64>, entity_animations: Rc<EntityAnimations>) {
+    pub fn add_enemy(&mut self, player_pos: Vector2<f64>, archetype: &EnemyArchetype, entity_animations: Rc<EntityAnimations>) -> Option<usize> {
 
-        let ryu = load_enemy("ryu");
+        let enemy = load_enemy(archetype);
 
         let mut animator = Animator::new();
 
         let starting_animation = entity_animations.animations.get("idle").unwrap().clone();
         animator.play(starting_animation, 1.0,false);
 
-        self.new_entity(
-            Some(Health(ryu.hp)),
-            Some(Box::new(BasicEnemy::new())),
+        let enemy_id = self.new_entity(
+            Some(Health(enemy.hp)),
+            Some(load_enemy_behaviour(archetype)),
             Some(AIType::Enemy),
             player_pos,
             Some(Position(player_pos)),
-            Some(ryu),
+            Some(enemy),
             Some(animator), 
             Some(ColliderManager::new()),
             Some(EventsPubSub::new()),
+            Some(Reward {
+                archetype: archetype.name.clone(),
+                loot_table: archetype.loot_table.clone(),
+                drop_chance: archetype.drop_chance,
+                score: archetype.score,
+                claimed: false,
+                last_hit_by: None,
+            }),
             entity_animations
         );
 
-        println!("Spawned entity");
+        println!("Spawned {}", archetype.name);
+        enemy_id
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use sdl2::{rect::{Point, Rect}, render::Texture};
 
-use crate::{asset_management::{asset_holders::{EntityAssets, EntityData}, common_assets::CommonAssets, vfx::particle::Particle}, collision::{collider_manager::ColliderManager, collision_attack_resolution::{detect_hit, did_sucessfully_block, hit_opponent, hit_particles, opponent_blocked}}, engine_types::animator::Animator, game_logic::{characters::{Attack, Character, player::{Player, EntityState}}, effects::{Effect, events_pub_sub::{CharacterEventUpdate, EventsPubSub}}, movement_controller::MovementController}, rendering::camera::Camera};
+use crate::{asset_management::{asset_holders::{EntityAssets, EntityData}, vfx::particle::Particle}, collision::{collider_manager::ColliderManager, collision_attack_resolution::{detect_hit, did_sucessfully_block, hit_opponent, hit_particles, opponent_blocked}}, engine_types::{animator::Animator, sprite_data::SpriteData}, game_logic::{characters::{Attack, Character, player::{Player, EntityState}}, effects::{Effect, events_pub_sub::{CharacterEventUpdate, EventsPubSub}}, movement_controller::MovementController}, rendering::camera::Camera};
 
 use super::{enemy_components::{Health, Position, Renderable}, enemy_manager::EnemyManager};
 
@@ -47,7 +47,8 @@ pub fn heal(hp: &mut Health, heal_amount: i32, char: &Character) {
     hp.0 = std::cmp::min(hp.0 + heal_amount, char.hp);
 }
 
-pub fn update_events(enemy_manager: &mut EnemyManager, player: &mut Player, dt: f64) {
+//every enemy event runs once per frame, it is handed the player closest to the enemy
+pub fn update_events(enemy_manager: &mut EnemyManager, players: &mut [&mut Player], dt: f64) {
     
     let zip = enemy_manager.
         events_components.iter_mut().enumerate()
@@ -64,8 +65,14 @@ pub fn update_events(enemy_manager: &mut EnemyManager, player: &mut Player, dt:
     }).collect::<Vec<(usize, Vec<(CharacterEventUpdate, Effect)>)>> ();
     
     enemy_events.iter_mut().for_each(|(i, events): &mut (usize,  Vec<(CharacterEventUpdate, Effect)>)| {
+        let closest = match &enemy_manager.positions_components[*i] {
+            Some(pos) => (0..players.len())
+                .min_by(|&a, &b| {(players[a].position - pos.0).magnitude().partial_cmp(&(players[b].position - pos.0).magnitude()).unwrap()})
+                .unwrap_or(0),
+            None => 0,
+        };
         for event in events.iter_mut() {
-            event.0(player, enemy_manager, *i as i32, &mut event.1, dt);
+            event.0(&mut *players[closest], enemy_manager, *i as i32, &mut event.1, dt);
         }
     });
 
@@ -92,7 +99,7 @@ pub fn update_animations_enemies(enemy_manager: &mut EnemyManager) {
     });
 }
 
-pub fn update_colliders_enemies(enemy_manager: &mut EnemyManager, enemy_assets: &HashMap<&str, EntityAssets>) {
+pub fn update_colliders_enemies(enemy_manager: &mut EnemyManager, enemy_sprite_data: &HashMap<String, HashMap<String, SpriteData>>) {
     let zip = enemy_manager.
         collider_components.iter_mut()
         .zip(enemy_manager.positions_components.iter())
@@ -106,11 +113,11 @@ pub fn update_colliders_enemies(enemy_manager: &mut EnemyManager, enemy_assets:
                 Some((collider.as_mut()?, pos.as_ref()?, animator.as_ref()?, renderable.as_ref()?, &character.as_ref()?.name))
         })
         .for_each(|(collider, pos, animator, renderable, name): (&mut ColliderManager, &Position, &Animator, &Renderable, &String) | {
-            collider.update_colliders(renderable.flipped, pos.0, animator , &enemy_assets.get(name as &str).unwrap().texture_data);
+            collider.update_colliders(renderable.flipped, pos.0, animator , enemy_sprite_data.get(name).unwrap());
         });
 }
 
-pub fn update_movement_enemies(enemy_manager: &mut EnemyManager, camera: &mut Camera, dt: f64, general_assets: &CommonAssets) {
+pub fn update_movement_enemies(enemy_manager: &mut EnemyManager, camera: &mut Camera, dt: f64, sounds: &mut Vec<String>) {
     let zip = enemy_manager
     .positions_components.iter_mut()
     .zip(enemy_manager.animator_components.iter_mut())
@@ -133,7 +140,7 @@ pub fn update_movement_enemies(enemy_manager: &mut EnemyManager, camera: &mut Ca
             camera,
             dt,
             100, //TODO fix this,
-            general_assets
+            sounds
         );
 
         renderable.flipped = mov.facing_dir > 0;
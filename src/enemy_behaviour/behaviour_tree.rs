@@ -0,0 +1,275 @@
+use crate::{ecs_system::enemy_components::Behaviour, game_logic::characters::player::EntityState};
+
+//what an entity knows about itself and its target when deciding what to do this frame
+pub struct Perception {
+    pub dt: f64,
+    //target position - own position
+    pub distance_to_target: (f64, f64),
+    pub target_state: EntityState,
+    pub target_is_attacking: bool,
+    pub target_is_airborne: bool,
+
+    pub hp_ratio: f64,
+    //false while busy with an attack that cant be cancelled, attacks fail instead of being dropped by the controller
+    pub can_attack: bool,
+    //only entities holding a token are allowed to go in and attack
+    pub has_attack_token: bool,
+    //signed horizontal offset from the target where the entity should wait while it has no token
+    pub surround_offset: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnemyAction {
+    Idle,
+    Approach,
+    Retreat,
+    Attack(String),
+    //plays the attack animation and fires the archetype's projectile
+    Shoot,
+    Block,
+    Jump,
+    Surround,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Success,
+    Failure,
+}
+
+#[derive(Clone, Debug)]
+pub enum Condition {
+    TargetCloserThan(f64),
+    TargetFurtherThan(f64),
+    TargetAttacking,
+    TargetAirborne,
+    TargetHurt,
+    HpBelow(f64),
+    HasAttackToken,
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn check(&self, perception: &Perception) -> bool {
+        match self {
+            Condition::TargetCloserThan(distance) => perception.distance_to_target.0.abs() < *distance,
+            Condition::TargetFurtherThan(distance) => perception.distance_to_target.0.abs() > *distance,
+            Condition::TargetAttacking => perception.target_is_attacking,
+            Condition::TargetAirborne => perception.target_is_airborne,
+            Condition::TargetHurt => {
+                perception.target_state == EntityState::Hurt ||
+                perception.target_state == EntityState::Knocked ||
+                perception.target_state == EntityState::Dropped
+            },
+            Condition::HpBelow(ratio) => perception.hp_ratio < *ratio,
+            Condition::HasAttackToken => perception.has_attack_token,
+            Condition::Not(condition) => !condition.check(perception),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Node {
+    //runs children in order until one fails
+    Sequence(Vec<Node>),
+    //runs children in order until one succeeds
+    Selector(Vec<Node>),
+    Condition(Condition),
+    //fails while the child succeeded less than duration seconds ago
+    Cooldown {
+        duration: f64,
+        last_success: f64,
+        child: Box<Node>,
+    },
+    //ticks one child per success, in order and spaced by interval, rests after the last one, used for attack strings
+    Chain {
+        interval: f64,
+        rest: f64,
+        next: usize,
+        last_success: f64,
+        children: Vec<Node>,
+    },
+    Action(EnemyAction),
+}
+
+impl Node {
+    pub fn tick(&mut self, perception: &Perception, time: f64, action: &mut Option<EnemyAction>) -> Status {
+        match self {
+            Node::Sequence(children) => {
+                for child in children.iter_mut() {
+                    if child.tick(perception, time, action) == Status::Failure {
+                        return Status::Failure;
+                    }
+                }
+                Status::Success
+            },
+            Node::Selector(children) => {
+                for child in children.iter_mut() {
+                    if child.tick(perception, time, action) == Status::Success {
+                        return Status::Success;
+                    }
+                }
+                Status::Failure
+            },
+            Node::Condition(condition) => {
+                if condition.check(perception) { Status::Success } else { Status::Failure }
+            },
+            Node::Cooldown { duration, last_success, child } => {
+                if time - *last_success < *duration {
+                    return Status::Failure;
+                }
+                let status = child.tick(perception, time, action);
+                if status == Status::Success {
+                    *last_success = time;
+                }
+                status
+            },
+            Node::Chain { interval, rest, next, last_success, children } => {
+                let elapsed = time - *last_success;
+                let wait = if *next == 0 { *rest } else { *interval };
+                if elapsed < wait {
+                    return Status::Failure;
+                }
+
+                let status = children[*next].tick(perception, time, action);
+                if status == Status::Success {
+                    *next = (*next + 1) % children.len();
+                    *last_success = time;
+                }
+                status
+            },
+            Node::Action(enemy_action) => {
+                let is_attack = match enemy_action {
+                    EnemyAction::Attack(_) | EnemyAction::Shoot => true,
+                    _ => false,
+                };
+                //chains and cooldowns only move on when the attack really starts
+                if is_attack && !perception.can_attack {
+                    return Status::Failure;
+                }
+                *action = Some(enemy_action.clone());
+                Status::Success
+            },
+        }
+    }
+}
+
+pub struct BehaviourTree {
+    root: Node,
+    time: f64,
+}
+
+impl BehaviourTree {
+    pub fn new(root: Node) -> Self {
+        Self {
+            root,
+            time: 0f64,
+        }
+    }
+}
+
+impl Behaviour for BehaviourTree {
+    fn act(&mut self, perception: &Perception) -> Option<EnemyAction> {
+        self.time += perception.dt;
+        let mut action = None;
+        self.root.tick(perception, self.time, &mut action);
+        action
+    }
+}
+
+pub fn sequence(children: Vec<Node>) -> Node {
+    Node::Sequence(children)
+}
+
+pub fn selector(children: Vec<Node>) -> Node {
+    Node::Selector(children)
+}
+
+pub fn condition(condition: Condition) -> Node {
+    Node::Condition(condition)
+}
+
+pub fn cooldown(duration: f64, child: Node) -> Node {
+    Node::Cooldown {
+        duration,
+        last_success: f64::NEG_INFINITY,
+        child: Box::new(child),
+    }
+}
+
+pub fn chain(interval: f64, rest: f64, children: Vec<Node>) -> Node {
+    Node::Chain {
+        interval,
+        rest,
+        next: 0,
+        last_success: f64::NEG_INFINITY,
+        children,
+    }
+}
+
+pub fn attack(name: &str) -> Node {
+    Node::Action(EnemyAction::Attack(name.to_string()))
+}
+
+pub fn action(action: EnemyAction) -> Node {
+    Node::Action(action)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_logic::characters::player::EntityState;
+
+    use super::{EnemyAction, Node, Perception, Status, attack, chain, cooldown};
+
+    fn perception(can_attack: bool) -> Perception {
+        Perception {
+            dt: 0.1,
+            distance_to_target: (50.0, 0.0),
+            target_state: EntityState::Idle,
+            target_is_attacking: false,
+            target_is_airborne: false,
+            hp_ratio: 1.0,
+            can_attack,
+            has_attack_token: true,
+            surround_offset: 0.0,
+        }
+    }
+
+    fn tick(node: &mut Node, can_attack: bool, time: f64) -> Option<EnemyAction> {
+        let mut action = None;
+        let status = node.tick(&perception(can_attack), time, &mut action);
+        assert_eq!(status == Status::Success, action.is_some());
+        action
+    }
+
+    #[test]
+    fn chain_plays_the_string_in_order() {
+        let mut string = chain(0.5, 2.0, vec![attack("jab"), attack("heavy")]);
+        assert_eq!(tick(&mut string, true, 0.0), Some(EnemyAction::Attack("jab".to_string())));
+        //too early for the next hit
+        assert_eq!(tick(&mut string, true, 0.2), None);
+        assert_eq!(tick(&mut string, true, 0.5), Some(EnemyAction::Attack("heavy".to_string())));
+        //rests after the last hit, then starts over
+        assert_eq!(tick(&mut string, true, 1.5), None);
+        assert_eq!(tick(&mut string, true, 2.5), Some(EnemyAction::Attack("jab".to_string())));
+    }
+
+    #[test]
+    fn chain_waits_while_the_attack_is_refused() {
+        let mut string = chain(0.5, 2.0, vec![attack("jab"), attack("heavy")]);
+        assert_eq!(tick(&mut string, true, 0.0), Some(EnemyAction::Attack("jab".to_string())));
+        assert_eq!(tick(&mut string, false, 0.5), None);
+        assert_eq!(tick(&mut string, false, 0.8), None);
+        //the heavy is not skipped
+        assert_eq!(tick(&mut string, true, 1.0), Some(EnemyAction::Attack("heavy".to_string())));
+    }
+
+    #[test]
+    fn cooldown_starts_only_when_the_attack_does() {
+        let mut poke = cooldown(1.0, attack("lunge"));
+        assert_eq!(tick(&mut poke, false, 0.0), None);
+        assert_eq!(tick(&mut poke, true, 0.1), Some(EnemyAction::Attack("lunge".to_string())));
+        assert_eq!(tick(&mut poke, true, 0.6), None);
+        assert_eq!(tick(&mut poke, true, 1.2), Some(EnemyAction::Attack("lunge".to_string())));
+    }
+}
@@ -1,11 +1,78 @@
 use parry2d::na::Vector2;
 
-use crate::{ecs_system::{enemy_components::{AIType, Behaviour, Health, Position}, enemy_manager::EnemyManager, enemy_systems::attack}, game_logic::{characters::{player::{EntityState, Player}}, inputs::game_inputs::GameAction}, utils::math_sign::Sign};
+use crate::{ecs_system::{enemy_components::{AIType, Behaviour, Health, Position}, enemy_manager::EnemyManager, enemy_systems::attack}, engine_types::animator::Animator, game_logic::{characters::{player::{EntityState, Player}}, movement_controller::MovementController}, collision::collider_manager::ColliderManager, utils::math_sign::Sign};
 
+use self::behaviour_tree::{EnemyAction, Perception};
+
+pub mod behaviour_tree;
 pub mod simple_enemy_behaviour;
 
+//how many enemies can be on the player at the same time, the rest wait around
+pub const MAX_ATTACK_TOKENS: usize = 2;
+const SURROUND_DISTANCE: f64 = 350f64;
+const SURROUND_SPACING: f64 = 120f64;
+const SURROUND_TOLERANCE: f64 = 20f64;
+
+pub struct CombatRole {
+    pub has_attack_token: bool,
+    pub surround_offset: f64,
+}
+
+//enemies go after the closest player that is still standing, or player 1 once everyone is down
+pub fn closest_target<'a>(pos: Vector2<f64>, players: &[&'a Player]) -> (usize, &'a Player) {
+    players.iter().enumerate()
+        .filter(|(_, player)| {player.hp.0 > 0})
+        .min_by(|(_, a), (_, b)| {(a.position.x - pos.x).abs().partial_cmp(&(b.position.x - pos.x).abs()).unwrap()})
+        .map_or((0, players[0]), |(i, player)| {(i, *player)})
+}
+
+//closest enemies to each player get that player's tokens, the others are spread on both sides of their player favouring the emptier side
+pub fn assign_combat_roles(enemy_manager: &EnemyManager, players: &[&Player]) -> Vec<Option<CombatRole>> {
+    let mut roles = enemy_manager.health_components.iter().map(|_| {None}).collect::<Vec<Option<CombatRole>>>();
+
+    let mut candidates = enemy_manager.health_components.iter().enumerate()
+        .zip(enemy_manager.ai_type_components.iter())
+        .zip(enemy_manager.positions_components.iter())
+        .filter_map(|(((i, hp), ai_type), pos)| {
+            if hp.as_ref()?.0 > 0 && *ai_type.as_ref()? == AIType::Enemy {
+                let pos = pos.as_ref()?.0;
+                let (target, target_player) = closest_target(pos, players);
+                Some((i, pos.x - target_player.position.x, target))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<(usize, f64, usize)>>();
+    candidates.sort_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap());
+
+    let mut tokens_given = vec![0; players.len()];
+    let mut waiting_per_player = vec![[0, 0]; players.len()];
+    for (i, dx, target) in candidates.iter() {
+        let waiting_on_side = &mut waiting_per_player[*target];
+        if tokens_given[*target] < MAX_ATTACK_TOKENS {
+            tokens_given[*target] += 1;
+            roles[*i] = Some(CombatRole { has_attack_token: true, surround_offset: 0f64 });
+        } else {
+            let own_side = if *dx < 0f64 { 0 } else { 1 };
+            let side = if waiting_on_side[own_side] > waiting_on_side[1 - own_side] { 1 - own_side } else { own_side };
+            let side_dir = if side == 0 { -1f64 } else { 1f64 };
+
+            roles[*i] = Some(CombatRole {
+                has_attack_token: false,
+                surround_offset: side_dir * (SURROUND_DISTANCE + SURROUND_SPACING * waiting_on_side[side] as f64),
+            });
+            waiting_on_side[side] += 1;
+        }
+    }
+
+    roles
+}
+
+//returns the enemies that fired a projectile this frame
+pub fn update_behaviour_enemies(enemy_manager: &mut EnemyManager, players: &[&Player], dt: f64) -> Vec<usize> {
+    let roles = assign_combat_roles(enemy_manager, players);
+    let mut shooters = Vec::new();
 
-pub fn update_behaviour_enemies(enemy_manager: &mut EnemyManager, player: &mut Player, dt: f64) {
     let zip = enemy_manager.
         behaviour_components.iter_mut()
         .enumerate()
@@ -24,6 +91,8 @@ pub fn update_behaviour_enemies(enemy_manager: &mut EnemyManager, player: &mut P
     .collect::<Vec<(usize, &mut Box<dyn Behaviour>)>>();
 
     let entity_positions = &enemy_manager.positions_components;
+    let entity_health = &enemy_manager.health_components;
+    let entity_characters = &enemy_manager.character_components;
     let entity_controllers = &mut enemy_manager.movement_controller_components;
     let entity_animators = &mut enemy_manager.animator_components;
     let entity_collision_managers = &mut enemy_manager.collider_components;
@@ -36,6 +105,7 @@ pub fn update_behaviour_enemies(enemy_manager: &mut EnemyManager, player: &mut P
         let collision_manager = entity_collision_managers[*index].as_mut().unwrap();
         let ai_type = entity_ai_type[*index].as_ref().unwrap();
 
+        let (_, player) = closest_target(pos.0, players);
         let target_pos = if *ai_type == AIType::Enemy { 
             Some(player.position)
         } else if *ai_type == AIType::Allied {
@@ -73,30 +143,83 @@ pub fn update_behaviour_enemies(enemy_manager: &mut EnemyManager, player: &mut P
         if let Some(target_pos) = target_pos {
             let dir_to_target = target_pos - pos.0;
 
-            let hurt = controller.state == EntityState::Hurt || controller.state == EntityState::Knocked || controller.state == EntityState::Dropped || controller.state == EntityState::Dead;
+            let hurt = controller.state == EntityState::Hurt || controller.state == EntityState::Knocked || controller.state == EntityState::Dropped || controller.state == EntityState::Grabbed || controller.state == EntityState::GuardBroken || controller.state == EntityState::Dead || controller.stun_frames > 0;
             let recovering = controller.state == EntityState::KnockedLanding || controller.state == EntityState::DroppedLanding;
             if !controller.is_airborne && !hurt && !recovering {
-                if dir_to_target.x.abs() > 180f64 {
-                    controller.set_velocity(Vector2::new((dir_to_target.x as i8).sign() , 0), animator);
-                } else {
-                    controller.set_velocity(Vector2::new(0 , 0), animator);
-                    let action = behaviour.act(dt);
-                    if let Some(action) = action {
-                        match action {
-                            GameAction::Punch => {
-                                attack(controller, animator, collision_manager, "attack".to_string())
-                            },
-                            _ => {}
-                        }
-                    }
+                let targets_player = *ai_type == AIType::Enemy;
+                let role = roles[*index].as_ref();
+                let perception = Perception {
+                    dt,
+                    distance_to_target: (dir_to_target.x, dir_to_target.y),
+                    target_state: if targets_player { player.controller.state } else { EntityState::Idle },
+                    target_is_attacking: targets_player && player.controller.is_attacking,
+                    target_is_airborne: targets_player && player.controller.is_airborne,
+
+                    hp_ratio: entity_health[*index].as_ref().unwrap().0 as f64 / entity_characters[*index].as_ref().unwrap().hp as f64,
+                    can_attack: controller.can_attack(),
+                    has_attack_token: role.map_or(true, |role| role.has_attack_token),
+                    surround_offset: role.map_or(0f64, |role| role.surround_offset),
+                };
+
+                let action = behaviour.act(&perception).unwrap_or(EnemyAction::Idle);
+                if apply_action(action, &perception, pos, controller, animator, collision_manager) {
+                    shooters.push(*index);
                 }
             }
         } else {
+            controller.is_blocking = false;
             controller.set_velocity(Vector2::new(0 , 0), animator);
         }
         
 
     });
+
+    shooters
 }
 
+//true when the entity fired a projectile
+fn apply_action(action: EnemyAction, perception: &Perception, pos: &Position, controller: &mut MovementController, animator: &mut Animator, collision_manager: &mut ColliderManager) -> bool {
+    let dir_to_target = (perception.distance_to_target.0 as i8).sign();
+    controller.is_blocking = action == EnemyAction::Block;
+    let mut shot = false;
 
+    match action {
+        EnemyAction::Idle | EnemyAction::Block => {
+            controller.set_velocity(Vector2::new(0 , 0), animator);
+        },
+        EnemyAction::Approach => {
+            controller.set_velocity(Vector2::new(dir_to_target, 0), animator);
+        },
+        EnemyAction::Retreat => {
+            controller.set_velocity(Vector2::new(-dir_to_target, 0), animator);
+        },
+        EnemyAction::Attack(attack_animation) => {
+            controller.set_velocity(Vector2::new(0 , 0), animator);
+            attack(controller, animator, collision_manager, attack_animation);
+        },
+        EnemyAction::Shoot => {
+            controller.set_velocity(Vector2::new(0 , 0), animator);
+            shot = controller.can_attack();
+            attack(controller, animator, collision_manager, "attack".to_string());
+        },
+        EnemyAction::Jump => {
+            controller.set_velocity(Vector2::new(dir_to_target, 0), animator);
+            controller.jump(animator);
+        },
+        EnemyAction::Surround => {
+            let target_x = pos.0.x + perception.distance_to_target.0 + perception.surround_offset;
+            let dir_to_slot = target_x - pos.0.x;
+            if dir_to_slot.abs() > SURROUND_TOLERANCE {
+                controller.set_velocity(Vector2::new((dir_to_slot as i8).sign(), 0), animator);
+            } else {
+                controller.set_velocity(Vector2::new(0 , 0), animator);
+            }
+        },
+    }
+
+    //keep looking at the target while backing off or repositioning
+    if dir_to_target != 0 {
+        controller.facing_dir = dir_to_target;
+    }
+    shot
+}
@@ -1,26 +1,92 @@
-use crate::{ecs_system::{enemy_components::{Behaviour}}, game_logic::inputs::game_inputs::GameAction};
+use super::behaviour_tree::{BehaviourTree, Condition, EnemyAction, action, attack, chain, condition, cooldown, selector, sequence};
 
-pub struct BasicEnemy {
-    delay_between_punches: f64,
-    time_accumulator: f64,
+//walks up, jabs and follows with a heavy, waits on the side while someone else has the turn
+pub fn basic_enemy(attack_delay: f64, engage_distance: f64) -> BehaviourTree {
+    BehaviourTree::new(selector(vec![
+        sequence(vec![
+            condition(Condition::Not(Box::new(Condition::HasAttackToken))),
+            action(EnemyAction::Surround),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetFurtherThan(engage_distance)),
+            action(EnemyAction::Approach),
+        ]),
+        chain(0.5f64, attack_delay, vec![attack("jab"), attack("heavy")]),
+        action(EnemyAction::Idle),
+    ]))
 }
 
-impl BasicEnemy {
-    pub fn new() -> Self{
-        Self {
-            delay_between_punches: 1f64,
-            time_accumulator: 0f64,
-        }
-    }
+//blocks when the player swings at it and answers with short combos
+pub fn brawler_enemy(attack_delay: f64, engage_distance: f64) -> BehaviourTree {
+    BehaviourTree::new(selector(vec![
+        sequence(vec![
+            condition(Condition::TargetAttacking),
+            condition(Condition::TargetCloserThan(engage_distance + 60f64)),
+            action(EnemyAction::Block),
+        ]),
+        sequence(vec![
+            condition(Condition::Not(Box::new(Condition::HasAttackToken))),
+            action(EnemyAction::Surround),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetFurtherThan(engage_distance)),
+            action(EnemyAction::Approach),
+        ]),
+        chain(0.35f64, attack_delay, vec![attack("jab"), attack("jab"), attack("heavy")]),
+        action(EnemyAction::Block),
+    ]))
 }
 
-impl Behaviour for BasicEnemy {
-    fn act(&mut self, dt: f64) -> Option<GameAction> {
-        self.time_accumulator += dt;
-        if self.time_accumulator > self.delay_between_punches {
-            self.time_accumulator = 0f64;
-            return Some(GameAction::Punch);
-        }
-        return None;
-    }
-}
\ No newline at end of file
+//hit and run, backs off when hurt and jumps in from the flanks
+pub fn skirmisher_enemy(attack_delay: f64, engage_distance: f64) -> BehaviourTree {
+    BehaviourTree::new(selector(vec![
+        sequence(vec![
+            condition(Condition::HpBelow(0.4f64)),
+            condition(Condition::TargetCloserThan(engage_distance + 100f64)),
+            condition(Condition::Not(Box::new(Condition::TargetHurt))),
+            action(EnemyAction::Retreat),
+        ]),
+        sequence(vec![
+            condition(Condition::Not(Box::new(Condition::HasAttackToken))),
+            action(EnemyAction::Surround),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetFurtherThan(engage_distance * 2f64)),
+            cooldown(3f64, action(EnemyAction::Jump)),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetFurtherThan(engage_distance)),
+            action(EnemyAction::Approach),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetHurt),
+            chain(0.3f64, attack_delay, vec![attack("jab"), attack("lunge")]),
+        ]),
+        cooldown(attack_delay, attack("lunge")),
+        action(EnemyAction::Retreat),
+    ]))
+}
+
+//keeps its distance and shoots, backs off when the target walks up to it and jabs when cornered
+pub fn ranged_enemy(attack_delay: f64, engage_distance: f64) -> BehaviourTree {
+    BehaviourTree::new(selector(vec![
+        sequence(vec![
+            condition(Condition::TargetCloserThan(engage_distance * 0.25f64)),
+            cooldown(attack_delay, attack("jab")),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetCloserThan(engage_distance * 0.5f64)),
+            action(EnemyAction::Retreat),
+        ]),
+        sequence(vec![
+            condition(Condition::Not(Box::new(Condition::HasAttackToken))),
+            action(EnemyAction::Surround),
+        ]),
+        sequence(vec![
+            condition(Condition::TargetFurtherThan(engage_distance)),
+            action(EnemyAction::Approach),
+        ]),
+        cooldown(attack_delay, action(EnemyAction::Shoot)),
+        action(EnemyAction::Idle),
+    ]))
+}
@@ -13,4 +13,9 @@ pub trait Scene {
         input_devices: &mut InputDevices,
         canvas: &mut Canvas<Window>,
     ) -> Transition;
+
+    //checked whenever the scene comes to the top of the stack, None keeps whatever is playing
+    fn music_track(&self) -> Option<&str> {
+        None
+    }
 }
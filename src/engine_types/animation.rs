@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use parry2d::na::Vector2;
 use crate::{asset_management::cast_point::CastPoint, engine_types::transform::Transform};
 
-use super::{collider::Collider};
+use super::{collider::Collider, frame_data::FrameData};
 
 #[derive(Clone)]
 pub struct Animation {
@@ -13,6 +13,7 @@ pub struct Animation {
     pub offsets: Option<Vec<Vector2<f64>>>,
     pub cast_point: HashMap<i64, CastPoint>,
     pub collider_animation: Option<ColliderAnimation>,
+    pub frame_data: Option<FrameData>,
 }
 
 #[derive(Clone, Debug)]
@@ -31,6 +32,7 @@ impl Animation {
             cast_point: HashMap::new(),
             sprite_alignments: HashMap::new(),
             collider_animation: None,
+            frame_data: None,
         }
     }
 
@@ -49,6 +51,7 @@ impl Animation {
             cast_point,
             sprite_alignments,
             collider_animation,
+            frame_data: None,
         }
     }
 }
@@ -4,8 +4,8 @@ use parry2d::bounding_volume::AABB;
 pub enum ColliderType {
     Hitbox,  //attacking collider
     Hurtbox, //take damage
-    Grabbox,
-    Grabbablebox,
+    Grabbox, //grabs
+    Grabbablebox, //can be grabbed, the pushbox stands in for it when a frame has none
     Pushbox, //push character
 }
 #[derive(Debug, Clone)]
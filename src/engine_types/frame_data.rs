@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::fs;
+
+//what an attack can be cancelled into
+#[derive(Debug, Clone, Copy, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum CancelKind {
+    Normal,
+    Special,
+    Jump,
+    Dash,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct CancelWindow {
+    //first and last frame, both included
+    pub frames: (u32, u32),
+    pub into: Vec<CancelKind>,
+    //only open once the attack connected, hit or block
+    #[serde(default)]
+    pub on_hit: bool,
+}
+
+//frames are animator ticks counted from the start of the animation
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct FrameData {
+    pub startup: u32,
+    pub active: u32,
+    pub recovery: u32,
+    //override the stun of the attack that uses this animation
+    #[serde(default)]
+    pub hit_stun: Option<i32>,
+    #[serde(default)]
+    pub block_stun: Option<i32>,
+    #[serde(default)]
+    pub cancels: Vec<CancelWindow>,
+}
+
+impl FrameData {
+    pub fn total(&self) -> u32 {
+        self.startup + self.active + self.recovery
+    }
+
+    //hitboxes only count during the active frames
+    pub fn is_active(&self, frame: f64) -> bool {
+        frame >= self.startup as f64 && frame < (self.startup + self.active) as f64
+    }
+
+    pub fn can_cancel(&self, frame: f64, into: CancelKind, has_hit: bool) -> bool {
+        self.cancels.iter().any(|window| {
+            window.into.contains(&into) &&
+            frame >= window.frames.0 as f64 && frame <= window.frames.1 as f64 &&
+            (!window.on_hit || has_hit)
+        })
+    }
+}
+
+//animation name -> frame data, animations missing from the file keep the old rules
+pub fn load_animation_frame_data(dir: &str) -> HashMap<String, FrameData> {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir).unwrap();
+    serde_json::from_str::<HashMap<String, FrameData>>(&json_string).unwrap()
+}
@@ -3,4 +3,5 @@ pub mod animation;
 pub mod animator;
 pub mod simple_animator;
 pub mod sprite_data;
-pub mod collider;
\ No newline at end of file
+pub mod collider;
+pub mod frame_data;
\ No newline at end of file
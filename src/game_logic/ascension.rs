@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs;
+
+use rand::prelude::SmallRng;
+
+use crate::{asset_management::rng_tables::LootTable, ecs_system::{enemy_components::DamageModifier, enemy_manager::EnemyManager}, overworld::{all_events, node::WorldNode, remove_stores}};
+
+use super::{boss::Boss, characters::{Character, player::Player}, effects::Effect, items::{Item, item_effects::remove_all_extra_attacks_wrap, loot_table_effects::change_spawn_item}};
+
+//not an item effect, marks the hooks added by the run modifiers
+const ASCENSION_EFFECT_ID: i32 = -2;
+//lifesteal on kill, lifesteal on hit and the healing active
+const HEALING_EFFECT_IDS: [i32; 3] = [8, 9, 34];
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum RunModifier {
+    EnemyDamage(f64),
+    NoStores,
+    AllEvents,
+    //every item that doesnt heal gets this many times more likely
+    FewerHealing(i32),
+    FasterEnemies(f64),
+    //getting hurt forgets every attack learned from items
+    FragileItems,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct AscensionTier {
+    pub name: String,
+    pub description: String,
+    pub modifier: RunModifier,
+}
+
+//ascension n turns on the first n tiers, they stack
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct AscensionConfig {
+    pub tiers: Vec<AscensionTier>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunModifiers {
+    pub enemy_damage_mod: f64,
+    pub enemy_speed_mod: f64,
+    pub no_stores: bool,
+    pub all_events: bool,
+    pub non_healing_rarity_mod: i32,
+    pub fragile_items: bool,
+}
+
+impl Default for RunModifiers {
+    fn default() -> Self {
+        Self {
+            enemy_damage_mod: 1f64,
+            enemy_speed_mod: 1f64,
+            no_stores: false,
+            all_events: false,
+            non_healing_rarity_mod: 1,
+            fragile_items: false,
+        }
+    }
+}
+
+impl AscensionConfig {
+    pub fn max_level(&self) -> u32 {
+        self.tiers.len() as u32
+    }
+
+    pub fn modifiers(&self, level: u32) -> RunModifiers {
+        let mut modifiers = RunModifiers::default();
+        for tier in self.tiers.iter().take(level as usize) {
+            match tier.modifier {
+                RunModifier::EnemyDamage(damage_mod) => modifiers.enemy_damage_mod *= damage_mod,
+                RunModifier::NoStores => modifiers.no_stores = true,
+                RunModifier::AllEvents => modifiers.all_events = true,
+                RunModifier::FewerHealing(rarity_mod) => modifiers.non_healing_rarity_mod *= rarity_mod,
+                RunModifier::FasterEnemies(speed_mod) => modifiers.enemy_speed_mod *= speed_mod,
+                RunModifier::FragileItems => modifiers.fragile_items = true,
+            }
+        }
+        modifiers
+    }
+}
+
+impl RunModifiers {
+    //runs on every freshly generated map, saved maps already have them
+    pub fn apply_to_overworld(&self, overworld: &mut Vec<WorldNode>, rng: &mut SmallRng) {
+        if self.all_events {
+            all_events(overworld, rng);
+        } else if self.no_stores {
+            remove_stores(overworld);
+        }
+    }
+
+    pub fn apply_to_loot_tables(&self, items: &HashMap<i32, Item>, character: &Character, loot_tables: &mut HashMap<String, LootTable>) {
+        if self.non_healing_rarity_mod <= 1 {
+            return;
+        }
+        let non_healing_items = items.values()
+            .filter(|item| {!item.effects.iter().any(|effect| {HEALING_EFFECT_IDS.contains(&effect.effect_id)})})
+            .map(|item| {item.id})
+            .collect::<Vec<i32>>();
+        change_spawn_item(non_healing_items, self.non_healing_rarity_mod, character, loot_tables);
+    }
+
+    pub fn apply_to_boss(&self, boss: &Boss, enemies: &mut EnemyManager) {
+        let damage_mod = enemies.damage_modifier_components[boss.enemy_id].as_ref().map(|modifier| {modifier.0}).unwrap_or(1f64);
+        enemies.damage_modifier_components[boss.enemy_id] = Some(DamageModifier(damage_mod * self.enemy_damage_mod));
+    }
+
+    pub fn register_hooks(&self, player: &mut Player) {
+        if self.fragile_items {
+            player.events.on_hurt.push((remove_all_extra_attacks_wrap, Effect {
+                effect_id: ASCENSION_EFFECT_ID,
+                duration: None,
+                time_elapsed: 0,
+                apply_at_every: None,
+                change: None,
+                stat: None,
+                add_attack: None,
+            }));
+        }
+    }
+}
+
+pub fn load_ascension_config(dir: String) -> AscensionConfig {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir).unwrap();
+    serde_json::from_str::<AscensionConfig>(&json_string).unwrap()
+}
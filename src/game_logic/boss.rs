@@ -0,0 +1,141 @@
+use std::{collections::HashMap, fs, path::Path, rc::Rc};
+
+use parry2d::na::Vector2;
+use tiled::Map;
+
+use crate::{asset_management::{asset_holders::EntityAnimations, asset_loader::load_tiled_map::load_level}, ecs_system::{enemy_components::DamageModifier, enemy_manager::EnemyManager}};
+
+use super::{difficulty::DifficultyTier, factories::enemy_factory::{EnemyArchetype, make_behaviour}};
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct BossDefinition {
+    pub name: String,
+    //base enemy archetype, gives the sprites and the stats that are not overridden here
+    pub archetype: String,
+    pub hp: i32,
+    #[serde(default)]
+    pub score: u32,
+    pub loot_table: String,
+    pub arena: Option<String>,
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct BossPhase {
+    //the phase starts once the boss hp ratio goes below this
+    pub hp_threshold: f64,
+    pub behaviour: String,
+    pub attack_delay: f64,
+    pub engage_distance: f64,
+    pub speed_mod: f64,
+}
+
+pub struct Boss {
+    pub definition: BossDefinition,
+    pub enemy_id: usize,
+    pub current_phase: usize,
+    pub defeated: bool,
+    base_speed: f64,
+}
+
+impl Boss {
+    pub fn spawn(
+        definition: &BossDefinition,
+        difficulty: &DifficultyTier,
+        archetypes: &HashMap<String, EnemyArchetype>,
+        enemy_animations: &HashMap<String, Rc<EntityAnimations>>,
+        enemies: &mut EnemyManager,
+        pos: Vector2<f64>
+    ) -> Option<Self> {
+        //the phase thresholds and the boss bar go off the scaled hp
+        let mut definition = definition.clone();
+        definition.hp = (definition.hp as f64 * difficulty.hp_mod).round() as i32;
+
+        let mut archetype = archetypes.get(&definition.archetype)?.clone();
+        archetype.stats.hp = definition.hp;
+        archetype.loot_table = Some(definition.loot_table.clone());
+        archetype.drop_chance = 1f64;
+        archetype.score = definition.score;
+
+        let first_phase = &definition.phases[0];
+        archetype.behaviour = first_phase.behaviour.clone();
+        archetype.attack_delay = first_phase.attack_delay;
+        archetype.engage_distance = first_phase.engage_distance;
+
+        let enemy_id = enemies.add_enemy(pos, &archetype, Rc::clone(enemy_animations.get(&archetype.sprite_set)?))?;
+        enemies.damage_modifier_components[enemy_id] = Some(DamageModifier(difficulty.damage_mod));
+        let mut boss = Self {
+            definition,
+            enemy_id,
+            current_phase: 0,
+            defeated: false,
+            base_speed: archetype.stats.speed,
+        };
+        boss.apply_phase(enemies);
+        Some(boss)
+    }
+
+    pub fn hp(&self, enemies: &EnemyManager) -> i32 {
+        match &enemies.health_components[self.enemy_id] {
+            Some(hp) => hp.0.max(0),
+            None => 0,
+        }
+    }
+
+    //returns true on the frame the boss moves into a new phase
+    pub fn update(&mut self, enemies: &mut EnemyManager) -> bool {
+        if self.defeated {
+            return false;
+        }
+
+        let hp = self.hp(enemies);
+        if hp <= 0 {
+            self.defeated = true;
+            return false;
+        }
+
+        let hp_ratio = hp as f64 / self.definition.hp as f64;
+        let mut next_phase = self.current_phase;
+        while next_phase + 1 < self.definition.phases.len() && hp_ratio < self.definition.phases[next_phase + 1].hp_threshold {
+            next_phase += 1;
+        }
+
+        if next_phase != self.current_phase {
+            self.current_phase = next_phase;
+            self.apply_phase(enemies);
+            return true;
+        }
+        false
+    }
+
+    fn apply_phase(&self, enemies: &mut EnemyManager) {
+        let phase = &self.definition.phases[self.current_phase];
+        enemies.behaviour_components[self.enemy_id] = make_behaviour(&phase.behaviour, phase.attack_delay, phase.engage_distance);
+        if let Some(character) = enemies.character_components[self.enemy_id].as_mut() {
+            character.speed = self.base_speed * phase.speed_mod;
+        }
+    }
+}
+
+pub fn load_bosses(dir: String) -> HashMap<String, BossDefinition> {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir.clone()).unwrap();
+    let bosses = serde_json::from_str::<Vec<BossDefinition>>(&json_string).unwrap();
+    for boss in bosses.iter() {
+        for phase in boss.phases.iter() {
+            if make_behaviour(&phase.behaviour, phase.attack_delay, phase.engage_distance).is_none() {
+                panic!("{}: boss {} has unknown behaviour {}", dir, boss.name, phase.behaviour);
+            }
+        }
+    }
+
+    bosses.into_iter().map(|boss| {(boss.name.clone(), boss)}).collect()
+}
+
+//bosses without their own arena, or with a missing file, fight in the first level room
+pub fn load_boss_arena(definition: &BossDefinition, level_rooms: &HashMap<i32, Map>) -> Map {
+    match &definition.arena {
+        Some(arena) if Path::new(arena).exists() => load_level(arena.clone()),
+        _ => level_rooms.get(&0).unwrap().clone(),
+    }
+}
@@ -51,7 +51,7 @@ pub struct Character {
     crash       0b1000u32
 */
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 pub enum AttackType {
     Punch,
     AirbornePunch,
@@ -65,6 +65,8 @@ pub struct Attack {
     pub stun_on_hit: i32,
     pub stun_on_block: i32,
     pub push_back: f64,
+    //taken from the guard meter when blocked
+    pub guard_damage: i32,
     pub attack_type: AttackType,
     pub on_hit: Option<OnHitSpecificAttack>,
 }
@@ -1,14 +1,14 @@
 use parry2d::na::Vector2;
+use rand::{SeedableRng, prelude::SmallRng};
 use sdl2::rect::{Point, Rect};
 use sdl2::render::Texture;
 
 use std::rc::Rc;
 use std::{collections::{HashMap, VecDeque}, fmt};
 
-use crate::asset_management::common_assets::CommonAssets;
 use crate::game_logic::effects::events_pub_sub::CharacterEventActive;
-use crate::ui::ingame::segmented_bar_ui::SegmentedBar;
-use crate::{asset_management::asset_holders::{EntityAnimations, EntityAssets, EntityData}, collision::collider_manager::ColliderManager, ecs_system::enemy_components::Health, engine_types::{animator::Animator, sprite_data::SpriteData}, game_logic::{effects::{Effect, ItemEffects, events_pub_sub::{CharacterEvent, EventsPubSub}}, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement}, items::{Item, ItemType}, movement_controller::MovementController}, rendering::camera::Camera};
+use crate::game_logic::grab::GrabCommand;
+use crate::{asset_management::asset_holders::{EntityAnimations, EntityAssets, EntityData}, collision::collider_manager::ColliderManager, ecs_system::enemy_components::Health, engine_types::{animator::Animator, sprite_data::SpriteData}, game_logic::{effects::{Effect, ItemEffects, events_pub_sub::{CharacterEvent, EventsPubSub}}, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement}, items::{Item, ItemType}, movement_controller::MovementController, rng_streams::{RngStream, stream_rng}}, rendering::camera::Camera};
 
 use super::Character;
 
@@ -25,6 +25,11 @@ pub enum EntityState {
     KnockedLanding,
     Dropped,
     DroppedLanding,
+    //holding someone, the one held is Grabbed until thrown, released or they break free
+    Grabbing,
+    Grabbed,
+    //blocked until the guard meter ran out, stunned until the guard refills
+    GuardBroken,
     Dead,
 }
 impl fmt::Display for EntityState {
@@ -49,11 +54,17 @@ pub struct Player {
 
     pub events: EventsPubSub,
     pub items: Vec<String>,
+    pub item_ids: Vec<i32>,
 
     pub active_item_key: Option<String>,
     pub active_item: Option<(CharacterEventActive, Effect)>,
     pub active_item_cost: i8,
     pub currency: u32,
+
+    //rolls made by items during a match
+    pub combat_rng: SmallRng,
+    //read and cleared by the grab system every step
+    pub grab_command: Option<GrabCommand>,
 }
 
 impl Player {
@@ -75,20 +86,28 @@ impl Player {
 
             events: EventsPubSub::new(),
             items: Vec::new(),
+            item_ids: Vec::new(),
 
             active_item_key: None,
             active_item: None,
             active_item_cost: 0,
             currency: 10,
+
+            combat_rng: SmallRng::seed_from_u64(0),
+            grab_command: None,
         }
     }
 
-    pub fn equip_item(&mut self, item: &mut Item, hash_effects: &HashMap<i32, ItemEffects>, energy_bars: &mut SegmentedBar){
+    pub fn seed_combat_rng(&mut self, seed: u64, curr_act: u32, curr_level: i32) {
+        self.combat_rng = stream_rng(seed, RngStream::Combat, &[curr_act as u64, curr_level as u64, self.id as u64]);
+    }
+
+    pub fn equip_item(&mut self, item: &mut Item, hash_effects: &HashMap<i32, ItemEffects>){
+        self.item_ids.push(item.id);
         match item.item_type {
             ItemType::ActivePart(cost) => {
                 self.active_item_cost = cost;
                 self.active_item_key = Some(item.asset_id.clone());
-                energy_bars.update_width(cost as i32, self.currency as i32);
             },
             _ => {
                 self.items.push(item.asset_id.clone())
@@ -129,9 +148,9 @@ impl Player {
         dt: f64,
         finished: bool,
         character_width: i32,
-        general_assets: &CommonAssets
+        sounds: &mut Vec<String>
     ) {
-       self.controller.update(&mut self.position, &self.character, &mut self.animator, camera, dt, character_width, general_assets);
+       self.controller.update(&mut self.position, &self.character, &mut self.animator, camera, dt, character_width, sounds);
     }
 
     pub fn state_update(&mut self, sprite_data: &HashMap<String, SpriteData>) {
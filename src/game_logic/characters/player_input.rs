@@ -2,12 +2,17 @@ use std::{cmp, collections::VecDeque};
 
 use parry2d::na::Vector2;
 
-use crate::{asset_management::asset_holders::EntityData, ecs_system::enemy_manager::EnemyManager, game_logic::inputs::{game_inputs::GameAction, input_cycle::AllInputManagement}};
+use crate::{asset_management::asset_holders::EntityData, ecs_system::enemy_manager::EnemyManager, engine_types::frame_data::CancelKind, game_logic::{grab::GrabCommand, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement, motion_inputs::MotionHistory}}};
 
 use super::player::{EntityState, Player};
 
 
 pub fn apply_input_state(player: &mut Player, inputs: &mut AllInputManagement, character_data: &EntityData, enemies: &mut EnemyManager) {
+    //holding someone keeps the facing direction until the throw, a broken guard cant turn around either
+    if player.controller.state == EntityState::Grabbing || player.controller.state == EntityState::GuardBroken {
+        return;
+    }
+
     if let Some(&last_action) = inputs.action_history.back() {
         if GameAction::is_pressed(last_action, GameAction::Right) { //1
             player.controller.set_velocity_x(1, &mut player.animator);
@@ -23,22 +28,20 @@ pub fn apply_input_state(player: &mut Player, inputs: &mut AllInputManagement, c
         }
     }
 
-    let mut occupied = (player.controller.is_attacking && !player.controller.has_hit) ||
-        player.controller.state == EntityState::Hurt ||
-        player.controller.state == EntityState::Landing || 
-        player.controller.state == EntityState::Dashing;
-
     let action_history = inputs.action_history.clone();
+    let motion_history = &inputs.motion_history;
 
     inputs.input_buffer.retain(|&buffered_input| {
 
-        occupied = (player.controller.is_attacking && !player.controller.has_hit) ||
+        let kind = if check_motion_inputs(player, character_data, motion_history, buffered_input).is_some() { CancelKind::Special } else { cancel_kind(buffered_input) };
+        let occupied = (player.controller.is_attacking && !player.controller.can_cancel_into(kind)) ||
+        player.controller.stun_frames > 0 ||
         player.controller.state == EntityState::Hurt ||
         player.controller.state == EntityState::Landing || 
         player.controller.state == EntityState::Dashing;
 
         if !occupied {
-            apply_input(player, buffered_input, character_data, &action_history, enemies);
+            apply_input(player, buffered_input, character_data, &action_history, motion_history, enemies);
         }
 
         occupied
@@ -62,7 +65,11 @@ pub fn process_input(player: &mut Player,
         inputs_for_current_frame |= GameAction::Dash as i32;
     }
 
-    let occupied = (player.controller.is_attacking && !player.controller.has_hit) ||
+    inputs.motion_history.record(inputs_for_current_frame);
+
+    let kind = if check_motion_inputs(player, character_data, &inputs.motion_history, inputs_for_current_frame).is_some() { CancelKind::Special } else { cancel_kind(inputs_for_current_frame) };
+    let occupied = (player.controller.is_attacking && !player.controller.can_cancel_into(kind)) ||
+    player.controller.stun_frames > 0 ||
     player.controller.state == EntityState::Hurt ||
     player.controller.state == EntityState::Landing;
     
@@ -94,7 +101,7 @@ pub fn process_input(player: &mut Player,
         return;
     }
 
-    apply_input(player, inputs_for_current_frame, character_data, &inputs.action_history, enemies);
+    apply_input(player, inputs_for_current_frame, character_data, &inputs.action_history, &inputs.motion_history, enemies);
 
     inputs.action_history.push_back(inputs_for_current_frame);
     inputs.input_reset_timer.push(0);
@@ -105,6 +112,7 @@ fn apply_input(player: &mut Player,
     inputs_for_current_frame: i32, 
     character_data: &EntityData, 
     action_history: &VecDeque<i32>,
+    motion_history: &MotionHistory,
     enemies: &mut EnemyManager) {
     //println!("run inputs {:?}", GameAction::debug_i32(inputs_for_current_frame));
     let x = if inputs_for_current_frame & GameAction::Right as i32 > 0 {
@@ -123,18 +131,44 @@ fn apply_input(player: &mut Player,
         0i8
     };
 
+    let n_prev_actions = action_history.len();
+    let newly_pressed = |action: GameAction| {
+        inputs_for_current_frame & action as i32 > 0 && (n_prev_actions == 0 || action_history[n_prev_actions-1] & action as i32 == 0)
+    };
+
+    if player.controller.state == EntityState::GuardBroken {
+        return;
+    }
+
+    if player.controller.state == EntityState::Grabbing {
+        if newly_pressed(GameAction::Punch) {
+            player.grab_command = Some(GrabCommand::Pummel);
+        } else if newly_pressed(GameAction::Kick) {
+            player.grab_command = Some(GrabCommand::Throw(x));
+        }
+        return;
+    }
+
     player.controller.set_velocity(Vector2::new(x, y), &mut player.animator);
 
     if inputs_for_current_frame & GameAction::Jump as i32 > 0 {
         player.jump();
     }
 
-    let n_prev_actions = action_history.len();
     let punch_kick_not_pressed = n_prev_actions == 0 || (n_prev_actions > 0 && action_history[n_prev_actions-1] & GameAction::Punch as i32 == 0 && action_history[n_prev_actions-1] & GameAction::Kick as i32 == 0);
     let punch_kick_simultaneously = inputs_for_current_frame & GameAction::Punch as i32 > 0 && inputs_for_current_frame & GameAction::Kick as i32 > 0;
     let has_currency_to_activate = player.currency >= player.active_item_cost as u32;
 
-    if punch_kick_simultaneously && punch_kick_not_pressed && has_currency_to_activate {
+    let grab_attempt = inputs_for_current_frame & GameAction::Block as i32 > 0 && newly_pressed(GameAction::Punch) &&
+        !player.controller.is_airborne && player.controller.can_attack();
+
+    if grab_attempt {
+        player.grab_command = Some(GrabCommand::Attempt);
+        //the grab animation brings its own grabbox, otherwise the grab system reaches out from the pushbox
+        if player.controller.animations.animations.contains_key("grab") {
+            player.attack(character_data, "grab".to_string());
+        }
+    } else if punch_kick_simultaneously && punch_kick_not_pressed && has_currency_to_activate {
         if let Some(active_item) = &mut player.active_item {
             
             let mut item = active_item.clone();
@@ -145,6 +179,8 @@ fn apply_input(player: &mut Player,
 
             player.active_item = Some(item);
         }
+    } else if let Some(special) = check_motion_inputs(player, character_data, motion_history, inputs_for_current_frame) {
+        player.attack(character_data, special);
     } else {
         if inputs_for_current_frame & GameAction::Punch as i32 > 0 {
             check_attack_inputs(
@@ -167,6 +203,10 @@ fn apply_input(player: &mut Player,
     }
     
     if inputs_for_current_frame & GameAction::Block as i32 > 0 { player.controller.is_blocking = true } else { player.controller.is_blocking = false }
+    //block + punch is a grab, not a parry
+    if newly_pressed(GameAction::Block) && !grab_attempt {
+        player.controller.start_parry_window();
+    }
 
     if inputs_for_current_frame & GameAction::Dash as i32 > 0 {
         player.controller.set_entity_state(EntityState::Dashing, &mut player.animator);
@@ -175,6 +215,21 @@ fn apply_input(player: &mut Player,
     if inputs_for_current_frame & GameAction::Slide as i32 > 0 {}
 }
 
+//which cancel window an input needs to interrupt the current attack
+fn cancel_kind(inputs: i32) -> CancelKind {
+    let attacking = GameAction::is_pressed(inputs, GameAction::Punch) || GameAction::is_pressed(inputs, GameAction::Kick);
+    let directional = GameAction::is_pressed(inputs, GameAction::Up) || GameAction::is_pressed(inputs, GameAction::Down);
+    if GameAction::is_pressed(inputs, GameAction::Dash) {
+        CancelKind::Dash
+    } else if GameAction::is_pressed(inputs, GameAction::Jump) {
+        CancelKind::Jump
+    } else if attacking && directional {
+        CancelKind::Special
+    } else {
+        CancelKind::Normal
+    }
+}
+
 fn check_for_dash_inputs(current_actions: i32, last_inputs: &VecDeque<i32>) -> bool {
     let len = last_inputs.len();
     if len >= 2 {
@@ -247,6 +302,32 @@ fn check_attack_inputs(
     }
 }
 
+//the first special whose motion just ended in one of the pressed buttons
+fn check_motion_inputs(
+    player: &Player,
+    character_data: &EntityData,
+    motion_history: &MotionHistory,
+    recent_inputs: i32
+) -> Option<String> {
+    let facing_dir = if GameAction::is_pressed(recent_inputs, GameAction::Right) {
+        1
+    } else if GameAction::is_pressed(recent_inputs, GameAction::Left) {
+        -1
+    } else {
+        player.controller.facing_dir
+    };
+
+    //same rules as the directional specials
+    if !player.controller.can_dash_attack() {
+        return None;
+    }
+
+    character_data.motions.iter()
+        .filter(|motion| {motion.is_airborne == player.controller.is_airborne && GameAction::is_pressed(recent_inputs, motion.button)})
+        .find(|motion| {motion_history.matches(motion, facing_dir)})
+        .map(|motion| {motion.attack.clone()})
+}
+
 fn check_directional_inputs(
     player: &mut Player,
     character_data: &EntityData,
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use parry2d::na::Vector2;
+use sdl2::{controller::Button, event::Event, keyboard::Keycode};
+
+use crate::input::controller_handler::{Controller, KEYBOARD_ID};
+
+use super::{characters::player::Player, effects::ItemEffects, items::Item};
+
+pub const PLAYER_2_ID: i32 = 2;
+//where player 2 drops in relative to player 1
+pub const PLAYER_2_JOIN_OFFSET: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum CoopLoot {
+    //currency is pooled on player 1 and every item goes to both players
+    Shared,
+    //each player keeps what they picked or paid for
+    Split,
+}
+
+//start on a pad, the keyboard only joins when a pad took player 1
+pub const JOIN_BUTTON: u8 = 7;
+pub const JOIN_KEY: Keycode = Keycode::F2;
+
+//player 2 only drops in when the device bound to it asks to, other inputs from it drive player 1 until then
+pub fn is_join_request(controllers: &Controller, event: &Event) -> bool {
+    let p2_device = match controllers.selected_controllers[1] {
+        Some(device_id) => device_id,
+        None => return false,
+    };
+    match *event {
+        Event::JoyButtonDown { which, button_idx, .. } => which == p2_device && button_idx == JOIN_BUTTON,
+        Event::ControllerButtonDown { which, button: Button::Start, .. } => which == p2_device,
+        Event::KeyDown { keycode: Some(JOIN_KEY), repeat: false, .. } => p2_device == KEYBOARD_ID,
+        _ => false,
+    }
+}
+
+//0 for player 1, 1 for player 2, until player 2 joins every device drives player 1
+//plugging in a pad moves the keyboard to the player 2 slot, it keeps playing for player 1 until then
+pub fn player_for_device(controllers: &Controller, device_id: u32, player2_joined: bool) -> usize {
+    match controllers.selected_controllers[1] {
+        Some(p2_device) if player2_joined && p2_device == device_id => 1,
+        _ => 0,
+    }
+}
+
+pub fn give_item(loot: CoopLoot, owner: &mut Player, other: Option<&mut Player>, item: &Item, effects: &HashMap<i32, ItemEffects>) {
+    owner.equip_item(&mut item.clone(), effects);
+    if let (CoopLoot::Shared, Some(other)) = (loot, other) {
+        other.equip_item(&mut item.clone(), effects);
+    }
+}
+
+//currency picked up by player 2 goes to the common pool after each match
+pub fn settle_currency(loot: CoopLoot, p1: &mut Player, p2: &mut Player) {
+    if loot == CoopLoot::Shared {
+        p1.currency += p2.currency;
+        p2.currency = 0;
+    }
+}
+
+//the player paying in stores and events, player 1 holds the pool when loot is shared
+pub fn buyer<'a>(loot: CoopLoot, player_index: usize, p1: &'a mut Player, p2: Option<&'a mut Player>) -> &'a mut Player {
+    match (loot, player_index, p2) {
+        (CoopLoot::Split, 1, Some(p2)) => p2,
+        _ => p1,
+    }
+}
+
+//the point the camera frames and enemies measure spread against
+pub fn players_center(players: &[&Player]) -> Vector2<f64> {
+    let alive = players.iter().filter(|player| {player.hp.0 > 0}).collect::<Vec<&&Player>>();
+    if alive.is_empty() {
+        return players[0].position;
+    }
+    alive.iter().map(|player| {player.position}).sum::<Vector2<f64>>() / alive.len() as f64
+}
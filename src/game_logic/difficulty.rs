@@ -0,0 +1,68 @@
+use std::fs;
+
+use rand::{Rng, prelude::SmallRng};
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct DifficultyCurve {
+    pub acts: u32,
+    //added to every level difficulty of an act, per act already cleared
+    pub act_difficulty: i32,
+    //overworld rows needed to go up one difficulty inside an act
+    pub rows_per_difficulty: i32,
+    //indexed by difficulty, anything past the end uses the last tier
+    pub tiers: Vec<DifficultyTier>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct DifficultyTier {
+    pub enemy_count_mod: f64,
+    pub hp_mod: f64,
+    pub damage_mod: f64,
+    //chance of an item tag rolling from the boss table instead of its own
+    pub rare_item_chance: f64,
+    pub archetype_weights: Vec<(String, u32)>,
+    //chance a grabbed enemy breaks free before it can be pummeled or thrown
+    #[serde(default)]
+    pub throw_tech_chance: f64,
+}
+
+impl DifficultyCurve {
+    pub fn tier(&self, difficulty: i32) -> &DifficultyTier {
+        let index = std::cmp::min(std::cmp::max(difficulty, 0) as usize, self.tiers.len() - 1);
+        &self.tiers[index]
+    }
+
+    pub fn level_difficulty(&self, act: u32, row: i32) -> i32 {
+        act as i32 * self.act_difficulty + (row - 1) / std::cmp::max(self.rows_per_difficulty, 1)
+    }
+}
+
+impl DifficultyTier {
+    //the fractional part of the modifier is the chance of one extra enemy
+    pub fn enemy_count(&self, rng: &mut SmallRng) -> u32 {
+        let extra = if rng.gen::<f64>() < self.enemy_count_mod.fract() { 1 } else { 0 };
+        std::cmp::max(self.enemy_count_mod as u32 + extra, 1)
+    }
+
+    pub fn pick_archetype(&self, rng: &mut SmallRng) -> Option<&str> {
+        let total = self.archetype_weights.iter().map(|(_, weight)| {*weight}).sum::<u32>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut random = rng.gen_range(0..total);
+        for (name, weight) in self.archetype_weights.iter() {
+            if random < *weight {
+                return Some(name);
+            }
+            random -= weight;
+        }
+        None
+    }
+}
+
+pub fn load_difficulty_curve(dir: String) -> DifficultyCurve {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir.clone()).unwrap();
+    serde_json::from_str::<DifficultyCurve>(&json_string).unwrap()
+}
@@ -1,380 +1,238 @@
-
 use sdl2::rect::Point;
 use parry2d::na::Vector2;
 use sdl2::render::{Texture, TextureCreator};
 use sdl2::video::WindowContext;
 
 use crate::asset_management::asset_holders::DirectionalAttack;
+use crate::engine_types::frame_data::load_animation_frame_data;
 use crate::game_logic::on_hit::basic_on_hits::dropper;
-use crate::{asset_management::{asset_holders::{EntityAnimations, EntityAssets, EntityData}, asset_loader::asset_loader::{self, load_textures_for_character}}, engine_types::{animation::Animation, sprite_data::SpriteData}, game_logic::{characters::{Attack, AttackType, Character, OnHitSpecificAttack, player::Player}, inputs::game_inputs::GameAction, on_hit::basic_on_hits::launch}};
+use crate::{asset_management::{asset_holders::{EntityAnimations, EntityAssets, EntityData}, asset_loader::asset_loader::{self, load_sprite_data_for_character, load_textures_for_character}}, engine_types::{animation::Animation, sprite_data::SpriteData}, game_logic::{characters::{Attack, AttackType, Character, OnHitSpecificAttack, player::Player}, inputs::{game_inputs::GameAction, motion_inputs::{Motion, MotionInput}}, on_hit::basic_on_hits::launch}};
 use std::collections::HashMap;
+use std::fs;
 use std::rc::Rc;
 use std::string::String;
 
-
-pub fn load_stage(texture_creator: &TextureCreator<WindowContext>) -> Texture {
-    asset_loader::load_texture(&texture_creator, "assets/stages/Sf3si-hugo.png")
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct CharacterDefinition {
+    pub name: String,
+    pub sprite_dir: String,
+    pub stats: CharacterStats,
+    pub animations: Vec<AnimationDefinition>,
+    pub auto_combos: HashMap<i32, Vec<String>>,
+    #[serde(default)]
+    pub directional_attacks: Vec<DirectionalAttackDefinition>,
+    pub attacks: HashMap<String, AttackDefinition>,
+    //startup, active, recovery and cancel windows per animation
+    #[serde(default)]
+    pub frame_data: Option<String>,
+    #[serde(default)]
+    pub motions: Vec<MotionDefinition>,
 }
 
-pub fn load_character(character_name: &str, spawn_pos: Point, id: i32, animations: Rc<EntityAnimations>) -> Player {
-    let fighter = match character_name {
-        "foxgirl" => Some(Character::new(
-            character_name.to_string(),
-            240,
-            200,
-            200,
-            250.0,
-            350.0,
-            700.0,
-            600.0,
-            false,
-            false,
-            2,
-            1,
-            2,
-            1,
-            0b0000u32,
-            3,
-            1,
-            3,
-            1
-        )),
-        _ => None,
-    }
-    .unwrap();
-    Player::new(id, fighter, spawn_pos, animations)
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct CharacterStats {
+    pub height: u32,
+    pub width: u32,
+    pub hp: i32,
+    pub speed: f64,
+    pub dash_speed: f64,
+    pub jump_height: f64,
+    pub jump_distance: f64,
+    pub can_double_jump: bool,
+    pub can_air_dash: bool,
+    pub punch_string: i8,
+    pub kick_string: i8,
+    pub airborne_punch_string: i8,
+    pub airborne_kick_string: i8,
+    pub directional_attacks_mask: u32,
+    pub punch_string_max: i8,
+    pub kick_string_max: i8,
+    pub airborne_punch_string_max: i8,
+    pub airborne_kick_string_max: i8,
 }
 
-pub fn load_character_animations(name: &str) -> EntityAnimations {
-    match name {
-        "foxgirl" => Some(load_foxgirl_animations()),
-        _ => None,
-    }.unwrap()
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct AnimationDefinition {
+    pub name: String,
+    //relative to sprite_dir
+    pub dir: String,
+    pub offsets: Option<Vec<(f64, f64)>>,
 }
 
-pub fn load_character_anim_data<'a>(
-    texture_creator: &'a TextureCreator<WindowContext>,
-    name: &str,
-) -> (EntityAssets<'a>, EntityData) {
-    match name {
-        "foxgirl" => Some((load_foxgirl_assets(texture_creator), load_foxgirl_data())),
-        _ => None,
-    }.unwrap()
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct DirectionalAttackDefinition {
+    pub mask: u32,
+    pub is_airborne: bool,
+    pub is_dashing: bool,
+    pub inputs: (String, String),
+    pub attack: String,
 }
 
-//===========================================================
-
-
-fn load_foxgirl_directional_inputs() ->   Vec<DirectionalAttack>{
-    let mut directional_inputs: Vec<DirectionalAttack> = Vec::new();
-    
-    directional_inputs.push(DirectionalAttack::new(0b0010u32, true, false, (GameAction::Down, GameAction::Kick), "dropper".to_string()) );
-    directional_inputs.push(DirectionalAttack::new(0b1000u32, true, false, (GameAction::Down, GameAction::Punch), "crash".to_string()) );
-    directional_inputs.push(DirectionalAttack::new(0b0001u32, false, false, (GameAction::Up, GameAction::Punch), "launcher".to_string()) );
-
-    directional_inputs.push(DirectionalAttack::new(0b0100u32, false, true, (GameAction::Left, GameAction::Punch), "dash_attack".to_string()) );
-    directional_inputs.push(DirectionalAttack::new(0b0100u32, false, true, (GameAction::Right, GameAction::Punch), "dash_attack".to_string()) );
-
-    directional_inputs
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct MotionDefinition {
+    pub motion: Motion,
+    pub button: String,
+    //frames the directions have to fit in
+    pub window: u32,
+    //frames between the last direction and the button
+    pub button_window: u32,
+    pub is_airborne: bool,
+    pub attack: String,
 }
 
-fn load_foxgirl_anims() -> HashMap<String, Animation> {
-
-    let idle_anim =
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/idle", "idle");
-
-    let take_damage_anim =
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/take_damage/1", "take_damage");
-
-    let dead_anim=
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/dead", "dead");
-
-    let walk_anim=
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/walk", "walk");
-
-    let crouch_start_anim =
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/crouch/crouched", "crouch");
-
-    let crouch_idle_anim=
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/crouch/crouching", "crouching");
-
-    let light_punch_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/light_punch", "light_punch");
-
-    let mut medium_punch_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/medium_punch", "medium_punch");
-
-    let heavy_punch_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/heavy_punch", "heavy_punch");
-
-    let mut light_kick_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/light_kick", "light_kick");
-
-    let airborne_light_kick_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/light_kick", "airborne_light_kick");
-
-    let mut airborne_crash_kick_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/crash", "crash");
-
-    let airborne_dropper_kick_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/down_kick", "dropper");
-
-    let airborne_punch = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/punch", "airborne_punch");
-
-    let airborne_poke = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/poke", "airborne_poke");
-
-    let airborne_slash = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/attacks/slash", "airborne_slash");
-
-    let launcher_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/launcher", "launcher");
-
-    let mut dash_attack_anim = 
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/attacks/dash_attack", "dash_attack");
-
-    let mut air_dash_anim=
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/airborne/dash", "air-dash");
-    let mut dash_anim=
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/dash", "dash");
-    let neutral_jump_anim =
-        asset_loader::load_anim_and_data_from_dir("assets/foxgirl/standing/neutral_jump", "neutral_jump");
-
-    let mut character_anims = HashMap::new();
-
-    character_anims.insert(airborne_punch.name.clone(),airborne_punch);
-    character_anims.insert(airborne_poke.name.clone(),airborne_poke);
-    character_anims.insert(airborne_slash.name.clone(),airborne_slash);
-
-    character_anims.insert(idle_anim.name.clone(),idle_anim);
-    character_anims.insert(take_damage_anim.name.clone(),take_damage_anim);
-    character_anims.insert(dead_anim.name.clone(),dead_anim);
-
-    
-    air_dash_anim.offsets = Some(vec![Vector2::new(0.0, 0.0), Vector2::new(6000.0, 0.0), Vector2::new(4000.0, 0.0), Vector2::new(1000.0, 0.0)]);
-    character_anims.insert(air_dash_anim.name.clone(),air_dash_anim);
-
-    dash_anim.offsets = Some(vec![Vector2::new(0.0, 0.0), Vector2::new(8000.0, 0.0), Vector2::new(5000.0, 0.0), 
-    Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0) , Vector2::new(0.0, 0.0) , Vector2::new(0.0, 0.0) , Vector2::new(0.0, 0.0) , Vector2::new(0.0, 0.0)]);
-    character_anims.insert(dash_anim.name.clone(),dash_anim);
-
-    dash_attack_anim.offsets =Some(vec![Vector2::new(3000.0, 0.0), Vector2::new(1000.0, 0.0), Vector2::new(500.0, 0.0), Vector2::new(0.0, 0.0)]);
-    character_anims.insert(dash_attack_anim.name.clone(),dash_attack_anim);
-    
-    character_anims.insert(walk_anim.name.clone(),walk_anim);
-    character_anims.insert(light_punch_anim.name.clone(),light_punch_anim);
-
-
-    medium_punch_anim.offsets = Some(vec![Vector2::new(100.0, 0.0), Vector2::new(0.0, 0.0), Vector2::new(50.0, 0.0), 
-    Vector2::new(1000.0, 0.0), Vector2::new(400.0, 0.0),Vector2::new(50.0, 0.0), Vector2::new(50.0, 0.0), Vector2::new(50.0, 0.0), Vector2::new(50.0, 0.0), 
-    Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0),  Vector2::new(0.0, 0.0),  Vector2::new(0.0, 0.0),  Vector2::new(0.0, 0.0)]);
-    character_anims.insert(medium_punch_anim.name.clone(),medium_punch_anim);
-
-    character_anims.insert(heavy_punch_anim.name.clone(),heavy_punch_anim);
-
-    light_kick_anim.offsets =Some(vec![Vector2::new(400.0, 0.0), Vector2::new(100.0, 0.0), Vector2::new(0.0, 0.0), 
-    Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0), Vector2::new(300.0, 0.0), Vector2::new(300.0, 0.0), 
-    Vector2::new(300.0, 0.0), Vector2::new(300.0, 0.0),  Vector2::new(300.0, 0.0)]);
-    character_anims.insert(light_kick_anim.name.clone(),light_kick_anim);
-
-    character_anims.insert(airborne_light_kick_anim.name.clone(),airborne_light_kick_anim);
-    character_anims.insert(airborne_dropper_kick_anim.name.clone(),airborne_dropper_kick_anim);
-
-    airborne_crash_kick_anim.offsets =Some(vec![Vector2::new(0.0, -2000.0), Vector2::new(0.0, -4000.0), Vector2::new(0.0, -2000.0), Vector2::new(0.0, -1000.0)]);
-    character_anims.insert(airborne_crash_kick_anim.name.clone(),airborne_crash_kick_anim);
-    
-    character_anims.insert(crouch_start_anim.name.clone(),crouch_start_anim);
-    character_anims.insert(crouch_idle_anim.name.clone(),crouch_idle_anim);
-    character_anims.insert(neutral_jump_anim.name.clone(),neutral_jump_anim);
-    
-    character_anims.insert(launcher_anim.name.clone(),launcher_anim);
-
-    character_anims
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct AttackDefinition {
+    pub damage: i32,
+    pub stun_on_hit: i32,
+    pub stun_on_block: i32,
+    pub push_back: f64,
+    //defaults to the damage
+    pub guard_damage: Option<i32>,
+    pub attack_type: AttackType,
+    pub on_hit: Option<String>,
 }
 
-fn load_foxgirl_attacks() -> HashMap<String, Attack> {
-    let mut attacks = HashMap::new();
-
-    attacks.insert(
-        "lk".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 400.0,
-            attack_type: AttackType::Kick,
-            on_hit: None,
-        },
-    );
-
-    attacks.insert(
-        "j.lk".to_string(),
-        Attack {
-            damage: 15,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 300.0,
-            attack_type: AttackType::AirborneKick,
-            on_hit: None,
-        },
-    );
-
-    attacks.insert(
-        "j.p".to_string(),
-        Attack {
-            damage: 15,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 300.0,
-            attack_type: AttackType::AirbornePunch,
-            on_hit: None,
-        },
-    );
+pub fn load_stage(texture_creator: &TextureCreator<WindowContext>) -> Texture {
+    asset_loader::load_texture(&texture_creator, "assets/stages/Sf3si-hugo.png")
+}
 
-    attacks.insert(
-        "poke".to_string(),
-        Attack {
-            damage: 15,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 300.0,
-            attack_type: AttackType::AirbornePunch,
-            on_hit: None,
-        },
-    );
+pub fn load_character_definition(name: &str) -> CharacterDefinition {
+    let dir = format!("assets/characters/{}.json", name);
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(&dir).unwrap();
+    serde_json::from_str::<CharacterDefinition>(&json_string).unwrap_or_else(|e| panic!("invalid character definition {}: {}", dir, e))
+}
 
-    attacks.insert(
-        "slash".to_string(),
-        Attack {
-            damage: 15,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 300.0,
-            attack_type: AttackType::AirbornePunch,
-            on_hit: None,
-        },
-    );
+//each definition is read once and kept for every loader below
+pub fn character_definition<'a>(definitions: &'a mut HashMap<String, CharacterDefinition>, name: &str) -> &'a CharacterDefinition {
+    definitions.entry(name.to_string()).or_insert_with(|| {load_character_definition(name)})
+}
 
-    attacks.insert(
-        "lp".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 50.0,
-            attack_type: AttackType::Punch,
-            on_hit: None,
-        },
+pub fn load_character(definition: &CharacterDefinition, spawn_pos: Point, id: i32, animations: Rc<EntityAnimations>) -> Player {
+    let stats = &definition.stats;
+    let fighter = Character::new(
+        definition.name.clone(),
+        stats.height,
+        stats.width,
+        stats.hp,
+        stats.speed,
+        stats.dash_speed,
+        stats.jump_height,
+        stats.jump_distance,
+        stats.can_double_jump,
+        stats.can_air_dash,
+        stats.punch_string,
+        stats.kick_string,
+        stats.airborne_punch_string,
+        stats.airborne_kick_string,
+        stats.directional_attacks_mask,
+        stats.punch_string_max,
+        stats.kick_string_max,
+        stats.airborne_punch_string_max,
+        stats.airborne_kick_string_max,
     );
+    Player::new(id, fighter, spawn_pos, animations)
+}
 
-    attacks.insert(
-        "mp".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 10,
-            stun_on_block: 4,
-            push_back: 1250.0,
-            attack_type: AttackType::Punch,
-            on_hit: None,
-        },
-    );
+pub fn load_character_animations(definition: &CharacterDefinition) -> EntityAnimations {
+    EntityAnimations {
+        animations: load_animations(definition),
+        projectile_animation: HashMap::new(),
+    }
+}
 
-    attacks.insert(
-        "hp".to_string(),
-        Attack {
-            damage: 10,
-            stun_on_hit: 20,
-            stun_on_block: 14,
-            push_back: 550.0,
-            attack_type: AttackType::Punch,
-            on_hit: None,
+pub fn load_character_anim_data<'a>(
+    texture_creator: &'a TextureCreator<WindowContext>,
+    definition: &CharacterDefinition,
+) -> (EntityAssets<'a>, EntityData) {
+    let (textures, data) = load_textures_for_character(texture_creator, &definition.sprite_dir);
+    (
+        EntityAssets {
+            textures,
+            texture_data: data,
         },
-    );
+        make_entity_data(definition)
+    )
+}
 
-    attacks.insert(
-        "launcher".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 20,
-            stun_on_block: 14,
-            push_back: 0.0,
-            attack_type: AttackType::Special,
-            on_hit: Some(launch as OnHitSpecificAttack),
-        },
-    );
+pub fn load_character_data(definition: &CharacterDefinition) -> EntityData {
+    make_entity_data(definition)
+}
 
-    attacks.insert(
-        "dropper".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 20,
-            stun_on_block: 14,
-            push_back: 0.0,
-            attack_type: AttackType::Special,
-            on_hit: Some(dropper as OnHitSpecificAttack),
-        },
-    );
+pub fn load_character_sprite_data(definition: &CharacterDefinition) -> HashMap<String, SpriteData> {
+    load_sprite_data_for_character(&definition.sprite_dir)
+}
 
-    attacks.insert(
-        "crash".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 20,
-            stun_on_block: 14,
-            push_back: 0.0,
-            attack_type: AttackType::Special,
-            on_hit: None,
-        },
-    );
+//===========================================================
 
-    attacks.insert(
-        "dash".to_string(),
-        Attack {
-            damage: 5,
-            stun_on_hit: 20,
-            stun_on_block: 14,
-            push_back: 100.0,
-            attack_type: AttackType::Special,
-            on_hit: None,
-        },
-    );
+fn load_animations(definition: &CharacterDefinition) -> HashMap<String, Animation> {
+    let mut character_anims = HashMap::new();
+    let mut frame_data = match &definition.frame_data {
+        Some(dir) => load_animation_frame_data(dir),
+        None => HashMap::new(),
+    };
+
+    for anim_definition in definition.animations.iter() {
+        let mut anim = asset_loader::load_anim_and_data_from_dir(
+            &format!("{}/{}", definition.sprite_dir, anim_definition.dir), 
+            &anim_definition.name
+        );
+        if let Some(offsets) = &anim_definition.offsets {
+            anim.offsets = Some(offsets.iter().map(|&(x, y)| {Vector2::new(x, y)}).collect());
+        }
+        anim.frame_data = frame_data.remove(&anim.name);
+        character_anims.insert(anim.name.clone(), anim);
+    }
 
-    attacks
+    character_anims
 }
 
-fn load_foxgirl_animations() -> EntityAnimations {
-    let anims= load_foxgirl_anims();
-    EntityAnimations {
-        animations: anims,
-        projectile_animation: HashMap::new(),
+fn make_entity_data(definition: &CharacterDefinition) -> EntityData {
+    EntityData {
+        auto_combo_strings: definition.auto_combos.clone(),
+        directional_variation_anims: definition.directional_attacks.iter().map(|attack| {make_directional_attack(&definition.name, attack)}).collect(),
+        attacks: definition.attacks.iter().map(|(key, attack)| {(key.clone(), make_attack(&definition.name, key, attack))}).collect(),
+        motions: definition.motions.iter().map(|motion| {make_motion_input(&definition.name, motion)}).collect(),
     }
 }
 
-fn load_foxgirl_assets(texture_creator: &TextureCreator<WindowContext>) -> EntityAssets {
-    let (textures, data) = load_textures_for_character(texture_creator, "assets/foxgirl");
-    EntityAssets {
-        textures,
-        texture_data: data,
+fn make_motion_input(character: &str, motion: &MotionDefinition) -> MotionInput {
+    MotionInput {
+        motion: motion.motion.clone(),
+        button: GameAction::from_name(&motion.button).unwrap_or_else(|| panic!("{}: unknown button {} in motion {}", character, motion.button, motion.attack)),
+        window: motion.window,
+        button_window: motion.button_window,
+        is_airborne: motion.is_airborne,
+        attack: motion.attack.clone(),
     }
 }
 
-fn load_foxgirl_auto_combos() -> HashMap<i32, Vec<&'static str>> {
-    let mut auto_combos = HashMap::new();
-
-    auto_combos.insert(0, vec!["light_punch", "heavy_punch", "medium_punch"]);
-    auto_combos.insert(1, vec!["light_kick"]);
-
-    auto_combos.insert(2, vec!["airborne_poke", "airborne_slash", "airborne_punch"]);
-    auto_combos.insert(3, vec!["airborne_light_kick"]);
-
-    auto_combos
+fn make_directional_attack(character: &str, attack: &DirectionalAttackDefinition) -> DirectionalAttack {
+    let input = |name: &str| {
+        GameAction::from_name(name).unwrap_or_else(|| panic!("{}: unknown input {} in directional attack {}", character, name, attack.attack))
+    };
+    DirectionalAttack::new(
+        attack.mask, 
+        attack.is_airborne, 
+        attack.is_dashing, 
+        (input(&attack.inputs.0), input(&attack.inputs.1)), 
+        attack.attack.clone()
+    )
 }
 
-fn load_foxgirl_data() -> EntityData {
-    EntityData {
-        auto_combo_strings: load_foxgirl_auto_combos(),
-        directional_variation_anims: load_foxgirl_directional_inputs(),
-        attacks: load_foxgirl_attacks(),
+fn make_attack(character: &str, key: &str, attack: &AttackDefinition) -> Attack {
+    Attack {
+        damage: attack.damage,
+        stun_on_hit: attack.stun_on_hit,
+        stun_on_block: attack.stun_on_block,
+        push_back: attack.push_back,
+        guard_damage: attack.guard_damage.unwrap_or(attack.damage),
+        attack_type: attack.attack_type.clone(),
+        on_hit: match attack.on_hit.as_deref() {
+            Some("launch") => Some(launch as OnHitSpecificAttack),
+            Some("dropper") => Some(dropper as OnHitSpecificAttack),
+            Some(on_hit) => panic!("{}: unknown on_hit {} in attack {}", character, on_hit, key),
+            None => None,
+        },
     }
 }
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs};
 
+use parry2d::na::Vector2;
 use sdl2::{render::TextureCreator, video::WindowContext};
 
-use crate::{asset_management::{asset_holders::{EntityAnimations, EntityAssets}, asset_loader::asset_loader::{load_anim_and_data_from_dir, load_anim_from_dir, load_textures_for_character}}, engine_types::{animation::Animation, sprite_data::SpriteData}, game_logic::characters::{Attack, Character}};
+use crate::{asset_management::{asset_holders::{EntityAnimations, EntityAssets}, asset_loader::asset_loader::{load_anim_and_data_from_dir, load_anim_from_dir, load_sprite_data_for_character, load_textures_for_character}}, engine_types::{animation::Animation, frame_data::FrameData, sprite_data::SpriteData}, ecs_system::enemy_components::Behaviour, enemy_behaviour::simple_enemy_behaviour::{basic_enemy, brawler_enemy, ranged_enemy, skirmisher_enemy}, game_logic::characters::{Attack, Character}};
 
 pub fn load_enemy_ryu_assets(texture_creator: &TextureCreator<WindowContext>) -> EntityAssets {
     let (textures, data) = load_textures_for_character(texture_creator, "assets/keetar");
@@ -12,6 +13,10 @@ pub fn load_enemy_ryu_assets(texture_creator: &TextureCreator<WindowContext>) ->
     }
 }
 
+pub fn load_enemy_ryu_sprite_data() -> HashMap<String, SpriteData> {
+    load_sprite_data_for_character("assets/keetar")
+}
+
 pub fn load_enemy_ryu_animations() -> EntityAnimations {
     let anims= load_enemy_ryu_anims();
     EntityAnimations {
@@ -66,35 +71,134 @@ fn load_enemy_ryu_anims() -> HashMap<String, Animation> {
     character_anims.insert(launched_anim.name.clone(), launched_anim);
     character_anims.insert(knocked_landing_anim.name.clone(), knocked_landing_anim);
 
+    for variant in enemy_attack_variants(&character_anims["attack"]) {
+        character_anims.insert(variant.name.clone(), variant);
+    }
+
     character_anims
 }
 
-pub fn load_enemy(character_name: &str) -> Character {
-    match character_name {
-        "ryu" => Some(Character::new(
-            character_name.to_string(),
-            240,
-            200,
-            50,
-            250.0,
-            350.0,
-            500.0,
-            600.0,
-            false,
-            false,
-            2,
-            2,
-            0,
-            0,
-            0b0000u32,
-            2,
-            2,
-            0,
-            0,
-        )),
+//the sprite set only has one attack sheet, the behaviour trees string these variants together
+fn enemy_attack_variants(attack: &Animation) -> Vec<Animation> {
+    let mut jab = attack.clone();
+    jab.name = "jab".to_string();
+
+    //steps in during the first half of the swing
+    let mut lunge = attack.clone();
+    lunge.name = "lunge".to_string();
+    let n_sprites = lunge.sprites.len();
+    lunge.offsets = Some((0..n_sprites).map(|i| {
+        if i < n_sprites / 2 { Vector2::new(600f64, 0f64) } else { Vector2::new(0f64, 0f64) }
+    }).collect());
+
+    //same swing but leaves the target stunned longer, ends a string
+    let mut heavy = attack.clone();
+    heavy.name = "heavy".to_string();
+    heavy.frame_data = Some(FrameData {
+        startup: 0,
+        active: heavy.length as u32 + 1,
+        recovery: 0,
+        hit_stun: Some(22),
+        block_stun: Some(14),
+        cancels: Vec::new(),
+    });
+
+    vec![jab, lunge, heavy]
+}
 
-        _ => None,
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct EnemyArchetype {
+    pub name: String,
+    //key of the animations and textures, several archetypes can share the same sprites
+    pub sprite_set: String,
+    pub stats: EnemyStats,
+    pub behaviour: String,
+    pub attack_delay: f64,
+    pub engage_distance: f64,
+    pub loot_table: Option<String>,
+    #[serde(default)]
+    pub drop_chance: f64,
+    #[serde(default)]
+    pub score: u32,
+    //only archetypes that shoot have one
+    #[serde(default)]
+    pub projectile: Option<EnemyProjectile>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct EnemyProjectile {
+    pub damage: i32,
+    pub stun_on_hit: i32,
+    pub stun_on_block: i32,
+    pub push_back: f64,
+    //pixels per logic frame
+    pub speed: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct EnemyStats {
+    pub height: u32,
+    pub width: u32,
+    pub hp: i32,
+    pub speed: f64,
+    pub dash_speed: f64,
+    pub jump_height: f64,
+    pub jump_distance: f64,
+}
+
+pub const DEFAULT_ENEMY_ARCHETYPE: &str = "grunt";
+
+pub fn load_enemy_archetypes(dir: String) -> HashMap<String, EnemyArchetype> {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir.clone()).unwrap();
+    let archetypes = serde_json::from_str::<Vec<EnemyArchetype>>(&json_string).unwrap();
+    for archetype in archetypes.iter() {
+        if make_behaviour(&archetype.behaviour, archetype.attack_delay, archetype.engage_distance).is_none() {
+            panic!("{}: enemy {} has unknown behaviour {}", dir, archetype.name, archetype.behaviour);
+        }
     }
-    .unwrap()
+
+    archetypes.into_iter().map(|archetype| {(archetype.name.clone(), archetype)}).collect()
+}
+
+pub fn load_enemy(archetype: &EnemyArchetype) -> Character {
+    let stats = &archetype.stats;
+    Character::new(
+        archetype.sprite_set.clone(),
+        stats.height,
+        stats.width,
+        stats.hp,
+        stats.speed,
+        stats.dash_speed,
+        stats.jump_height,
+        stats.jump_distance,
+        false,
+        false,
+        2,
+        2,
+        0,
+        0,
+        0b0000u32,
+        2,
+        2,
+        0,
+        0,
+    )
 }
 
+//behaviours are checked when the archetypes and bosses are loaded
+pub fn load_enemy_behaviour(archetype: &EnemyArchetype) -> Box<dyn Behaviour> {
+    make_behaviour(&archetype.behaviour, archetype.attack_delay, archetype.engage_distance).unwrap()
+}
+
+pub fn make_behaviour(behaviour: &str, attack_delay: f64, engage_distance: f64) -> Option<Box<dyn Behaviour>> {
+    match behaviour {
+        "basic" => Some(Box::new(basic_enemy(attack_delay, engage_distance))),
+        "brawler" => Some(Box::new(brawler_enemy(attack_delay, engage_distance))),
+        "skirmisher" => Some(Box::new(skirmisher_enemy(attack_delay, engage_distance))),
+        "ranged" => Some(Box::new(ranged_enemy(attack_delay, engage_distance))),
+        _ => None,
+    }
+}
@@ -1,18 +1,24 @@
 use std::{collections::HashMap, rc::Rc};
 
 use parry2d::na::Vector2;
-use rand::{Rng, SeedableRng, prelude::SmallRng};
-use sdl2::{pixels::Color, rect::Rect, render::TextureQuery};
+use rand::Rng;
+use sdl2::{pixels::Color, rect::Rect};
 
-use crate::{GameStateData, asset_management::{asset_holders::{EntityAnimations, LevelAssets}, cast_point::CastPoint, common_assets::CommonAssets, rng_tables::LootTable, vfx::particle::Particle}, ecs_system::enemy_manager::EnemyManager, level_generation::Level, rendering::camera::Camera};
+use crate::{asset_management::{asset_holders::{EntityAnimations, EntityData, VfxData}, cast_point::CastPoint, rng_tables::{BOSS_TABLE, LootTable}, vfx::particle::Particle}, challenges::ChallengeManager, collision::{collision_detection::calculate_hits, pushbox_resolution::resolve_pushboxes}, ecs_system::{enemy_components::DamageModifier, enemy_manager::EnemyManager, enemy_systems::{update_animations_enemies, update_colliders_enemies, update_events, update_movement_enemies}}, enemy_behaviour::update_behaviour_enemies, engine_types::{collider::ColliderType, sprite_data::SpriteData}, level_generation::Level, rendering::camera::Camera, telemetry::TelemetryEvent};
 
-use super::{characters::player::Player, inputs::input_cycle::AllInputManagement, items::{Item, ItemGround, get_random_item}, projectile::Projectile};
+use super::{ascension::RunModifiers, boss::Boss, coop::{CoopLoot, PLAYER_2_JOIN_OFFSET, give_item}, difficulty::DifficultyTier, characters::{player::{EntityState, Player}, player_input::{apply_input_state, process_input}}, combo_string::ComboCounter, effects::ItemEffects, factories::enemy_factory::{DEFAULT_ENEMY_ARCHETYPE, EnemyArchetype}, inputs::input_cycle::AllInputManagement, items::{Item, ItemGround, get_random_item}, grab::{Grab, Thrown, update_grabs}, projectile::{Projectile, spawn_enemy_projectile, update_enemy_projectiles}, rng_streams::{RngStream, stream_rng}, run_stats::RunStats};
 
 const LIMIT_NUMBER_OF_VFX: usize = 20;
+const ITEM_PICKUP_DISTANCE: f64 = 50.0;
+const BOSS_PHASE_HIT_STOP: i32 = 20;
+const ENEMY_SPAWN_SPACING: f64 = 80.0;
+
 pub struct Game {
     pub is_finished: bool,
     pub current_frame: i32,
     pub player: Player,
+    //drop in co-op, plays the same character as player 1
+    pub player2: Option<Player>,
     pub enemies: EnemyManager,
     pub camera: Camera,
 
@@ -21,7 +27,51 @@ pub struct Game {
     pub hit_vfx: Vec<Particle>,
     pub items_on_ground: Vec<ItemGround>,
 
-    pub levels: Vec<Level>
+    pub levels: Vec<Level>,
+
+    pub hit_stop: i32,
+    pub score: u32,
+    pub boss: Option<Boss>,
+    //sound ids requested by the simulation, whoever is presenting the game drains and plays them
+    pub sounds: Vec<String>,
+    pub stats: RunStats,
+    //same as sounds, the scene drains these into the telemetry log
+    pub telemetry: Vec<TelemetryEvent>,
+    pub grabs: Vec<Grab>,
+    pub thrown: Vec<Thrown>,
+}
+
+//everything the simulation reads from outside the Game, none of it needs a renderer
+pub struct StepContext<'a> {
+    pub p1_data: &'a EntityData,
+    pub p1_sprite_data: &'a HashMap<String, SpriteData>,
+    pub enemy_sprite_data: &'a HashMap<String, HashMap<String, SpriteData>>,
+    pub vfx: &'a VfxData,
+
+    pub items: &'a HashMap<i32, Item>,
+    pub effects: &'a HashMap<i32, ItemEffects>,
+    pub enemy_animations: &'a HashMap<String, Rc<EntityAnimations>>,
+    pub enemy_archetypes: &'a HashMap<String, EnemyArchetype>,
+    pub difficulty: &'a DifficultyTier,
+    pub modifiers: &'a RunModifiers,
+    pub loot_tables: &'a mut HashMap<String, LootTable>,
+
+    pub seed: u64,
+    pub curr_act: u32,
+    pub curr_level: i32,
+
+    pub friendly_fire: bool,
+    pub coop_loot: CoopLoot,
+
+    pub challenges: &'a mut ChallengeManager,
+    pub combo_counter: &'a mut ComboCounter,
+}
+
+pub struct StepResult {
+    //false while in hit stop
+    pub advanced: bool,
+    pub picked_items: Vec<Item>,
+    pub left_level: bool,
 }
 
 impl Game {
@@ -31,6 +81,7 @@ impl Game {
             current_frame: 0,
 
             player,
+            player2: None,
             enemies: EnemyManager::new(),
             camera,
 
@@ -40,7 +91,380 @@ impl Game {
             items_on_ground: Vec::new(),
             
             levels,
+
+            hit_stop: 0,
+            score: 0,
+            boss: None,
+            sounds: Vec::new(),
+            stats: RunStats::default(),
+            telemetry: Vec::new(),
+            grabs: Vec::new(),
+            thrown: Vec::new(),
+        }
+    }
+
+    pub fn start_level(&mut self) {
+        let start_pos = Vector2::new(150.0f64, 150f64);
+        Game::enter_level(&mut self.player, &mut self.enemies, start_pos);
+        if let Some(player2) = self.player2.as_mut() {
+            Game::enter_level(player2, &mut self.enemies, start_pos + Vector2::new(PLAYER_2_JOIN_OFFSET, 0f64));
+        }
+    }
+
+    //player 2 can join at any point of a match, they show up next to player 1
+    pub fn join_player2(&mut self, mut player: Player) {
+        let join_pos = self.player.position + Vector2::new(PLAYER_2_JOIN_OFFSET, 0f64);
+        Game::enter_level(&mut player, &mut self.enemies, join_pos);
+        self.player2 = Some(player);
+    }
+
+    fn enter_level(player: &mut Player, enemies: &mut EnemyManager, position: Vector2<f64>) {
+        player.position = position;
+
+        player
+            .animator
+            .play(player.controller.animations.animations.get("idle").unwrap().clone(), 1.0,false);
+
+        player.collision_manager.init_colliders(&player.animator);
+
+        let mut start_level_events = player.events.on_start_level.clone();
+        for event_on_lvl_start in start_level_events.iter_mut() {
+            (event_on_lvl_start.0)(player, enemies, -1, &mut event_on_lvl_start.1);
+        }
+        player.events.on_start_level = start_level_events;
+    }
+
+    pub fn players(&self) -> Vec<&Player> {
+        Game::collect_players(&self.player, &self.player2)
+    }
+
+    pub fn players_mut(&mut self) -> Vec<&mut Player> {
+        Game::collect_players_mut(&mut self.player, &mut self.player2)
+    }
+
+    //field level versions, for when the enemies or the camera are borrowed at the same time
+    fn collect_players<'a>(player: &'a Player, player2: &'a Option<Player>) -> Vec<&'a Player> {
+        let mut players = vec![player];
+        if let Some(player2) = player2 {
+            players.push(player2);
         }
+        players
+    }
+
+    fn collect_players_mut<'a>(player: &'a mut Player, player2: &'a mut Option<Player>) -> Vec<&'a mut Player> {
+        let mut players = vec![player];
+        if let Some(player2) = player2.as_mut() {
+            players.push(player2);
+        }
+        players
+    }
+
+    pub fn step(&mut self, inputs: &mut AllInputManagement, p2_inputs: Option<&mut AllInputManagement>, ctx: &mut StepContext, dt: f64) -> StepResult {
+        let mut result = StepResult {
+            advanced: false,
+            picked_items: Vec::new(),
+            left_level: false,
+        };
+
+        if self.hit_stop > 0 {
+            self.hit_stop -= 1;
+            return result;
+        }
+        result.advanced = true;
+
+        self.current_frame += 1;
+        self.stats.time += dt;
+        //hp is compared over the whole step so hits, projectiles, grabs and item effects all count
+        let enemy_hp_before = self.enemy_hps();
+        let player_hp_before = self.players().iter().map(|player| {player.hp.0.max(0)}).collect::<Vec<i32>>();
+        let currency_before = self.players().iter().map(|player| {player.currency}).sum::<u32>();
+        let telemetry_before = self.telemetry.len();
+        let alive_before = self.players().iter()
+            .filter(|player| {player.controller.state != EntityState::Dead})
+            .map(|player| {player.id})
+            .collect::<Vec<i32>>();
+
+        Game::update_player(&mut self.player, inputs, ctx, &mut self.enemies, &mut self.camera, self.is_finished, &mut self.sounds, dt);
+        if let (Some(player2), Some(p2_inputs)) = (self.player2.as_mut(), p2_inputs) {
+            Game::update_player(player2, p2_inputs, ctx, &mut self.enemies, &mut self.camera, self.is_finished, &mut self.sounds, dt);
+        }
+
+        self.pickup_items(ctx, &mut result.picked_items);
+        self.stats.items_collected += result.picked_items.len() as u32;
+
+        update_animations_enemies(&mut self.enemies);
+        let shooters = update_behaviour_enemies(&mut self.enemies, &Game::collect_players(&self.player, &self.player2), dt);
+        for enemy in shooters {
+            let definition = self.enemies.reward_components[enemy].as_ref()
+                .and_then(|reward| {ctx.enemy_archetypes.get(&reward.archetype)})
+                .and_then(|archetype| {archetype.projectile.as_ref()});
+            if let Some(projectile) = definition.and_then(|definition| {spawn_enemy_projectile(&self.enemies, enemy, definition)}) {
+                self.projectiles.push(projectile);
+            }
+        }
+        update_movement_enemies(&mut self.enemies, &mut self.camera, dt, &mut self.sounds);
+        update_events(&mut self.enemies, &mut Game::collect_players_mut(&mut self.player, &mut self.player2), dt);
+        update_colliders_enemies(&mut self.enemies, ctx.enemy_sprite_data);
+
+        let camera_rect = self.camera.rect;
+        let level_width = self.max_level_width();
+        let boss_id = self.boss.as_ref().map(|boss| {boss.enemy_id});
+
+        //built once after the hp snapshots and reused by every combat system below
+        let mut players = Game::collect_players_mut(&mut self.player, &mut self.player2);
+        let start_positions = players.iter().map(|player| {player.position}).collect::<Vec<Vector2<f64>>>();
+        update_enemy_projectiles(&mut self.projectiles, &mut players, &self.camera, &mut self.hit_stop, &mut self.sounds, &mut self.telemetry, dt);
+        self.projectiles.retain(|p| p.is_alive);
+
+        for (player, start_pos) in players.iter_mut().zip(start_positions.into_iter()) {
+            if player.position != start_pos {
+                Game::update_player_colliders_position_only(player, start_pos);
+            }
+        }
+
+        resolve_pushboxes(&mut players, &mut self.enemies, camera_rect, level_width);
+
+        update_grabs(&mut players,
+            &mut self.enemies,
+            &mut self.grabs,
+            &mut self.thrown,
+            boss_id,
+            ctx.difficulty.throw_tech_chance,
+            &mut self.hit_stop,
+            &mut self.sounds,
+            &mut self.telemetry,
+            dt);
+        calculate_hits(&mut players, 
+            &mut self.enemies, 
+            &mut self.hit_vfx, 
+            &mut self.hit_stop, 
+            dt, 
+            &mut self.sounds, 
+            ctx.vfx, 
+            ctx.p1_data,
+            ctx.combo_counter,
+            ctx.challenges,
+            &mut self.camera,
+            ctx.friendly_fire,
+            &mut self.telemetry);
+
+        self.stats.best_combo = self.stats.best_combo.max(ctx.combo_counter.counter);
+
+        self.resolve_kills(ctx);
+
+        if let Some(boss) = self.boss.as_mut() {
+            if boss.update(&mut self.enemies) {
+                self.hit_stop = BOSS_PHASE_HIT_STOP;
+                self.sounds.push("boss_phase".to_string());
+            }
+        }
+
+        self.fx(ctx.vfx);
+        self.update_vfx(ctx.vfx);
+
+        let level_width = self.max_level_width();
+        self.camera.update(level_width, &Game::collect_players(&self.player, &self.player2), dt);
+        self.check_level_tags_and_apply(ctx);
+
+        self.is_finished = self.check_finished_level();
+        let camera_rect = self.camera.rect;
+        let is_finished = self.is_finished;
+        for player in self.players_mut() {
+            result.left_level |= Game::keep_player_in_camera(player, camera_rect, is_finished);
+        }
+        if result.left_level {
+            self.stats.floors_cleared += 1;
+        }
+
+        //enemies spawned during the step only count from the next one
+        let enemy_hp_after = self.enemy_hps();
+        for (i, before) in enemy_hp_before.iter().enumerate() {
+            if let Some(before) = before {
+                let after = enemy_hp_after.get(i).cloned().flatten().unwrap_or(0);
+                self.stats.damage_dealt += (before - after).max(0) as u32;
+            }
+        }
+        for (player, before) in self.players().iter().zip(player_hp_before.iter()) {
+            self.stats.damage_taken += (before - player.hp.0.max(0)).max(0) as u32;
+        }
+
+        for event in self.telemetry[telemetry_before..].iter() {
+            match event {
+                TelemetryEvent::Hit { .. } => self.stats.hits_landed += 1,
+                TelemetryEvent::DamageTaken { .. } => self.stats.hits_taken += 1,
+                _ => {},
+            }
+        }
+
+        let currency_after = self.players().iter().map(|player| {player.currency}).sum::<u32>();
+        self.stats.currency_earned += currency_after.saturating_sub(currency_before);
+
+        let died = self.players().iter()
+            .filter(|player| {player.controller.state == EntityState::Dead && alive_before.contains(&player.id)})
+            .map(|player| {player.id})
+            .collect::<Vec<i32>>();
+        for player in died {
+            self.telemetry.push(TelemetryEvent::Death { player });
+        }
+
+        result
+    }
+
+    fn update_player(player: &mut Player, inputs: &mut AllInputManagement, ctx: &StepContext, enemies: &mut EnemyManager, camera: &mut Camera, is_finished: bool, sounds: &mut Vec<String>, dt: f64) {
+        if player.controller.state != EntityState::Dead
+        {
+            if inputs.input_new_frame != 0 {
+                process_input(player, ctx.p1_data, inputs, enemies);
+            }
+
+            apply_input_state(player, inputs, ctx.p1_data, enemies);
+        }
+
+        inputs.update_inputs_reset_timer();
+        inputs.update_input_buffer_reset_time();
+
+        player.character_width = match player.collision_manager.colliders
+            .iter()
+            .filter(|&c| c.collider_type == ColliderType::Pushbox)
+            .last()
+        {
+            Some(point) => point.aabb.half_extents().x as f64,
+            None => { player.character_width },
+        };
+
+        let mut player_update_events = player.events.on_update.clone();
+        for event in player_update_events.iter_mut() {
+            event.0(player, enemies, -1, &mut event.1, dt);
+        }
+        player.events.on_update = player_update_events;
+
+        player.animator.update();
+        player.state_update(ctx.p1_sprite_data);
+        let character_width = player.character_width as i32;
+        player.update(
+            camera,
+            dt,
+            is_finished,
+            character_width,
+            sounds
+        );
+        player.state_update(ctx.p1_sprite_data);
+    }
+
+    //gives out the score, loot and on kill events of enemies that died this frame
+    fn resolve_kills(&mut self, ctx: &mut StepContext) {
+        for i in 0..self.enemies.reward_components.len() {
+            let is_dead = match &self.enemies.health_components[i] {
+                Some(hp) => hp.0 <= 0,
+                None => false,
+            };
+            if !is_dead {
+                continue;
+            }
+
+            let (loot_table, drop_chance, score) = match self.enemies.reward_components[i].as_mut() {
+                Some(reward) if !reward.claimed => {
+                    reward.claimed = true;
+                    (reward.loot_table.clone(), reward.drop_chance, reward.score)
+                },
+                _ => continue,
+            };
+            self.score += score;
+            self.stats.enemies_killed += 1;
+            let reward = self.enemies.reward_components[i].as_ref().unwrap();
+            self.telemetry.push(TelemetryEvent::Kill { player: reward.last_hit_by, enemy: reward.archetype.clone() });
+
+            //kills count for everyone in co-op
+            for player in Game::collect_players_mut(&mut self.player, &mut self.player2) {
+                let mut kill_events = player.events.on_kill.clone();
+                for event in kill_events.iter_mut() {
+                    (event.0)(player, &mut self.enemies, i as i32, &mut event.1);
+                }
+                player.events.on_kill = kill_events;
+            }
+
+            for challenge in ctx.challenges.on_kill.iter_mut() {
+                (challenge.0)(&mut challenge.1);
+            }
+
+            let enemy_pos = match &self.enemies.positions_components[i] {
+                Some(pos) => pos.0,
+                None => continue,
+            };
+            if let Some(table) = loot_table.and_then(|table_name| ctx.loot_tables.get(&table_name)) {
+                let mut rng = stream_rng(ctx.seed, RngStream::Loot, &[ctx.curr_act as u64, ctx.curr_level as u64, self.current_frame as u64, i as u64]);
+                if !table.items.is_empty() && rng.gen::<f64>() < drop_chance {
+                    let item_id = get_random_item(table, &mut rng) as i32;
+                    self.items_on_ground.push(ItemGround{ position: enemy_pos, item: (*ctx.items.get(&item_id).unwrap()).clone() });
+                }
+            }
+        }
+    }
+
+    fn pickup_position(player: &Player) -> Vector2<f64> {
+        if !player.controller.is_airborne {
+            player.position
+        } else {
+            Vector2::new(player.position.x, player.controller.ground_height as f64)
+        }
+    }
+
+    fn enemy_hps(&self) -> Vec<Option<i32>> {
+        self.enemies.health_components.iter()
+            .map(|health| {health.as_ref().map(|health| {health.0.max(0)})})
+            .collect()
+    }
+
+    fn pickup_items(&mut self, ctx: &mut StepContext, picked_items: &mut Vec<Item>) {
+        let player_positions = self.players().iter().map(|player| {Game::pickup_position(player)}).collect::<Vec<Vector2<f64>>>();
+        
+        let items_spawned = self.items_on_ground.clone();
+        for item_ground in items_spawned.iter() {
+            let picked_by = player_positions.iter().position(|player_position| {(player_position - item_ground.position).magnitude() <= ITEM_PICKUP_DISTANCE});
+            if let Some(picked_by) = picked_by {
+                let (owner, other) = if picked_by == 0 {
+                    (&mut self.player, self.player2.as_mut())
+                } else {
+                    (self.player2.as_mut().unwrap(), Some(&mut self.player))
+                };
+                give_item(ctx.coop_loot, owner, other, &item_ground.item, ctx.effects);
+                self.telemetry.push(TelemetryEvent::ItemPickup { player: owner.id, item_id: item_ground.item.id });
+                
+                if let Some(chance_mod) = &item_ground.item.chance_mod {
+                    (chance_mod.modifier)(chance_mod.item_ids.clone(), chance_mod.chance_mod, &owner.character, ctx.loot_tables);
+                } else {
+                    for (_key, val) in ctx.loot_tables.iter_mut() {
+                        val.items.retain(|x| x.item_id as i32 != item_ground.item.id);
+                        val.acc = val.items.iter().map(|i|{i.rarity}).sum();
+                    }
+                }
+                picked_items.push(item_ground.item.clone());
+            }
+        }
+
+        self.items_on_ground.retain(|item_ground| {
+            return player_positions.iter().all(|player_position| {(player_position - item_ground.position).magnitude() >= ITEM_PICKUP_DISTANCE});
+        });
+    }
+
+    //returns true once the player walks out of the right side of a finished level
+    fn keep_player_in_camera(player: &mut Player, camera_rect: Rect, is_finished: bool) -> bool {
+        if player.controller.state == EntityState::Dead {
+            return false;
+        }
+
+        if (player.position.x  as i32 - player.character_width as i32) < camera_rect.x() {
+            player.position.x = (camera_rect.x() + player.character_width as i32) as f64;
+        }
+
+        if (player.position.x as i32 + player.character_width as i32) > (camera_rect.x() + camera_rect.width() as i32) {
+            if !is_finished {
+                player.position.x = (camera_rect.x() + camera_rect.width() as i32 - player.character_width as i32) as f64;
+            } else if (player.position.x as i32 - (player.character_width as f32 * 1.5) as i32 ) > (camera_rect.x() + camera_rect.width() as i32) {
+                return true;
+            }
+        }
+        false
     }
 
     pub fn max_level_width(&self) -> i32 {
@@ -70,7 +494,7 @@ impl Game {
         n_enemy_spawners + n_enemies_alive == 0
     }
 
-    pub fn check_level_tags_and_apply(&mut self, game_state_data: &mut GameStateData) {
+    pub fn check_level_tags_and_apply(&mut self, ctx: &mut StepContext) {
         for (level_index, level) in self.levels.iter_mut().enumerate() {
             if !(self.camera.rect.x > level.start_x + (level.level_map.width * level.level_map.tile_width) as i32 || self.camera.rect.x + (self.camera.rect.width() as i32) < level.start_x) {
                 for tag in level.level_map.object_groups[0].objects.iter_mut() {
@@ -81,15 +505,28 @@ impl Game {
                             let tag_pos = Vector2::new(tag.x as f64 + level.start_x as f64, ((level.level_map.height * level.level_map.tile_height) as f32 - tag.y) as f64);
 
                             if tag.name == "enemy".to_string() {
-                                self.enemies.add_enemy(tag_pos, Rc::clone(game_state_data.enemy_animations.get("ryu").unwrap()));
+                                let mut spawn_rng = stream_rng(ctx.seed, RngStream::LevelLayout, &[ctx.curr_act as u64, ctx.curr_level as u64, level_index as u64, tag.id as u64]);
+                                let n_enemies = ctx.difficulty.enemy_count(&mut spawn_rng);
+                                for n in 0..n_enemies {
+                                    //tags without a type get an archetype from the difficulty mix
+                                    let archetype = match ctx.enemy_archetypes.get(&tag.obj_type) {
+                                        Some(archetype) => archetype,
+                                        None => ctx.difficulty.pick_archetype(&mut spawn_rng)
+                                            .and_then(|name| {ctx.enemy_archetypes.get(name)})
+                                            .unwrap_or(ctx.enemy_archetypes.get(DEFAULT_ENEMY_ARCHETYPE).unwrap()),
+                                    };
+                                    let spawn_pos = tag_pos + Vector2::new(n as f64 * ENEMY_SPAWN_SPACING, 0f64);
+                                    Game::spawn_enemy(&mut self.enemies, spawn_pos, archetype, ctx);
+                                }
                                 tag.visible = false;
                             
                             } else if tag.name == "item".to_string() {
-                                let table = game_state_data.general_assets.loot_tables.get(&tag.obj_type).unwrap();
+                                let mut item_rng = stream_rng(ctx.seed, RngStream::Loot, &[ctx.curr_act as u64, ctx.curr_level as u64, level_index as u64, tag.id as u64]);
+                                let table_name = if item_rng.gen::<f64>() < ctx.difficulty.rare_item_chance { BOSS_TABLE } else { &tag.obj_type };
+                                let table = ctx.loot_tables.get(table_name).unwrap();
 
-                                let item_room_seed = game_state_data.seed.unwrap() * (game_state_data.curr_level as u64 + level_index as u64); //+ some id of overworld map level picked + picked tileset level 
-                                let item_id = get_random_item(table, &mut SmallRng::seed_from_u64(item_room_seed)) as i32;
-                                self.items_on_ground.push(ItemGround{ position: tag_pos, item: (*game_state_data.items.get(&item_id).unwrap()).clone() });
+                                let item_id = get_random_item(table, &mut item_rng) as i32;
+                                self.items_on_ground.push(ItemGround{ position: tag_pos, item: (*ctx.items.get(&item_id).unwrap()).clone() });
                                 tag.visible = false;
                             }
                         }
@@ -99,6 +536,19 @@ impl Game {
         }
     }
 
+    //spawns the archetype with the stats of the current difficulty
+    pub fn spawn_enemy(enemies: &mut EnemyManager, pos: Vector2<f64>, archetype: &EnemyArchetype, ctx: &StepContext) {
+        let mut scaled_archetype = archetype.clone();
+        scaled_archetype.stats.hp = (archetype.stats.hp as f64 * ctx.difficulty.hp_mod).round() as i32;
+        scaled_archetype.stats.speed *= ctx.modifiers.enemy_speed_mod;
+        scaled_archetype.stats.dash_speed *= ctx.modifiers.enemy_speed_mod;
+
+        let enemy_id = enemies.add_enemy(pos, &scaled_archetype, Rc::clone(ctx.enemy_animations.get(&archetype.sprite_set).unwrap()));
+        if let Some(enemy_id) = enemy_id {
+            enemies.damage_modifier_components[enemy_id] = Some(DamageModifier(ctx.difficulty.damage_mod * ctx.modifiers.enemy_damage_mod));
+        }
+    }
+
     pub fn spawn_vfx(hit_vfx: &mut Vec<Particle>, rect: Rect, flipped: bool, type_of_animation: String, tint: Option<Color>) {
         if hit_vfx.len() < LIMIT_NUMBER_OF_VFX {
             //push with bool as true
@@ -131,15 +581,15 @@ impl Game {
         }
     }
 
-    pub fn update_vfx(&mut self, assets: &LevelAssets) {
+    pub fn update_vfx(&mut self, vfx_data: &VfxData) {
         for i in 0..self.hit_vfx.len() {
             let vfx = &mut self.hit_vfx[i];
 
             if vfx.active {
                 vfx.animation_index += 1;
                 
-                let curr_animation = assets
-                .hit_effect_animations
+                let curr_animation = vfx_data
+                .animations
                 .get(&vfx.name)
                 .unwrap();
 
@@ -202,7 +652,7 @@ impl Game {
         }
     }
 
-    pub fn fx(&mut self, assets: &LevelAssets) {
+    pub fn fx(&mut self, vfx_data: &VfxData) {
 
         let process_point_offset = |player: &Player, point: &CastPoint| -> Vector2<f64> {
             let mut final_pos = player.position;
@@ -216,28 +666,26 @@ impl Game {
             }
             final_pos
         };
-        let player_dir = self.player.controller.facing_dir;
         let mut points = Vec::new();
-        let hash_points = &self.player.animator.current_animation.as_ref().unwrap().cast_point;
-
-        if hash_points.keys().len() > 0 {
-            match hash_points.get(&(self.player.animator.animation_index as i64 -1)) {
-                Some(point) => {
-                    let mut point_position_fixed = point.clone();
-                    point_position_fixed.point = process_point_offset(&self.player, &point_position_fixed);
-                    points.push((point_position_fixed, player_dir));
+        for player in self.players() {
+            let player_dir = player.controller.facing_dir;
+            let hash_points = &player.animator.current_animation.as_ref().unwrap().cast_point;
+
+            if hash_points.keys().len() > 0 {
+                match hash_points.get(&(player.animator.animation_index as i64 -1)) {
+                    Some(point) => {
+                        let mut point_position_fixed = point.clone();
+                        point_position_fixed.point = process_point_offset(player, &point_position_fixed);
+                        points.push((point_position_fixed, player_dir));
+                    }
+                    None => {}
                 }
-                None => {}
             }
         }
 
         for point in &mut points {
-            let texture_id = &assets.hit_effect_animations.get(&point.0.name.replace("?", "")).unwrap().sprites[0].1;
-            let TextureQuery { width, height, .. } = assets
-                                    .hit_effect_textures
-                                    .get(texture_id)
-                                    .unwrap()
-                                    .query();
+            let texture_id = &vfx_data.animations.get(&point.0.name.replace("?", "")).unwrap().sprites[0].1;
+            let (width, height) = vfx_data.texture_size(texture_id);
         
             let texture_width = width * 2;
             let texture_height = height * 2;
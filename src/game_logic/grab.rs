@@ -0,0 +1,319 @@
+use parry2d::{bounding_volume::AABB, math::Point, na::Vector2};
+use rand::Rng;
+
+use crate::{collision::collision_attack_resolution::{detect_body_hit, detect_grab}, ecs_system::{enemy_components::AIType, enemy_manager::EnemyManager, enemy_systems::take_damage}, engine_types::collider::{Collider, ColliderType}, telemetry::TelemetryEvent, utils::math_sign::Sign};
+
+use super::characters::player::{EntityState, Player};
+
+//without a grab animation the attempt reaches this far past the pushbox
+const GRAB_REACH: f32 = 40.0;
+const GRAB_HOLD_DISTANCE: f64 = 90.0;
+//seconds until whoever is held breaks free, every pummel brings it closer
+const GRAB_ESCAPE_TIME: f64 = 1.5;
+const PUMMEL_ESCAPE_COST: f64 = 0.3;
+//a tech breaks the grab this long after it started
+const GRAB_TECH_WINDOW: f64 = 0.2;
+const GRAB_BREAK_PUSH: f64 = 600.0;
+
+const PUMMEL_DAMAGE: i32 = 4;
+const THROW_DAMAGE: i32 = 12;
+const THROW_PUSH: f64 = 900.0;
+//damage dealt to everyone the thrown body crashes into
+const THROWN_BODY_DAMAGE: i32 = 10;
+const THROWN_BODY_PUSH: f64 = 500.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GrabCommand {
+    Attempt,
+    Pummel,
+    //direction of the throw, back throws go against the facing direction
+    Throw(i8),
+}
+
+pub struct Grab {
+    pub player: usize,
+    pub enemy: usize,
+    pub escape_timer: f64,
+    pub teched: bool,
+}
+
+//an enemy thrown as a projectile, until it lands
+pub struct Thrown {
+    pub enemy: usize,
+    pub direction: i8,
+    pub hit: Vec<usize>,
+}
+
+//a grabbox in front of the pushbox, for characters without a grab animation
+fn reach_collider(player: &Player) -> Option<Collider> {
+    let pushbox = player.collision_manager.colliders.iter()
+        .find(|c| c.collider_type == ColliderType::Pushbox)?;
+    let (mins, maxs) = (pushbox.aabb.mins, pushbox.aabb.maxs);
+    let aabb = if player.controller.facing_dir > 0 {
+        AABB::new(Point::new(maxs.x, mins.y), Point::new(maxs.x + GRAB_REACH, maxs.y))
+    } else {
+        AABB::new(Point::new(mins.x - GRAB_REACH, mins.y), Point::new(mins.x, maxs.y))
+    };
+    Some(Collider {
+        collider_type: ColliderType::Grabbox,
+        name: "grab".to_string(),
+        aabb,
+        enabled: true,
+    })
+}
+
+fn can_be_grabbed(enemies: &EnemyManager, enemy: usize) -> bool {
+    let alive = enemies.health_components[enemy].as_ref().map_or(false, |hp| hp.0 > 0);
+    let is_enemy = enemies.ai_type_components[enemy] == Some(AIType::Enemy);
+    let grounded = enemies.movement_controller_components[enemy].as_ref().map_or(false, |mov| {
+        !mov.is_airborne && mov.state != EntityState::Knocked && mov.state != EntityState::Dropped && mov.state != EntityState::Grabbed
+    });
+    alive && is_enemy && grounded
+}
+
+fn release(player: &mut Player, enemies: &mut EnemyManager, enemy: usize) {
+    if player.controller.state == EntityState::Grabbing {
+        player.controller.set_entity_state(EntityState::Idle, &mut player.animator);
+    }
+    if let (Some(mov), Some(animator)) = (enemies.movement_controller_components[enemy].as_mut(), enemies.animator_components[enemy].as_mut()) {
+        if mov.state == EntityState::Grabbed {
+            mov.set_entity_state(EntityState::Idle, animator);
+        }
+    }
+}
+
+//both sides get pushed apart, used for techs and escapes
+fn break_free(player: &mut Player, enemies: &mut EnemyManager, enemy: usize, sounds: &mut Vec<String>, dt: f64) {
+    release(player, enemies, enemy);
+    let facing = player.controller.facing_dir.sign() as f64;
+    player.controller.knock_back(&mut player.position, -GRAB_BREAK_PUSH * facing, dt);
+    if let (Some(mov), Some(pos)) = (enemies.movement_controller_components[enemy].as_mut(), enemies.positions_components[enemy].as_mut()) {
+        mov.knock_back(&mut pos.0, GRAB_BREAK_PUSH * facing, dt);
+    }
+    sounds.push("block".to_string());
+}
+
+fn start_grab(player: &mut Player, enemies: &mut EnemyManager, enemy: usize) {
+    player.controller.is_attacking = false;
+    player.controller.is_blocking = false;
+    player.controller.walking_dir.x = 0;
+    player.controller.walking_dir.y = 0;
+    player.controller.set_entity_state(EntityState::Grabbing, &mut player.animator);
+
+    if let (Some(mov), Some(animator)) = (enemies.movement_controller_components[enemy].as_mut(), enemies.animator_components[enemy].as_mut()) {
+        mov.is_attacking = false;
+        mov.is_blocking = false;
+        mov.knock_back_distance = 0.0;
+        mov.set_velocity(Vector2::new(0, 0), animator);
+        mov.set_entity_state(EntityState::Grabbed, animator);
+        mov.facing_dir = -player.controller.facing_dir;
+    }
+}
+
+fn credit_hit(enemies: &mut EnemyManager, enemy: usize, player: Option<i32>) {
+    if let Some(reward) = enemies.reward_components[enemy].as_mut() {
+        reward.last_hit_by = player;
+    }
+}
+
+fn enemy_name(enemies: &EnemyManager, enemy: usize) -> String {
+    match &enemies.reward_components[enemy] {
+        Some(reward) => reward.archetype.clone(),
+        None => "unknown".to_string(),
+    }
+}
+
+//players grab enemies when a grabbox overlaps their grabbable box, then pummel, throw or lose them
+pub fn update_grabs(players: &mut [&mut Player],
+    enemies: &mut EnemyManager,
+    grabs: &mut Vec<Grab>,
+    thrown: &mut Vec<Thrown>,
+    boss: Option<usize>,
+    throw_tech_chance: f64,
+    hit_stop: &mut i32,
+    sounds: &mut Vec<String>,
+    telemetry: &mut Vec<TelemetryEvent>,
+    dt: f64) {
+
+    //getting hit on either side ends the grab
+    grabs.retain(|grab| {
+        let enemy_held = enemies.movement_controller_components[grab.enemy].as_ref().map_or(false, |mov| mov.state == EntityState::Grabbed);
+        let player_holding = players[grab.player].controller.state == EntityState::Grabbing;
+        if !(enemy_held && player_holding) {
+            release(players[grab.player], enemies, grab.enemy);
+        }
+        enemy_held && player_holding
+    });
+
+    for p in 0..players.len() {
+        let holding = grabs.iter().any(|grab| grab.player == p);
+        let attempt = players[p].grab_command == Some(GrabCommand::Attempt);
+        if holding || players[p].controller.state == EntityState::Dead {
+            continue;
+        }
+
+        let mut grab_colliders = players[p].collision_manager.colliders.iter()
+            .filter(|c| c.collider_type == ColliderType::Grabbox && c.enabled)
+            .cloned()
+            .collect::<Vec<Collider>>();
+        if grab_colliders.is_empty() && attempt {
+            grab_colliders.extend(reach_collider(players[p]));
+        }
+        if grab_colliders.is_empty() {
+            continue;
+        }
+
+        let target = (0..enemies.collider_components.len()).find(|&enemy| {
+            Some(enemy) != boss && can_be_grabbed(enemies, enemy) &&
+            enemies.collider_components[enemy].as_ref().map_or(false, |colliders| detect_grab(&grab_colliders, &colliders.colliders))
+        });
+
+        if let Some(enemy) = target {
+            start_grab(players[p], enemies, enemy);
+            grabs.push(Grab {
+                player: p,
+                enemy,
+                escape_timer: 0.0,
+                //enemies have no throw tech input, the tech is a random roll on the player's combat rng when the grab starts
+                teched: players[p].combat_rng.gen::<f64>() < throw_tech_chance,
+            });
+            sounds.push("grab".to_string());
+        }
+    }
+
+    let mut ended = Vec::new();
+    for (g, grab) in grabs.iter_mut().enumerate() {
+        let player = &mut *players[grab.player];
+        grab.escape_timer += dt;
+
+        let facing = player.controller.facing_dir.sign() as f64;
+        if let (Some(pos), Some(mov)) = (enemies.positions_components[grab.enemy].as_mut(), enemies.movement_controller_components[grab.enemy].as_mut()) {
+            pos.0.x = player.position.x + facing * GRAB_HOLD_DISTANCE;
+            pos.0.y = player.position.y;
+            mov.ground_height = player.position.y as i32;
+        }
+
+        if grab.teched && grab.escape_timer >= GRAB_TECH_WINDOW {
+            break_free(player, enemies, grab.enemy, sounds, dt);
+            ended.push(g);
+            continue;
+        }
+
+        match player.grab_command {
+            Some(GrabCommand::Pummel) => {
+                let name = enemy_name(enemies, grab.enemy);
+                credit_hit(enemies, grab.enemy, Some(player.id));
+                if let Some(hp) = enemies.health_components[grab.enemy].as_mut() {
+                    let hp_before = hp.0;
+                    hp.0 -= PUMMEL_DAMAGE;
+                    telemetry.push(TelemetryEvent::Hit {
+                        player: player.id,
+                        attack: "pummel".to_string(),
+                        damage: hp_before - hp.0,
+                        enemy: name,
+                    });
+                }
+                grab.escape_timer += PUMMEL_ESCAPE_COST;
+                *hit_stop = 4;
+                sounds.push("hit".to_string());
+
+                let dead = enemies.health_components[grab.enemy].as_ref().map_or(true, |hp| hp.0 <= 0);
+                if dead {
+                    if let (Some(mov), Some(animator)) = (enemies.movement_controller_components[grab.enemy].as_mut(), enemies.animator_components[grab.enemy].as_mut()) {
+                        mov.set_entity_state(EntityState::Dead, animator);
+                    }
+                    release(player, enemies, grab.enemy);
+                    ended.push(g);
+                    continue;
+                }
+            },
+            Some(GrabCommand::Throw(direction)) => {
+                release(player, enemies, grab.enemy);
+                let direction = if direction != 0 { direction } else { player.controller.facing_dir };
+                let name = enemy_name(enemies, grab.enemy);
+                credit_hit(enemies, grab.enemy, Some(player.id));
+                if let (Some(hp), Some(pos), Some(mov), Some(animator)) = (
+                    enemies.health_components[grab.enemy].as_mut(),
+                    enemies.positions_components[grab.enemy].as_mut(),
+                    enemies.movement_controller_components[grab.enemy].as_mut(),
+                    enemies.animator_components[grab.enemy].as_mut()) {
+                    let hp_before = hp.0;
+                    //back throws carry the enemy over to the other side first
+                    pos.0.x = player.position.x + direction.sign() as f64 * GRAB_HOLD_DISTANCE;
+                    take_damage(hp, THROW_DAMAGE, mov, animator);
+                    if hp.0 > 0 {
+                        mov.launch(animator);
+                        mov.direction_at_jump_time = direction;
+                        mov.knock_back(&mut pos.0, THROW_PUSH * direction.sign() as f64, dt);
+                        thrown.push(Thrown { enemy: grab.enemy, direction, hit: Vec::new() });
+                    }
+                    telemetry.push(TelemetryEvent::Hit {
+                        player: player.id,
+                        attack: "throw".to_string(),
+                        damage: hp_before - hp.0,
+                        enemy: name,
+                    });
+                }
+                player.controller.facing_dir = direction;
+                *hit_stop = 8;
+                sounds.push("throw".to_string());
+                ended.push(g);
+                continue;
+            },
+            _ => {},
+        }
+
+        if grab.escape_timer >= GRAB_ESCAPE_TIME {
+            break_free(player, enemies, grab.enemy, sounds, dt);
+            ended.push(g);
+        }
+    }
+    for g in ended.into_iter().rev() {
+        grabs.remove(g);
+    }
+
+    for player in players.iter_mut() {
+        player.grab_command = None;
+    }
+
+    update_thrown(enemies, thrown, sounds, dt);
+}
+
+//the thrown enemy is a projectile until it lands, everyone it crashes into is hit once
+fn update_thrown(enemies: &mut EnemyManager, thrown: &mut Vec<Thrown>, sounds: &mut Vec<String>, dt: f64) {
+    thrown.retain(|body| {
+        enemies.movement_controller_components[body.enemy].as_ref().map_or(false, |mov| mov.state == EntityState::Knocked)
+    });
+
+    for body in thrown.iter_mut() {
+        let body_colliders = match enemies.collider_components[body.enemy].as_ref() {
+            Some(colliders) => colliders.colliders.clone(),
+            None => continue,
+        };
+
+        for other in 0..enemies.collider_components.len() {
+            if other == body.enemy || body.hit.contains(&other) {
+                continue;
+            }
+            let alive = enemies.health_components[other].as_ref().map_or(false, |hp| hp.0 > 0);
+            let crashed = alive && enemies.collider_components[other].as_ref().map_or(false, |colliders| detect_body_hit(&body_colliders, &colliders.colliders));
+            if !crashed {
+                continue;
+            }
+
+            body.hit.push(other);
+            //the thrower gets the kill
+            let thrower = enemies.reward_components[body.enemy].as_ref().and_then(|reward| reward.last_hit_by);
+            credit_hit(enemies, other, thrower);
+            if let (Some(hp), Some(pos), Some(mov), Some(animator)) = (
+                enemies.health_components[other].as_mut(),
+                enemies.positions_components[other].as_mut(),
+                enemies.movement_controller_components[other].as_mut(),
+                enemies.animator_components[other].as_mut()) {
+                take_damage(hp, THROWN_BODY_DAMAGE, mov, animator);
+                mov.knock_back(&mut pos.0, THROWN_BODY_PUSH * body.direction.sign() as f64, dt);
+            }
+            sounds.push("hit".to_string());
+        }
+    }
+}
@@ -88,6 +88,22 @@ impl GameAction {
         curr_state & GameAction::Down as i32 > 0
     }
 
+    pub fn from_name(name: &str) -> Option<GameAction> {
+        match name {
+            "Right" => Some(GameAction::Right),
+            "Left" => Some(GameAction::Left),
+            "Up" => Some(GameAction::Up),
+            "Down" => Some(GameAction::Down),
+            "Punch" => Some(GameAction::Punch),
+            "Kick" => Some(GameAction::Kick),
+            "Jump" => Some(GameAction::Jump),
+            "Block" => Some(GameAction::Block),
+            "Dash" => Some(GameAction::Dash),
+            "Slide" => Some(GameAction::Slide),
+            _ => None,
+        }
+    }
+
     pub fn from_translated_input(
         original_input: TranslatedInput,
         curr_state: i32,
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use super::motion_inputs::MotionHistory;
+
 const FRAME_WINDOW_BETWEEN_INPUTS: i32 = 10;
 const FRAME_WINDOW_BUFFER: i32 = 10;
 
@@ -11,6 +13,9 @@ pub struct AllInputManagement {
 
     pub input_buffer: VecDeque<i32>,
     pub input_buffer_reset_time: Vec<i32>,
+
+    //longer lived than action_history, for quarter circles and charges
+    pub motion_history: MotionHistory,
 }
 
 impl AllInputManagement {
@@ -22,10 +27,13 @@ impl AllInputManagement {
 
             input_buffer: VecDeque::new(),
             input_buffer_reset_time: Vec::new(),
+
+            motion_history: MotionHistory::new(),
         }
     }
 
     pub fn update_inputs_reset_timer(&mut self) {
+        self.motion_history.tick();
         for i in 0..self.input_reset_timer.len() {
             self.input_reset_timer[i] += 1;
             if self.input_reset_timer[i] > FRAME_WINDOW_BETWEEN_INPUTS {
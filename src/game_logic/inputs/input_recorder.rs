@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::{asset_management::{rng_tables::LootTable, run_save::PlayerSave}, debug_console::console::DebugAction};
+
+//everything needed to rebuild a match deterministically, the inputs are stored per logic frame
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct InputRecording {
+    pub seed: u64,
+    pub level_seed: u64,
+    #[serde(default)]
+    pub curr_act: u32,
+    pub curr_level: i32,
+    pub difficulty: i32,
+    #[serde(default)]
+    pub ascension: u32,
+    pub player: PlayerSave,
+    pub loot_tables: HashMap<String, LootTable>,
+    pub boss: Option<String>,
+
+    pub last_frame: i32,
+    //(frame, input_new_frame) only for frames where something was pressed or released
+    pub frames: Vec<(i32, i32)>,
+    //(frame, action) for debug keys and console commands, applied before that frame is stepped
+    pub debug_actions: Vec<(i32, DebugAction)>,
+    //frames where the held inputs were dropped, after leaving the pause menu
+    #[serde(default)]
+    pub input_resets: Vec<i32>,
+}
+
+impl InputRecording {
+    pub fn new(seed: u64, level_seed: u64, curr_act: u32, curr_level: i32, difficulty: i32, ascension: u32, player: PlayerSave, loot_tables: HashMap<String, LootTable>, boss: Option<String>) -> Self {
+        Self {
+            seed,
+            level_seed,
+            curr_act,
+            curr_level,
+            difficulty,
+            ascension,
+            player,
+            loot_tables,
+            boss,
+
+            last_frame: 0,
+            frames: Vec::new(),
+            debug_actions: Vec::new(),
+            input_resets: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, frame: i32, input_new_frame: i32) {
+        self.last_frame = frame;
+        if input_new_frame != 0 {
+            self.frames.push((frame, input_new_frame));
+        }
+    }
+
+    pub fn record_debug_action(&mut self, frame: i32, action: DebugAction) {
+        self.debug_actions.push((frame, action));
+    }
+
+    pub fn debug_actions_at(&self, frame: i32) -> Vec<&DebugAction> {
+        self.debug_actions.iter()
+            .filter(|(f, _)| {*f == frame})
+            .map(|(_, action)| {action})
+            .collect()
+    }
+
+    pub fn record_input_reset(&mut self, frame: i32) {
+        self.input_resets.push(frame);
+    }
+
+    pub fn resets_inputs_at(&self, frame: i32) -> bool {
+        self.input_resets.contains(&frame)
+    }
+
+    pub fn input_at(&self, frame: i32) -> i32 {
+        match self.frames.binary_search_by_key(&frame, |&(f, _)| f) {
+            Ok(index) => self.frames[index].1,
+            Err(_) => 0,
+        }
+    }
+
+    pub fn is_finished(&self, frame: i32) -> bool {
+        frame > self.last_frame
+    }
+
+    pub fn save(&self, path: &Path) {
+        let encoded = bincode::serialize(self).unwrap();
+        if let Err(e) = fs::write(path, encoded) {
+            println!("failed to save input recording {:?}", e);
+        }
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        match bincode::deserialize::<InputRecording>(&bytes) {
+            Ok(recording) => Some(recording),
+            Err(e) => {
+                println!("ignoring invalid input recording {:?}", e);
+                None
+            }
+        }
+    }
+}
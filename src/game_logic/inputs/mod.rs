@@ -1,2 +1,4 @@
 pub mod game_inputs;
 pub mod input_cycle;
+pub mod input_recorder;
+pub mod motion_inputs;
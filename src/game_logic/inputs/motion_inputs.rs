@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+
+use super::game_inputs::GameAction;
+
+//how long directions are remembered, longer than any motion window or charge
+const MOTION_HISTORY_FRAMES: u32 = 90;
+const DIRECTIONS: i32 = GameAction::Right as i32 | GameAction::Left as i32 | GameAction::Up as i32 | GameAction::Down as i32;
+
+//directions use numpad notation relative to the facing when the button is pressed, 6 is forward, 4 is back, 2 is down and 5 is neutral
+//walking turns characters around, so motions should end going forward or straight down
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum Motion {
+    //each direction in order, anything else pressed in between is ignored
+    Sequence(Vec<u8>),
+    //hold a direction for some frames, then press the release direction
+    Charge { hold: u8, frames: u32, release: u8 },
+}
+
+pub struct MotionInput {
+    pub motion: Motion,
+    pub button: GameAction,
+    //frames the whole motion has to fit in
+    pub window: u32,
+    //frames allowed between the last direction and the button
+    pub button_window: u32,
+    pub is_airborne: bool,
+    pub attack: String,
+}
+
+//every change of the held directions with the frame it happened on
+#[derive(Clone, PartialEq, Debug)]
+pub struct MotionHistory {
+    pub frame: u32,
+    pub directions: VecDeque<(u32, i32)>,
+}
+
+impl MotionHistory {
+    pub fn new() -> Self {
+        Self {
+            frame: 0,
+            directions: VecDeque::new(),
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.frame += 1;
+        //the newest entry stays, it is still being held
+        while self.directions.len() > 1 && self.frame - self.directions[1].0 > MOTION_HISTORY_FRAMES {
+            self.directions.pop_front();
+        }
+    }
+
+    pub fn record(&mut self, actions: i32) {
+        let directions = actions & DIRECTIONS;
+        if self.directions.back().map_or(true, |&(_, last)| {last != directions}) {
+            self.directions.push_back((self.frame, directions));
+        }
+    }
+
+    //the motion has to end in the direction held right now, or just before the button
+    pub fn matches(&self, motion_input: &MotionInput, facing_dir: i8) -> bool {
+        let history = self.directions.iter()
+            .map(|&(frame, directions)| {(frame, numpad(directions, facing_dir))})
+            .collect::<Vec<(u32, u8)>>();
+        if history.is_empty() {
+            return false;
+        }
+
+        match &motion_input.motion {
+            Motion::Sequence(sequence) => self.matches_sequence(&history, sequence, motion_input),
+            Motion::Charge { hold, frames, release } => self.matches_charge(&history, *hold, *frames, *release, motion_input),
+        }
+    }
+
+    fn matches_sequence(&self, history: &[(u32, u8)], sequence: &[u8], motion_input: &MotionInput) -> bool {
+        if sequence.is_empty() {
+            return false;
+        }
+
+        //walk back from the newest direction, matching the sequence from its end
+        let mut remaining = sequence.len();
+        let mut end_frame = None;
+        for i in (0..history.len()).rev() {
+            let (frame, direction) = history[i];
+            //entries stay held until the next one replaced them
+            let released_at = if i + 1 < history.len() { history[i + 1].0 } else { self.frame };
+
+            match end_frame {
+                None if self.frame - released_at > motion_input.button_window => return false,
+                Some(end_frame) if end_frame - released_at > motion_input.window => return false,
+                _ => {},
+            }
+
+            if direction == sequence[remaining - 1] {
+                end_frame = end_frame.or(Some(frame));
+                remaining -= 1;
+                if remaining == 0 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn matches_charge(&self, history: &[(u32, u8)], hold: u8, frames: u32, release: u8, motion_input: &MotionInput) -> bool {
+        //the release has to be recent
+        let release_index = match (0..history.len()).rev().find(|&i| {
+            let released_at = if i + 1 < history.len() { history[i + 1].0 } else { self.frame };
+            history[i].1 == release && self.frame - released_at <= motion_input.button_window
+        }) {
+            Some(i) => i,
+            None => return false,
+        };
+
+        //everything held right before it that counts as the charge direction, down-back charges back too
+        let charge_end = history[release_index].0;
+        let mut charge_start = None;
+        for i in (0..release_index).rev() {
+            if !holds(history[i].1, hold) {
+                break;
+            }
+            charge_start = Some(history[i].0);
+        }
+
+        match charge_start {
+            Some(start) => charge_end - start >= frames,
+            None => false,
+        }
+    }
+}
+
+fn numpad(directions: i32, facing_dir: i8) -> u8 {
+    let forward = if facing_dir >= 0 { GameAction::Right } else { GameAction::Left };
+    let back = if facing_dir >= 0 { GameAction::Left } else { GameAction::Right };
+    let x = if GameAction::is_pressed(directions, forward) { 1 } else if GameAction::is_pressed(directions, back) { -1 } else { 0 };
+    let y = if GameAction::is_pressed(directions, GameAction::Up) { 1 } else if GameAction::is_pressed(directions, GameAction::Down) { -1 } else { 0 };
+    (5 + x + 3 * y) as u8
+}
+
+//charging back also counts down-back and up-back, same for down
+fn holds(direction: u8, hold: u8) -> bool {
+    let (x, y) = ((direction as i32 - 1) % 3 - 1, (direction as i32 - 1) / 3 - 1);
+    match hold {
+        4 => x == -1,
+        6 => x == 1,
+        2 => y == -1,
+        8 => y == 1,
+        _ => direction == hold,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_logic::inputs::game_inputs::GameAction;
+
+    use super::{Motion, MotionHistory, MotionInput};
+
+    const DOWN: i32 = GameAction::Down as i32;
+    const RIGHT: i32 = GameAction::Right as i32;
+    const LEFT: i32 = GameAction::Left as i32;
+
+    fn motion_input(motion: Motion) -> MotionInput {
+        MotionInput {
+            motion,
+            button: GameAction::Punch,
+            window: 15,
+            button_window: 8,
+            is_airborne: false,
+            attack: "special".to_string(),
+        }
+    }
+
+    //holds each set of actions for some frames, the same way the input cycle ticks and records
+    fn history(inputs: &[(i32, u32)]) -> MotionHistory {
+        let mut history = MotionHistory::new();
+        for &(actions, frames) in inputs.iter() {
+            for _ in 0..frames {
+                history.tick();
+                history.record(actions);
+            }
+        }
+        history
+    }
+
+    #[test]
+    fn quarter_circle_forward() {
+        let qcf = motion_input(Motion::Sequence(vec![2, 3, 6]));
+        let history = history(&[(0, 1), (DOWN, 2), (DOWN | RIGHT, 2), (RIGHT, 1)]);
+        assert!(history.matches(&qcf, 1));
+        assert!(!history.matches(&motion_input(Motion::Sequence(vec![6, 2, 3])), 1));
+    }
+
+    #[test]
+    fn dragon_punch() {
+        let dp = motion_input(Motion::Sequence(vec![6, 2, 3]));
+        let history = history(&[(0, 1), (RIGHT, 2), (DOWN, 2), (DOWN | RIGHT, 1)]);
+        assert!(history.matches(&dp, 1));
+        assert!(!history.matches(&motion_input(Motion::Sequence(vec![2, 3, 6])), 1));
+    }
+
+    #[test]
+    fn motions_flip_with_facing() {
+        let qcf = motion_input(Motion::Sequence(vec![2, 3, 6]));
+        let history = history(&[(0, 1), (DOWN, 2), (DOWN | LEFT, 2), (LEFT, 1)]);
+        assert!(history.matches(&qcf, -1));
+        assert!(!history.matches(&qcf, 1));
+    }
+
+    #[test]
+    fn motion_slower_than_the_window_is_rejected() {
+        let qcf = motion_input(Motion::Sequence(vec![2, 3, 6]));
+        let history = history(&[(0, 1), (DOWN, 1), (0, 20), (DOWN | RIGHT, 1), (RIGHT, 1)]);
+        assert!(!history.matches(&qcf, 1));
+    }
+
+    #[test]
+    fn button_late_after_the_motion_is_rejected() {
+        let qcf = motion_input(Motion::Sequence(vec![2, 3, 6]));
+        assert!(history(&[(0, 1), (DOWN, 2), (DOWN | RIGHT, 2), (RIGHT, 1), (0, 8)]).matches(&qcf, 1));
+        assert!(!history(&[(0, 1), (DOWN, 2), (DOWN | RIGHT, 2), (RIGHT, 1), (0, 10)]).matches(&qcf, 1));
+    }
+
+    #[test]
+    fn charge_needs_the_full_hold() {
+        let charge = motion_input(Motion::Charge { hold: 4, frames: 30, release: 6 });
+        assert!(history(&[(0, 1), (LEFT, 40), (RIGHT, 1)]).matches(&charge, 1));
+        assert!(!history(&[(0, 1), (LEFT, 20), (RIGHT, 1)]).matches(&charge, 1));
+        //down-back keeps the charge
+        assert!(history(&[(0, 1), (LEFT, 20), (DOWN | LEFT, 20), (RIGHT, 1)]).matches(&charge, 1));
+        //facing left the charge is held on the right
+        assert!(history(&[(0, 1), (RIGHT, 40), (LEFT, 1)]).matches(&charge, -1));
+    }
+}
@@ -1,6 +1,6 @@
 use std::cmp;
 
-use rand::{Rng, SeedableRng, prelude::SmallRng};
+use rand::{Rng, prelude::SmallRng};
 
 use crate::{ecs_system::{enemy_components::{AIType, Position}, enemy_manager::EnemyManager, enemy_systems::{heal, take_damage_light}}, engine_types::animator::Animator, game_logic::{characters::{Attack, AttackType, player::Player}, effects::{Effect, events_pub_sub::{CharacterEvent, CharacterEventAttack, CharacterEventMap, CharacterEventUpdate}}, movement_controller::MovementController}, scenes::overworld_scene::OverworldScene};
 
@@ -117,8 +117,7 @@ pub fn charm_enemies(player: &mut Player, enemies: &mut EnemyManager, effect: &m
     let n_enemies = actual_enemies.len();
 
     if n_enemies > 0 {
-        let mut rng = rand::thread_rng();
-        let enemy_to_charm = (rng.gen::<f64>() * n_enemies as f64) as usize;
+        let enemy_to_charm = (player.combat_rng.gen::<f64>() * n_enemies as f64) as usize;
     
         let actual_id = actual_enemies[enemy_to_charm].0;
         enemies.ai_type_components[actual_id] = Some(AIType::Allied);
@@ -197,9 +196,8 @@ pub fn once_in_awhile_forget_or_remenber_attacks(player: &mut Player, _: &mut En
     effect.time_elapsed += (dt * 1000f64) as i32;
     if let Some(time_threshold) = effect.apply_at_every {
         if effect.time_elapsed > time_threshold {
-            let mut rng = SmallRng::seed_from_u64(96873523456);
-            let random_n = rng.gen::<f64>();
-            let random_attack = rng.gen::<f64>() * 9f64;
+            let random_n = player.combat_rng.gen::<f64>();
+            let random_attack = player.combat_rng.gen::<f64>() * 9f64;
             let possible_attacks= ["punch", "kick", "airborne punch", "airborne kick", "launcher", "dropper", "dashing", "crash", "doublejump", "airdash"];
             
             let mut effect = Effect {
@@ -10,6 +10,7 @@ pub fn change_spawn_item(items: Vec<i32>, chance_mod: i32, character: &Character
                 item.rarity *= chance_mod as u64;
             }
         }
+        table.acc = table.items.iter().map(|i|{i.rarity}).sum();
     }
 }
 
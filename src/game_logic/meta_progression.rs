@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::asset_management::{profile::Profile, rng_tables::{Item as LootItem, LootTable}};
+
+use super::{characters::player::Player, effects::ItemEffects, items::Item, run_stats::{RunOutcome, RunStats}};
+
+pub const DEFAULT_LOADOUT: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum MilestoneCounter {
+    //counted from the stats of each match
+    Kills,
+    Hits,
+    HitsTaken,
+    //counted when a run ends
+    FloorsCleared,
+    RunsPlayed,
+    RunsWon,
+    BestCombo,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ItemUnlock {
+    pub item_id: i32,
+    pub table: String,
+    pub rarity: u64,
+    pub cost: u32,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Loadout {
+    pub id: String,
+    pub name: String,
+    pub cost: u32,
+    #[serde(default)]
+    pub item_ids: Vec<i32>,
+    #[serde(default)]
+    pub currency: u32,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Milestone {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub counter: MilestoneCounter,
+    pub target: u32,
+    #[serde(default)]
+    pub reward: u32,
+    //milestones can hand out unlocks instead of, or on top of, currency
+    #[serde(default)]
+    pub unlock_item: Option<i32>,
+    #[serde(default)]
+    pub unlock_loadout: Option<String>,
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct MetaConfig {
+    pub currency_per_floor: u32,
+    //one meta currency for every this many kills
+    pub kills_per_currency: u32,
+    pub win_bonus: u32,
+    pub item_unlocks: Vec<ItemUnlock>,
+    pub loadouts: Vec<Loadout>,
+    pub milestones: Vec<Milestone>,
+}
+
+impl MetaConfig {
+    pub fn loadout(&self, id: &str) -> Option<&Loadout> {
+        self.loadouts.iter().find(|loadout| {loadout.id == id})
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunRewards {
+    pub currency: u32,
+    pub milestones: Vec<String>,
+}
+
+pub fn load_meta_config(dir: String) -> MetaConfig {
+    println!("loading {}", dir);
+    let json_string = fs::read_to_string(dir).unwrap();
+    serde_json::from_str::<MetaConfig>(&json_string).unwrap()
+}
+
+//locked items are taken out of the tables and unlocked ones join the table they were set for
+pub fn apply_item_unlocks(config: &MetaConfig, profile: &Profile, loot_tables: &mut HashMap<String, LootTable>) {
+    for unlock in config.item_unlocks.iter() {
+        let table = match loot_tables.get_mut(&unlock.table) {
+            Some(table) => table,
+            None => continue,
+        };
+        let in_table = table.items.iter().any(|item| {item.item_id as i32 == unlock.item_id});
+
+        if !profile.unlocked_items.contains(&unlock.item_id) {
+            table.items.retain(|item| {item.item_id as i32 != unlock.item_id});
+        } else if !in_table {
+            table.items.push(LootItem { item_id: unlock.item_id as i64, rarity: unlock.rarity });
+            table.items.sort_by(|a,b| a.rarity.cmp(&b.rarity));
+        }
+        table.acc = table.items.iter().map(|i|{i.rarity}).sum();
+    }
+}
+
+pub fn equip_loadout(config: &MetaConfig, profile: &Profile, player: &mut Player, items: &HashMap<i32, Item>, effects: &HashMap<i32, ItemEffects>) {
+    let loadout = match config.loadout(&profile.loadout) {
+        Some(loadout) if profile.has_loadout(&loadout.id) => loadout,
+        _ => return,
+    };
+    for item_id in loadout.item_ids.iter() {
+        if let Some(item) = items.get(item_id) {
+            player.equip_item(&mut item.clone(), effects);
+        }
+    }
+    player.currency += loadout.currency;
+}
+
+//the counters that grow during matches, taken from the stats of one match
+pub fn milestone_counts(stats: &RunStats) -> Vec<(MilestoneCounter, u32)> {
+    vec![
+        (MilestoneCounter::Kills, stats.enemies_killed),
+        (MilestoneCounter::Hits, stats.hits_landed),
+        (MilestoneCounter::HitsTaken, stats.hits_taken),
+    ]
+}
+
+pub fn finish_run(config: &MetaConfig, profile: &mut Profile, stats: &RunStats, outcome: RunOutcome) -> RunRewards {
+    let won = outcome == RunOutcome::Won;
+    profile.add_progress(MilestoneCounter::RunsPlayed, 1);
+    profile.add_progress(MilestoneCounter::FloorsCleared, stats.floors_cleared);
+    profile.add_progress(MilestoneCounter::RunsWon, won as u32);
+    profile.record_best(MilestoneCounter::BestCombo, stats.best_combo);
+
+    let mut rewards = RunRewards::default();
+    //giving up only keeps the progress, not the currency
+    if outcome != RunOutcome::Abandoned {
+        rewards.currency = stats.floors_cleared * config.currency_per_floor
+            + stats.enemies_killed / config.kills_per_currency.max(1)
+            + if won { config.win_bonus } else { 0 };
+    }
+
+    for milestone in config.milestones.iter() {
+        if profile.completed_milestones.contains(&milestone.id) || profile.progress(milestone.counter) < milestone.target {
+            continue;
+        }
+        profile.completed_milestones.insert(milestone.id.clone());
+        rewards.currency += milestone.reward;
+        if let Some(item_id) = milestone.unlock_item {
+            profile.unlocked_items.insert(item_id);
+        }
+        if let Some(loadout) = &milestone.unlock_loadout {
+            profile.unlocked_loadouts.insert(loadout.clone());
+        }
+        rewards.milestones.push(milestone.name.clone());
+    }
+
+    profile.meta_currency += rewards.currency;
+    rewards
+}
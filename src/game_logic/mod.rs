@@ -9,5 +9,14 @@ pub mod inputs;
 pub mod projectile;
 pub mod store;
 pub mod combo_string;
+pub mod boss;
+pub mod difficulty;
+pub mod coop;
+pub mod run_seed;
+pub mod rng_streams;
+pub mod run_stats;
+pub mod meta_progression;
+pub mod ascension;
+pub mod grab;
 
 pub mod events;
\ No newline at end of file
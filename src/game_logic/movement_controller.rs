@@ -2,12 +2,22 @@ use std::rc::Rc;
 
 use parry2d::na::Vector2;
 
-use crate::{asset_management::{asset_holders::EntityAnimations, common_assets::CommonAssets, sound::audio_player}, engine_types::animator::Animator, rendering::camera::Camera};
+use crate::{asset_management::asset_holders::EntityAnimations, engine_types::{animator::Animator, frame_data::CancelKind}, rendering::camera::Camera};
 
 use super::characters::{Character, player::EntityState};
 
 use crate::utils::math_sign::Sign;
 
+pub const MAX_GUARD: f64 = 100.0;
+//guard per second, only after a while without blocking
+const GUARD_REGEN: f64 = 25.0;
+const GUARD_REGEN_DELAY: f64 = 1.0;
+const GUARD_BREAK_STUN: f64 = 1.5;
+//a block pressed this close to the hit is a parry, about 6 frames
+const PARRY_WINDOW: f64 = 0.1;
+//a missed parry cant be retried for a while, mashing block would parry everything otherwise
+const PARRY_LOCKOUT: f64 = 0.4;
+
 #[derive(Clone)]
 pub struct MovementController {
     pub walking_dir: Vector2<i8>,
@@ -31,6 +41,15 @@ pub struct MovementController {
     pub combo_counter: i32,
         
     pub knock_back_distance: f64,
+    pub guard: f64,
+    pub guard_regen_delay: f64,
+    pub guard_break_timer: f64,
+    pub parry_window: f64,
+    pub parry_lockout: f64,
+    //frames left of hit or block stun
+    pub stun_frames: i32,
+    //cancel windows open on the current frame of the attack, None when the animation has no frame data
+    pub open_cancels: Option<Vec<CancelKind>>,
     pub mid_jump_pos: f64,
 }
 
@@ -58,6 +77,13 @@ impl MovementController {
             combo_counter: 0,
             has_hit: false,
             knock_back_distance: 0f64,
+            guard: MAX_GUARD,
+            guard_regen_delay: 0f64,
+            guard_break_timer: 0f64,
+            parry_window: 0f64,
+            parry_lockout: 0f64,
+            stun_frames: 0,
+            open_cancels: None,
         
             mid_jump_pos: 0f64,
         }
@@ -96,16 +122,27 @@ impl MovementController {
         self.walking_dir.y = y;
     }
 
+    pub fn can_cancel_into(&self, kind: CancelKind) -> bool {
+        match &self.open_cancels {
+            Some(cancels) => cancels.contains(&kind),
+            //without frame data anything goes once the attack connected
+            None => self.has_hit,
+        }
+    }
+
     pub fn can_dash_attack(&self) -> bool {
-        !((self.is_attacking && !self.has_hit)
+        !((self.is_attacking && !self.can_cancel_into(CancelKind::Special))
             || self.state == EntityState::Jump
             || self.state == EntityState::Dead)
     }
 
     pub fn can_attack(&self) -> bool {
-        !((self.is_attacking && !self.has_hit)
+        !((self.is_attacking && !self.can_cancel_into(CancelKind::Normal))
             || self.state == EntityState::Jump
             || self.state == EntityState::Dashing
+            || self.state == EntityState::Grabbing
+            || self.state == EntityState::Grabbed
+            || self.state == EntityState::GuardBroken
             || self.state == EntityState::Dead)
     }
 
@@ -114,6 +151,9 @@ impl MovementController {
             || self.is_airborne
             || self.knock_back_distance.abs() > 0.0
             || self.state == EntityState::Dead
+            || self.state == EntityState::Grabbing
+            || self.state == EntityState::Grabbed
+            || self.state == EntityState::GuardBroken
             || self.state == EntityState::Dashing)
     }
 
@@ -177,6 +217,19 @@ impl MovementController {
                     animator.play_once(animation.clone(), 1.0, false);
                 }
             }
+            //not every character has grab animations yet
+            EntityState::Grabbing => {
+                let animation = character_animation.get("grab_hold").or(character_animation.get("idle"));
+                animator.play(animation.unwrap().clone(), 1.0, false);
+            }
+            EntityState::Grabbed => {
+                let animation = character_animation.get("grabbed").or(character_animation.get("take_damage"));
+                animator.play_once(animation.unwrap().clone(), 1.0, false);
+            }
+            EntityState::GuardBroken => {
+                let animation = character_animation.get("guard_break").or(character_animation.get("take_damage"));
+                animator.play_once(animation.unwrap().clone(), 1.0, false);
+            }
         }
     }
 
@@ -189,7 +242,9 @@ impl MovementController {
             (self.state == EntityState::Dashing && animator.is_finished) || 
             self.state == EntityState::Hurt ||
             self.state == EntityState::KnockedLanding || 
-            self.state == EntityState::DroppedLanding;
+            self.state == EntityState::DroppedLanding ||
+            self.state == EntityState::Grabbing ||
+            self.state == EntityState::Grabbed;
 
         let can_walk = 
             self.state == EntityState::Idle ||
@@ -201,7 +256,7 @@ impl MovementController {
             (self.state == EntityState::DroppedLanding && animator.is_finished);
 
         let can_jump = 
-            (self.is_attacking && self.has_hit) ||
+            (self.is_attacking && self.can_cancel_into(CancelKind::Jump)) ||
             self.state == EntityState::Idle ||
             self.state == EntityState::Jumping ||
             self.state == EntityState::Walking;
@@ -209,9 +264,9 @@ impl MovementController {
         let interrupt_attack = new_state == EntityState::Landing || 
             new_state == EntityState::Hurt;
 
-        let cancel_attack = (self.is_attacking && self.has_hit) && (
-            new_state == EntityState::Jump ||
-            new_state == EntityState::Dashing 
+        let cancel_attack = self.is_attacking && (
+            (new_state == EntityState::Jump && self.can_cancel_into(CancelKind::Jump)) ||
+            (new_state == EntityState::Dashing && self.can_cancel_into(CancelKind::Dash))
             );
         
         let can_dash = !self.is_airborne && 
@@ -226,7 +281,7 @@ impl MovementController {
         );
 
         let can_air_dash =(self.is_airborne && self.can_air_dash) && self.state != EntityState::Knocked;
-        let got_hurt = new_state == EntityState::Hurt || new_state == EntityState::Knocked || new_state == EntityState::Dropped || new_state == EntityState::Dead;
+        let got_hurt = new_state == EntityState::Hurt || new_state == EntityState::Knocked || new_state == EntityState::Dropped || new_state == EntityState::Grabbed || new_state == EntityState::Dead;
         
         if (!self.is_attacking || (self.is_attacking && (interrupt_attack || got_hurt)) || cancel_attack )  && self.state != EntityState::Dead {
             match new_state {
@@ -284,12 +339,91 @@ impl MovementController {
         self.direction_at_jump_time = 0;
     }
 
+    pub fn start_parry_window(&mut self) {
+        if self.parry_lockout > 0.0 {
+            return;
+        }
+        self.parry_window = PARRY_WINDOW;
+        self.parry_lockout = PARRY_WINDOW + PARRY_LOCKOUT;
+    }
+
+    //a parry that landed can be followed up by another one right away
+    pub fn parry_landed(&mut self) {
+        self.parry_lockout = 0.0;
+    }
+
+    pub fn is_guard_broken(&self) -> bool {
+        self.guard_break_timer > 0.0
+    }
+
+    //returns true when this block broke the guard
+    pub fn drain_guard(&mut self, guard_damage: i32, animator: &mut Animator) -> bool {
+        self.guard -= guard_damage as f64;
+        self.guard_regen_delay = GUARD_REGEN_DELAY;
+        if self.guard > 0.0 {
+            return false;
+        }
+
+        self.guard = 0.0;
+        self.is_blocking = false;
+        self.is_attacking = false;
+        self.guard_break_timer = GUARD_BREAK_STUN;
+        self.update_state(EntityState::GuardBroken, animator);
+        true
+    }
+
+    fn update_guard(&mut self, animator: &mut Animator, dt: f64) {
+        self.parry_window = (self.parry_window - dt).max(0.0);
+        self.parry_lockout = (self.parry_lockout - dt).max(0.0);
+
+        //the timer keeps running through hits, the stun is picked up again once the hurt animation is over
+        if self.is_guard_broken() {
+            self.guard_break_timer -= dt;
+            if self.guard_break_timer <= 0.0 {
+                self.guard_break_timer = 0.0;
+                self.guard = MAX_GUARD;
+                if self.state == EntityState::GuardBroken {
+                    self.update_state(EntityState::Idle, animator);
+                }
+            } else if self.state == EntityState::Idle || self.state == EntityState::Walking {
+                self.update_state(EntityState::GuardBroken, animator);
+            }
+        } else if self.guard_regen_delay > 0.0 {
+            self.guard_regen_delay -= dt;
+        } else {
+            self.guard = (self.guard + GUARD_REGEN * dt).min(MAX_GUARD);
+        }
+    }
+
     pub fn knock_back(&mut self, pos: &mut Vector2<f64>, amount: f64, dt: f64) {
         *pos += Vector2::new(amount * dt, 0.0);
         self.knock_back_distance = amount - (amount * 10.0 * dt);
     }
 
+    fn update_frame_data(&mut self, animator: &mut Animator) {
+        let frame_data = match animator.current_animation.as_ref().and_then(|animation| {animation.frame_data.as_ref()}) {
+            Some(frame_data) if self.is_attacking => frame_data,
+            _ => {
+                self.open_cancels = None;
+                return;
+            }
+        };
+
+        let frame = animator.animation_index;
+        self.open_cancels = Some([CancelKind::Normal, CancelKind::Special, CancelKind::Jump, CancelKind::Dash].iter()
+            .filter(|&&kind| {frame_data.can_cancel(frame, kind, self.has_hit)})
+            .cloned()
+            .collect());
+
+        //recovery can end before the animation does
+        if frame >= frame_data.total() as f64 && !animator.is_finished {
+            animator.is_playing = false;
+            animator.is_finished = true;
+        }
+    }
+
     pub fn state_update(&mut self, animator: &mut Animator, debug: bool) {
+        self.update_frame_data(animator);
 
         if animator.is_finished && self.state != EntityState::Dead {
 
@@ -316,7 +450,7 @@ impl MovementController {
                 
             }
 
-            if self.state == EntityState::Hurt {
+            if self.state == EntityState::Hurt && self.stun_frames <= 0 {
                 if self.walking_dir.x != 0 || self.walking_dir.y != 0  {
                     self.set_entity_state(EntityState::Walking, animator);
                 } else {
@@ -351,8 +485,13 @@ impl MovementController {
         camera: &mut Camera,
         dt: f64,
         character_width: i32,
-        common_assets: &CommonAssets,
+        sounds: &mut Vec<String>,
     ) {
+        self.update_guard(animator, dt);
+        if self.stun_frames > 0 {
+            self.stun_frames -= 1;
+        }
+
         if self.state == EntityState::Jump {
             if !self.is_airborne {
                 self.ground_height = position.y as i32;
@@ -363,7 +502,7 @@ impl MovementController {
    
         if self.state == EntityState::Jumping {
             if !self.is_airborne || (self.is_airborne && self.is_double_jumping && animator.is_starting) {
-                audio_player::play_sound(common_assets.sound_effects.get("jump").unwrap());
+                sounds.push("jump".to_string());
             }
             self.is_airborne = true;
         }
@@ -416,16 +555,16 @@ impl MovementController {
                     self.set_entity_state(EntityState::Landing, animator);
                     self.is_attacking = false;
                     self.combo_counter = 0;
-                    audio_player::play_sound(common_assets.sound_effects.get("land").unwrap());
+                    sounds.push("land".to_string());
                 }
                 if self.state == EntityState::Knocked {
                     self.set_entity_state(EntityState::KnockedLanding, animator);
-                    audio_player::play_sound(common_assets.sound_effects.get("land").unwrap());
+                    sounds.push("land".to_string());
                 }
                 if self.state == EntityState::Dropped {
                     camera.shake();
                     self.set_entity_state(EntityState::DroppedLanding, animator);
-                    audio_player::play_sound(common_assets.sound_effects.get("dropped").unwrap());
+                    sounds.push("dropped".to_string());
                 }
                 self.is_double_jumping = false;
                 self.is_airborne = false;
@@ -447,7 +586,7 @@ impl MovementController {
                 Some(offsets) => {
                     let offset = offsets[animator.sprite_shown as usize];
                     if self.state == EntityState::Dashing && offset.x > 0f64 && animator.sprite_shown > 0 && offsets[animator.sprite_shown as usize - 1].x == 0f64  {
-                        audio_player::play_sound(common_assets.sound_effects.get("dash").unwrap());
+                        sounds.push("dash".to_string());
                     }
                     *position += Vector2::new( self.facing_dir as f64 * offset.x, offset.y) * dt
                 }
@@ -1,9 +1,12 @@
-use parry2d::{math::{Point, Real}, na::Vector2};
+use parry2d::{bounding_volume::AABB, math::{Point, Real}, na::Vector2};
 use sdl2::{rect::Rect, render::Texture};
 
-use crate::{asset_management::asset_holders::{EntityAnimations, EntityAssets}, engine_types::{animation::Animation, animator::Animator, collider::Collider}, rendering::camera::Camera};
+use crate::{asset_management::asset_holders::{EntityAnimations, EntityAssets}, collision::collision_attack_resolution::{detect_hit, did_sucessfully_block}, ecs_system::{enemy_manager::EnemyManager, enemy_systems::take_damage}, engine_types::{animation::Animation, animator::Animator, collider::{Collider, ColliderType}}, rendering::camera::Camera, telemetry::TelemetryEvent};
 
-use super::{characters::Attack, inputs::input_cycle::AllInputManagement};
+use super::{characters::{Attack, AttackType, player::{EntityState, Player}}, factories::enemy_factory::EnemyProjectile, inputs::input_cycle::AllInputManagement};
+
+//projectiles fired by enemies, players use their own id
+pub const ENEMY_PROJECTILE_OWNER: i32 = -1;
 
 pub struct Projectile {
     pub position: Vector2<f64>,
@@ -18,6 +21,8 @@ pub struct Projectile {
     pub flipped: bool,
     pub animator: Animator,
     pub player_owner: i32,
+    //only hits entities on the same depth, like melee attacks
+    pub ground_height: i32,
     pub kill_at_animation_end: bool,
     pub is_alive: bool,
     pub die_out_of_camera: bool,
@@ -54,6 +59,7 @@ impl Projectile {
             flipped: false,
             animator: Animator::new(),
             player_owner,
+            ground_height: spawn_point.y as i32,
             is_alive: true,
             kill_at_animation_end: false,
             die_out_of_camera: true,
@@ -83,13 +89,13 @@ impl Projectile {
     }
 
     pub fn update(&mut self, camera: &Camera) {
+        if self.die_out_of_camera && (self.position.x < camera.rect.x as f64 || self.position.x > (camera.rect.x as u32 + camera.rect.width()) as f64) {
+            self.is_alive = false;
+        }
+
         match self.target_position {
             Some(target) => {
 
-                if self.position.x < camera.rect.x as f64 || self.position.x > (camera.rect.x as u32 + camera.rect.width()) as f64 {
-                    self.is_alive = false;
-                }
-
                 let mut position_directionless = self.position;
                 position_directionless.x *= self.direction.x as f64;
                 position_directionless.y *= self.direction.y as f64;
@@ -113,10 +119,113 @@ impl Projectile {
         if self.kill_at_animation_end && self.animator.is_finished {
             self.is_alive = false;
         }
-        self.animator.update();
+        //enemy projectiles have no sprites yet
+        if self.animator.current_animation.is_some() {
+            self.animator.update();
+        }
     }
 
     pub fn render<'a>(&'a self, assets: &'a EntityAssets<'a>) -> &'a Texture {
         assets.textures.get(&self.animator.render()).unwrap()
     }
 }
+
+//fired from the front of the enemy pushbox, straight ahead
+pub fn spawn_enemy_projectile(enemies: &EnemyManager, enemy: usize, definition: &EnemyProjectile) -> Option<Projectile> {
+    let mov = enemies.movement_controller_components[enemy].as_ref()?;
+    let pushbox = enemies.collider_components[enemy].as_ref()?.colliders.iter()
+        .filter(|c| c.collider_type == ColliderType::Pushbox)
+        .last()?;
+    let damage_modifier = enemies.damage_modifier_components[enemy].as_ref().map_or(1f64, |modifier| {modifier.0});
+
+    let dir = if mov.facing_dir < 0 { -1f64 } else { 1f64 };
+    let center = pushbox.aabb.center();
+    let spawn_point = Vector2::new(center.x as f64 + dir * pushbox.aabb.half_extents().x as f64, center.y as f64);
+
+    let damage = (definition.damage as f64 * damage_modifier).round() as i32;
+    let mut projectile = Projectile::new(ENEMY_PROJECTILE_OWNER, spawn_point, Attack {
+        damage,
+        stun_on_hit: definition.stun_on_hit,
+        stun_on_block: definition.stun_on_block,
+        push_back: definition.push_back,
+        guard_damage: damage,
+        attack_type: AttackType::Special,
+        on_hit: None,
+    });
+    projectile.direction = Vector2::new(dir, 0f64);
+    projectile.speed = definition.speed;
+    projectile.sprite = Rect::new(0, 0, definition.width, definition.height);
+    projectile.flipped = dir < 0f64;
+    projectile.ground_height = mov.ground_height;
+
+    let (half_w, half_h) = (definition.width as f32 / 2f32, definition.height as f32 / 2f32);
+    projectile.colliders.push(Collider {
+        collider_type: ColliderType::Hitbox,
+        name: "projectile".to_string(),
+        aabb: AABB::new(
+            Point::new(spawn_point.x as f32 - half_w, spawn_point.y as f32 - half_h),
+            Point::new(spawn_point.x as f32 + half_w, spawn_point.y as f32 + half_h),
+        ),
+        enabled: true,
+    });
+    Some(projectile)
+}
+
+//enemy projectiles fly until they hit a player or leave the camera, blocking them works like blocking a punch
+pub fn update_enemy_projectiles(projectiles: &mut Vec<Projectile>,
+    players: &mut [&mut Player],
+    camera: &Camera,
+    hit_stop: &mut i32,
+    sounds: &mut Vec<String>,
+    telemetry: &mut Vec<TelemetryEvent>,
+    dt: f64) {
+
+    for projectile in projectiles.iter_mut().filter(|p| p.player_owner == ENEMY_PROJECTILE_OWNER) {
+        let prev_pos = projectile.position;
+        projectile.update(camera);
+        let offset = projectile.position - prev_pos;
+        for collider in projectile.colliders.iter_mut() {
+            collider.aabb.mins.coords[0] += offset.x as f32;
+            collider.aabb.mins.coords[1] += offset.y as f32;
+            collider.aabb.maxs.coords[0] += offset.x as f32;
+            collider.aabb.maxs.coords[1] += offset.y as f32;
+        }
+
+        for player in players.iter_mut() {
+            if !projectile.is_alive || player.controller.state == EntityState::Dead || (player.controller.ground_height - projectile.ground_height).abs() > 50 {
+                continue;
+            }
+            let point = match detect_hit(&projectile.colliders, &player.collision_manager.colliders) {
+                Some((point, _)) => point,
+                None => continue,
+            };
+            projectile.is_alive = false;
+
+            let attack = &projectile.attack;
+            let push_back = attack.push_back * projectile.direction.x;
+            if did_sucessfully_block(point, player.position, &player.controller) {
+                sounds.push("block".to_string());
+                player.controller.knock_back(&mut player.position, push_back, dt);
+                player.controller.stun_frames = attack.stun_on_block;
+                *hit_stop = 5;
+                if player.controller.drain_guard(attack.guard_damage, &mut player.animator) {
+                    sounds.push("guard_break".to_string());
+                    *hit_stop = 10;
+                }
+            } else {
+                let hp_before = player.hp.0;
+                sounds.push("hit".to_string());
+                take_damage(&mut player.hp, attack.damage, &mut player.controller, &mut player.animator);
+                player.controller.state_update(&mut player.animator, false);
+                player.controller.knock_back(&mut player.position, push_back, dt);
+                player.controller.stun_frames = attack.stun_on_hit;
+                *hit_stop = 10;
+                telemetry.push(TelemetryEvent::DamageTaken {
+                    player: player.id,
+                    amount: hp_before - player.hp.0,
+                    source: "projectile".to_string(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use rand::{SeedableRng, prelude::SmallRng};
+
+use super::run_seed::splitmix64;
+
+//every system that rolls dice gets its own stream derived from the run seed
+//so an extra roll in one of them doesnt reshuffle what the others get
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum RngStream {
+    Map,
+    LevelLayout,
+    Loot,
+    Store,
+    Combat,
+}
+
+impl RngStream {
+    //fixed ids, reordering the enum must not change the runs of a seed, 5 was the unused ai stream
+    fn id(&self) -> u64 {
+        match self {
+            RngStream::Map => 1,
+            RngStream::LevelLayout => 2,
+            RngStream::Loot => 3,
+            RngStream::Store => 4,
+            RngStream::Combat => 6,
+        }
+    }
+}
+
+//rng for one stream, keys pick a spot inside it (act, level, room...) so the same keys always roll the same
+pub fn stream_rng(seed: u64, stream: RngStream, keys: &[u64]) -> SmallRng {
+    let mut state = splitmix64(seed ^ splitmix64(stream.id()));
+    for key in keys {
+        state = splitmix64(state ^ key);
+    }
+    SmallRng::seed_from_u64(state)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RngStreams {
+    pub seed: u64,
+    //how many rngs each stream handed out, saved with the run so a loaded run continues the sequence
+    pub draws: HashMap<RngStream, u64>,
+}
+
+impl RngStreams {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            draws: HashMap::new(),
+        }
+    }
+
+    pub fn restore(seed: u64, draws: HashMap<RngStream, u64>) -> Self {
+        Self {
+            seed,
+            draws,
+        }
+    }
+
+    //for things that happen in sequence, like generating each act map
+    pub fn next(&mut self, stream: RngStream) -> SmallRng {
+        let draw = self.draws.entry(stream).or_insert(0);
+        let rng = stream_rng(self.seed, stream, &[*draw]);
+        *draw += 1;
+        rng
+    }
+
+    //for things tied to a place in the run, rolling twice gives the same result
+    pub fn keyed(&self, stream: RngStream, keys: &[u64]) -> SmallRng {
+        stream_rng(self.seed, stream, keys)
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+
+const SECONDS_PER_DAY: u64 = 86400;
+pub const MAX_SEED_TEXT_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum SeedMode {
+    Random,
+    //days since the unix epoch, in utc so everyone gets the same run on the same day
+    Daily(u64),
+    Custom,
+}
+
+impl Default for SeedMode {
+    fn default() -> Self {
+        SeedMode::Random
+    }
+}
+
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen::<u64>()
+}
+
+pub fn today() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() / SECONDS_PER_DAY
+}
+
+pub fn daily_seed(day: u64) -> u64 {
+    splitmix64(day)
+}
+
+//numbers are used as they are so seeds can be copied around, any other text is hashed into one
+pub fn parse_seed(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    match text.parse::<u64>() {
+        Ok(seed) => Some(seed),
+        Err(_) => Some(fnv1a(text)),
+    }
+}
+
+pub fn seed_label(seed: u64, mode: SeedMode) -> String {
+    match mode {
+        SeedMode::Daily(day) => format!("Daily {} seed {}", date_string(day), seed),
+        _ => format!("Seed {}", seed),
+    }
+}
+
+//yyyy-mm-dd from days since the epoch, civil from days by Howard Hinnant
+pub fn date_string(day: u64) -> String {
+    let z = day as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+pub fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+//stable across builds unlike the std hasher
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
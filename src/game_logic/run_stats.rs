@@ -0,0 +1,44 @@
+#[derive(Debug, Clone, Copy, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum RunOutcome {
+    Abandoned,
+    Died,
+    Won,
+}
+
+//totals for the whole run, every match keeps its own and adds them here when it ends
+#[derive(Debug, Clone, Default, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RunStats {
+    pub floors_cleared: u32,
+    pub enemies_killed: u32,
+    //hits that landed on enemies and hits the players took, for the profile milestones
+    pub hits_landed: u32,
+    pub hits_taken: u32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub best_combo: u32,
+    pub items_collected: u32,
+    pub currency_earned: u32,
+    //seconds of simulated match time, menus and the overworld dont count
+    pub time: f64,
+}
+
+impl RunStats {
+    pub fn add(&mut self, other: &RunStats) {
+        self.floors_cleared += other.floors_cleared;
+        self.enemies_killed += other.enemies_killed;
+        self.hits_landed += other.hits_landed;
+        self.hits_taken += other.hits_taken;
+        self.damage_dealt += other.damage_dealt;
+        self.damage_taken += other.damage_taken;
+        self.best_combo = self.best_combo.max(other.best_combo);
+        self.items_collected += other.items_collected;
+        self.currency_earned += other.currency_earned;
+        self.time += other.time;
+    }
+}
+
+pub fn time_label(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
@@ -1,4 +1,4 @@
-use rand::{Rng, SeedableRng, prelude::SmallRng};
+use rand::prelude::SmallRng;
 use sdl2::rect::Rect;
 
 use crate::{asset_management::rng_tables::LootTable, game_logic::items::get_random_item};
@@ -63,26 +63,24 @@ impl StoreUI {
 
 }
 
-pub fn get_store_item_list(seed: u64, loot_table: &LootTable) -> Vec<i64>{
-    let mut rng = SmallRng::seed_from_u64(seed);
-
+pub fn get_store_item_list(rng: &mut SmallRng, loot_table: &LootTable) -> Vec<i64>{
     let mut store_clone = (*loot_table).clone();
 
     let mut four_random_indexes: Vec<i64> = Vec::new();
 
-    let random_item = get_random_item(&store_clone, &mut rng);
+    let random_item = get_random_item(&store_clone, rng);
     four_random_indexes.push(random_item);
     store_clone.items.retain(|i| {i.item_id != random_item});
 
-    let random_item = get_random_item(&store_clone, &mut rng);
+    let random_item = get_random_item(&store_clone, rng);
     four_random_indexes.push(random_item);
     store_clone.items.retain(|i| {i.item_id != random_item});
 
-    let random_item = get_random_item(&store_clone, &mut rng);
+    let random_item = get_random_item(&store_clone, rng);
     four_random_indexes.push(random_item);
     store_clone.items.retain(|i| {i.item_id != random_item});
 
-    let random_item = get_random_item(&store_clone, &mut rng);
+    let random_item = get_random_item(&store_clone, rng);
     four_random_indexes.push(random_item);
     store_clone.items.retain(|i| {i.item_id != random_item});
 
@@ -0,0 +1,293 @@
+use std::{collections::HashMap, rc::Rc};
+
+use parry2d::na::Vector2;
+use rand::{Rng, SeedableRng, prelude::SmallRng};
+use sdl2::rect::Point;
+
+use crate::{asset_management::{asset_holders::{VfxData, load_level_rooms}, rng_tables::load_item_table, run_save::PlayerSave, settings::Settings}, challenges::ChallengeManager, debug_console::console::apply_debug_action, game_logic::{ascension::load_ascension_config, difficulty::load_difficulty_curve, boss::{Boss, load_bosses, load_boss_arena}, characters::player::EntityState, combo_string::ComboCounter, effects::hash_effects, factories::{character_factory::{load_character, load_character_animations, load_character_data, load_character_definition, load_character_sprite_data}, enemy_factory::{load_enemy_archetypes, load_enemy_ryu_animations, load_enemy_ryu_sprite_data}, item_factory::load_items}, game::{Game, StepContext}, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement, input_recorder::InputRecording}, rng_streams::{RngStream, stream_rng}}, level_generation::{Level, generate::generate_levels}, rendering::camera::Camera};
+
+const LOGIC_TIMESTEP: f64 = 0.016;
+const REPORT_EVERY_FRAMES: i32 = 600;
+
+const RANDOM_ACTIONS: [GameAction; 10] = [
+    GameAction::Right,
+    GameAction::Left,
+    GameAction::Up,
+    GameAction::Down,
+    GameAction::Punch,
+    GameAction::Kick,
+    GameAction::Jump,
+    GameAction::Block,
+    GameAction::Dash,
+    GameAction::Slide,
+];
+
+//what a headless run ended on, two runs from the same seed or recording have to end the same way
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessSummary {
+    pub result_message: &'static str,
+    pub frame: i32,
+    pub hp: i32,
+    pub position: (f64, f64),
+    pub enemies_alive: usize,
+    pub items_picked: usize,
+    pub score: u32,
+}
+
+//runs a match without a window, either replaying a recording or mashing random inputs from the seed
+pub fn run_headless(frames: i32, seed: u64, replay: Option<InputRecording>) {
+    let (summary, _recording) = simulate(frames, seed, replay);
+    println!("headless run ended: {}", summary.result_message);
+    print_summary(&summary);
+}
+
+//the random inputs are recorded the same way the match scene records a player, so they can be replayed
+pub fn simulate(frames: i32, seed: u64, replay: Option<InputRecording>) -> (HeadlessSummary, InputRecording) {
+    let items = load_items("assets/items/items.json".to_string());
+    let effects = hash_effects();
+    let mut loot_tables = load_item_table("assets/items/loot_tables.json".to_string());
+    let level_rooms = load_level_rooms();
+    let vfx = VfxData {
+        animations: VfxData::load_animations(),
+        texture_sizes: HashMap::new(),
+    };
+
+    let enemy_archetypes = load_enemy_archetypes("assets/enemies/enemies.json".to_string());
+
+    let definition = load_character_definition("foxgirl");
+    let mut enemy_animations = HashMap::new();
+    enemy_animations.insert("player".to_string(), Rc::new(load_character_animations(&definition)));
+    enemy_animations.insert("ryu".to_string(), Rc::new(load_enemy_ryu_animations()));
+
+    let p1_data = load_character_data(&definition);
+    let p1_sprite_data = load_character_sprite_data(&definition);
+    let mut enemy_sprite_data = HashMap::new();
+    enemy_sprite_data.insert("ryu".to_string(), load_enemy_ryu_sprite_data());
+
+    let mut player = load_character(&definition, Point::new(200, 50), 1, Rc::clone(enemy_animations.get("player").unwrap()));
+
+    let difficulty_curve = load_difficulty_curve("assets/difficulty.json".to_string());
+    let ascension_config = load_ascension_config("assets/meta/ascension.json".to_string());
+    let modifiers = ascension_config.modifiers(replay.as_ref().map(|replay| {replay.ascension}).unwrap_or(0));
+    //headless runs are single player, the co-op options keep their defaults
+    let settings = Settings::default();
+    modifiers.register_hooks(&mut player);
+
+    let (run_seed, level_seed, curr_act, curr_level, difficulty) = if let Some(replay) = &replay {
+        for item_id in replay.player.item_ids.iter() {
+            if let Some(item) = items.get(item_id) {
+                player.equip_item(&mut item.clone(), &effects);
+            }
+        }
+        replay.player.apply(&mut player);
+        loot_tables = replay.loot_tables.clone();
+        (replay.seed, replay.level_seed, replay.curr_act, replay.curr_level, replay.difficulty)
+    } else {
+        (seed, stream_rng(seed, RngStream::LevelLayout, &[0]).gen::<u64>(), 0, 0, 0)
+    };
+
+    let bosses = load_bosses("assets/enemies/bosses.json".to_string());
+    let boss_definition = replay.as_ref().and_then(|replay| {replay.boss.as_ref()}).and_then(|boss| {bosses.get(boss)});
+
+    let levels = if let Some(boss_definition) = boss_definition {
+        vec![Level::new(&load_boss_arena(boss_definition, &level_rooms), 0)]
+    } else {
+        generate_levels(&level_rooms, &mut SmallRng::seed_from_u64(level_seed))
+    };
+    player.seed_combat_rng(run_seed, curr_act, curr_level);
+    let mut game = Game::new(player, Camera::new(0, 0, 1280, 720), levels);
+    game.start_level();
+
+    if let Some(boss_definition) = boss_definition {
+        let boss_pos = game.player.position + Vector2::new(600f64, 0f64);
+        game.boss = Boss::spawn(boss_definition, difficulty_curve.tier(difficulty), &enemy_archetypes, &enemy_animations, &mut game.enemies, boss_pos);
+        if let Some(boss) = &game.boss {
+            modifiers.apply_to_boss(boss, &mut game.enemies);
+        }
+    }
+
+    let mut recording = InputRecording::new(
+        run_seed,
+        level_seed,
+        curr_act,
+        curr_level,
+        difficulty,
+        replay.as_ref().map(|replay| {replay.ascension}).unwrap_or(0),
+        PlayerSave::new(&game.player),
+        loot_tables.clone(),
+        replay.as_ref().and_then(|replay| {replay.boss.clone()}),
+    );
+
+    let mut inputs = AllInputManagement::new();
+    let mut input_rng = SmallRng::seed_from_u64(seed);
+    let mut challenges = ChallengeManager::new();
+    let mut combo_counter = ComboCounter::new(1.8f64);
+    let mut items_picked = 0;
+
+    println!("headless run seed {} level seed {} act {} level {} difficulty {}", run_seed, level_seed, curr_act, curr_level, difficulty);
+
+    let mut result_message = "frame limit reached";
+    let mut logic_frames = 0;
+    while logic_frames < frames {
+        logic_frames += 1;
+
+        if game.hit_stop == 0 {
+            if let Some(replay) = &replay {
+                if replay.is_finished(game.current_frame + 1) {
+                    result_message = "replay finished";
+                    break;
+                }
+                if replay.resets_inputs_at(game.current_frame + 1) {
+                    inputs = AllInputManagement::new();
+                }
+                inputs.input_new_frame = replay.input_at(game.current_frame + 1);
+                for action in replay.debug_actions_at(game.current_frame + 1) {
+                    apply_debug_action(&mut game, action, &items, &enemy_archetypes, &enemy_animations);
+                }
+            } else if input_rng.gen_bool(0.2) {
+                let action = RANDOM_ACTIONS[input_rng.gen_range(0..RANDOM_ACTIONS.len())];
+                inputs.input_new_frame ^= action as i32;
+            }
+        }
+
+        let mut ctx = StepContext {
+            p1_data: &p1_data,
+            p1_sprite_data: &p1_sprite_data,
+            enemy_sprite_data: &enemy_sprite_data,
+            vfx: &vfx,
+
+            items: &items,
+            effects: &effects,
+            enemy_animations: &enemy_animations,
+            enemy_archetypes: &enemy_archetypes,
+            difficulty: difficulty_curve.tier(difficulty),
+            modifiers: &modifiers,
+            loot_tables: &mut loot_tables,
+
+            seed: run_seed,
+            curr_act,
+            curr_level,
+
+            friendly_fire: settings.friendly_fire,
+            coop_loot: settings.coop_loot,
+
+            challenges: &mut challenges,
+            combo_counter: &mut combo_counter,
+        };
+        let frame_input = inputs.input_new_frame;
+        let result = game.step(&mut inputs, None, &mut ctx, LOGIC_TIMESTEP);
+        game.sounds.clear();
+        game.telemetry.clear();
+
+        if !result.advanced {
+            continue;
+        }
+        recording.record(game.current_frame, frame_input);
+        items_picked += result.picked_items.len();
+
+        if game.current_frame % REPORT_EVERY_FRAMES == 0 {
+            print_summary(&summarize(&game, items_picked, "running"));
+        }
+
+        if game.player.controller.state == EntityState::Dead {
+            result_message = "player died";
+            break;
+        }
+        if result.left_level {
+            result_message = "level cleared";
+            break;
+        }
+    }
+
+    (summarize(&game, items_picked, result_message), recording)
+}
+
+fn summarize(game: &Game, items_picked: usize, result_message: &'static str) -> HeadlessSummary {
+    let enemies_alive = game.enemies.health_components.iter()
+        .filter(|health| {
+            match health {
+                Some(health) => health.0 > 0,
+                None => false,
+            }
+        })
+        .count();
+
+    HeadlessSummary {
+        result_message,
+        frame: game.current_frame,
+        hp: game.player.hp.0,
+        position: (game.player.position.x, game.player.position.y),
+        enemies_alive,
+        items_picked,
+        score: game.score,
+    }
+}
+
+fn print_summary(summary: &HeadlessSummary) {
+    println!("frame {} hp {} position {:?} enemies alive {} items picked {} score {}", summary.frame, summary.hp, summary.position, summary.enemies_alive, summary.items_picked, summary.score);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use sdl2::rect::Point;
+
+    use crate::{asset_management::{rng_tables::load_item_table, run_save::PlayerSave, settings::Settings}, debug_console::console::DebugAction, game_logic::{factories::character_factory::{load_character, load_character_animations, load_character_definition}, inputs::{game_inputs::GameAction, input_recorder::InputRecording}}};
+
+    use super::simulate;
+
+    fn fixture_recording() -> InputRecording {
+        let definition = load_character_definition("foxgirl");
+        let player = load_character(&definition, Point::new(200, 50), 1, Rc::new(load_character_animations(&definition)));
+        let mut recording = InputRecording::new(7, 11, 0, 0, 0, 0, PlayerSave::new(&player), load_item_table("assets/items/loot_tables.json".to_string()), None);
+        //walk right for a second, punch twice and take a debug hit on the way
+        recording.record(1, GameAction::Right as i32);
+        recording.record(30, GameAction::Punch as i32);
+        recording.record(32, GameAction::Punch as i32);
+        recording.record(61, GameAction::Right as i32);
+        recording.record_debug_action(40, DebugAction::HurtPlayer(10));
+        recording.record(120, 0);
+        recording
+    }
+
+    #[test]
+    fn replaying_a_fixture_reaches_the_same_state() {
+        let recording = fixture_recording();
+        let start_hp = recording.player.hp;
+
+        let mut idle = recording.clone();
+        idle.frames.clear();
+        idle.debug_actions.clear();
+        let (idle_summary, _) = simulate(1000, 0, Some(idle));
+
+        let (summary, _) = simulate(1000, 0, Some(recording.clone()));
+        assert_eq!(summary.result_message, "replay finished");
+        assert_eq!(summary.frame, 120);
+        assert!(summary.position.0 > idle_summary.position.0);
+        assert!(summary.hp <= start_hp - 10);
+
+        let (again, _) = simulate(1000, 0, Some(recording));
+        assert_eq!(summary, again);
+    }
+
+    #[test]
+    fn random_runs_are_deterministic() {
+        //a few thousand frames of mashing, the same seed has to end in the same state
+        let (first, _) = simulate(3000, 42, None);
+        let (second, _) = simulate(3000, 42, None);
+        assert!(first.frame > 0);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn replaying_a_recorded_run_reproduces_it() {
+        let (recorded, recording) = simulate(900, 3, None);
+        let (replayed, _) = simulate(recording.last_frame * 2, 3, Some(recording));
+        assert_eq!(recorded.frame, replayed.frame);
+        assert_eq!(recorded.hp, replayed.hp);
+        assert_eq!(recorded.position, replayed.position);
+        assert_eq!(recorded.enemies_alive, replayed.enemies_alive);
+        assert_eq!(recorded.score, replayed.score);
+    }
+}
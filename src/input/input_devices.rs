@@ -1,12 +1,12 @@
-use std::collections::HashMap;
-
 use sdl2::{GameControllerSubsystem, JoystickSubsystem};
 
-use super::{controller_handler::Controller, translated_inputs::TranslatedInput};
+use crate::asset_management::controls::Controls;
+
+use super::controller_handler::Controller;
 
 pub struct InputDevices {
     pub joystick: JoystickSubsystem,
     pub  controller: GameControllerSubsystem,
-    pub  controls: HashMap<String, TranslatedInput>,
+    pub  controls: Controls,
     pub  joys: Controller,
 }
\ No newline at end of file
@@ -1,16 +1,17 @@
 extern crate sdl2;
 
 use sdl2::event::Event;
-use std::collections::HashMap;
 
 use crate::utils::math_sign::Sign;
 
+use crate::asset_management::controls::Controls;
+
 use super::translated_inputs::TranslatedInput;
 use super::controller_handler::KEYBOARD_ID;
 
 pub fn rcv_input(
     event: &Event,
-    game_controls: &HashMap<std::string::String, TranslatedInput>,
+    controls: &Controls,
 ) -> Option<(u32, TranslatedInput, bool)> {
     return match *event {
         Event::JoyAxisMotion {
@@ -37,6 +38,7 @@ pub fn rcv_input(
             which, button_idx, ..
         } => {
             println!("joy#{} button#{} down", which, button_idx);
+            let game_controls = &controls.gamepad;
             if game_controls.contains_key(&button_idx.to_string()) {
                 let input = *game_controls.get(&button_idx.to_string()).unwrap();
                 Some((which, input, true))
@@ -48,6 +50,7 @@ pub fn rcv_input(
             which, button_idx, ..
         } => {
             println!("joy#{} button#{} up", which, button_idx);
+            let game_controls = &controls.gamepad;
             if game_controls.contains_key(&button_idx.to_string()) {
                 let input = *game_controls.get(&button_idx.to_string()).unwrap();
                 Some((which, input, false))
@@ -58,6 +61,7 @@ pub fn rcv_input(
         Event::KeyDown {
             keycode, repeat, ..
         } => {
+            let game_controls = &controls.keyboard;
             if let Some(key_down) = keycode {
                 if game_controls.contains_key(&key_down.to_string()) && !repeat {
                     let input = *game_controls.get(&key_down.to_string()).unwrap();
@@ -67,6 +71,7 @@ pub fn rcv_input(
             None
         }
         Event::KeyUp { keycode, .. } => {
+            let game_controls = &controls.keyboard;
             if let Some(key_up) = keycode {
                 if game_controls.contains_key(&key_up.to_string()) {
                     let input = *game_controls.get(&key_up.to_string()).unwrap();
@@ -2,7 +2,7 @@ use std::fmt::{self, Display};
 
 use serde::de::{value, Deserializer, IntoDeserializer, Visitor};
 use serde::ser::{Serialize, Serializer};
-use serde::{ser::SerializeTupleVariant, Deserialize};
+use serde::Deserialize;
 use std::result::Result;
 use std::str::FromStr;
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -39,16 +39,12 @@ impl Serialize for TranslatedInput {
             TranslatedInput::Block => {
                 serializer.serialize_unit_variant("Block", 0, "Block")
             },
+            //written the same way the deserializer reads them back
             TranslatedInput::Vertical(ref v) => {
-                let mut state = serializer.serialize_tuple_variant("Vertical", 1, "Vertical", 1)?;
-                state.serialize_field(v)?;
-                state.end()
+                serializer.serialize_str(&format!("Vertical({})", v))
             }
             TranslatedInput::Horizontal(ref h) => {
-                let mut state =
-                    serializer.serialize_tuple_variant("Horizontal", 1, "Horizontal", 1)?;
-                state.serialize_field(h)?;
-                state.end()
+                serializer.serialize_str(&format!("Horizontal({})", h))
             }
         }
     }
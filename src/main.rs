@@ -1,12 +1,11 @@
 use engine_traits::scene::Scene;
-use game_logic::{characters::player::Player, effects::ItemEffects, events::Event, items::Item};
-use rand::prelude::SmallRng;
+use game_logic::{characters::player::Player, effects::ItemEffects, events::Event, inputs::input_recorder::InputRecording, items::Item};
 use scenes::menu_scene::MenuScene;
 use sdl2::{image::{self, InitFlag}, pixels::Color, rect::{Point, Rect}, render::Texture, ttf::Font};
 use sdl2::render::BlendMode;
 use ui::ingame::{segmented_bar_ui::SegmentedBar, wrapping_list_ui::WrappingList};
 
-use std::{collections::HashMap, path::Path, rc::Rc};
+use std::{collections::HashMap, path::{Path, PathBuf}, rc::Rc};
 
 extern crate serde_derive;
 extern crate directories;
@@ -26,12 +25,14 @@ mod enemy_behaviour;
 mod overworld;
 mod level_generation;
 mod challenges;
+mod headless;
+mod telemetry;
 
 mod debug_console;
 
-use asset_management::{asset_holders::{EntityAnimations, ItemAssets, LevelAssets, UIAssets}, common_assets::CommonAssets, sound::{init_sound, music_player}};
+use asset_management::{asset_holders::{EntityAnimations, ItemAssets, LevelAssets, UIAssets}, common_assets::CommonAssets, settings::{RENDER_RESOLUTION, Settings, apply_video_settings, load_settings}, sound::{audio_manager::AudioManager, init_sound}};
 
-use crate::{asset_management::{asset_loader::events_loader::load_events, controls}, game_logic::{effects::hash_effects, factories::item_factory::{load_item_assets, load_items}}, input::input_devices::InputDevices};
+use crate::{asset_management::{asset_loader::events_loader::load_events, controls, profile::{Profile, load_profile}}, game_logic::{boss::{BossDefinition, load_bosses}, rng_streams::RngStreams, run_seed::SeedMode, run_stats::{RunOutcome, RunStats}, meta_progression::{MetaConfig, load_meta_config}, ascension::{AscensionConfig, RunModifiers, load_ascension_config}, difficulty::{DifficultyCurve, load_difficulty_curve}, effects::hash_effects, factories::{character_factory::CharacterDefinition, enemy_factory::{EnemyArchetype, load_enemy_archetypes}, item_factory::{load_item_assets, load_items}}}, input::input_devices::InputDevices, telemetry::TelemetryLog};
 use crate::input::controller_handler::Controller;
 
 
@@ -56,7 +57,6 @@ use crate::input::controller_handler::Controller;
 
 //add sound_effects
     //everytime punch happens (very light sound)
-    //add variation to sounds through pitch changing
 
 //implement more item effects
     
@@ -65,8 +65,6 @@ use crate::input::controller_handler::Controller;
     // decision making on the type of node (level, event, store)
     // add small up/down animation on the "character" icon
 
-//separate json for animation offsets and animation states (startup, active, recovery)
-
 //Improve AI
 
 //TODO FEATURES
@@ -74,9 +72,7 @@ use crate::input::controller_handler::Controller;
 //add hit combos and block combos, these should be displayed while they are happening and not at the end to give faster feedback
 //charge special attacks like makoto where you can hold punch for a stronger attack
 //dash attack
-//save sound volume settings on config
 //add menu to change the controllers for each player
-//add menu to change the controller mapping of keys/buttons
 //make ui loop only 60fps to avoid the computer doing too many wasted computations
 
 //TODO TECH DEBT AND BUGS
@@ -98,15 +94,37 @@ use crate::input::controller_handler::Controller;
 pub struct GameStateData<'a> {
 
     event_success: bool,
+    boss_defeated: bool,
+    //set when a match ends the run, the overworld closes it when it sees it
+    run_outcome: Option<RunOutcome>,
+    run_stats: RunStats,
+    profile: Profile,
+    meta: MetaConfig,
+    ascension_config: AscensionConfig,
+    //ascension of the current run and the modifiers it turned on
+    ascension: u32,
+    run_modifiers: RunModifiers,
+    telemetry: TelemetryLog,
     player: Option<Player>,
     hp_bar: Option<SegmentedBar<'a>>,
     energy_bar: Option<SegmentedBar<'a>>,
+    //only set once someone dropped in as player 2
+    player2: Option<Player>,
+    p2_hp_bar: Option<SegmentedBar<'a>>,
+    p2_energy_bar: Option<SegmentedBar<'a>>,
+
+    settings: Settings,
+    audio: AudioManager,
 
     items: HashMap<i32, Item>,
     effects: HashMap<i32, ItemEffects>,
     events: HashMap<u32, Event>,
+    enemy_archetypes: HashMap<String, EnemyArchetype>,
+    bosses: HashMap<String, BossDefinition>,
+    difficulty_curve: DifficultyCurve,
 
     enemy_animations: HashMap<String, Rc<EntityAnimations>>,
+    character_definitions: HashMap<String, CharacterDefinition>,
     
     general_assets: CommonAssets<'a>,
     item_assets: ItemAssets<'a>,
@@ -115,10 +133,13 @@ pub struct GameStateData<'a> {
 
 
     curr_level: i32,
+    curr_act: u32,
+    curr_difficulty: i32,
 
     //rng
     seed: Option<u64>,
-    map_rng: Option<SmallRng>,
+    seed_mode: SeedMode,
+    rng_streams: RngStreams,
 }
 
 pub enum Transition {
@@ -158,6 +179,45 @@ pub fn energy_bar_init<'a>(screen_res: (u32, u32), max_energy: i32, curr_energy:
     )
 }
 
+pub fn p2_hp_bar_init<'a>(screen_res: (u32, u32), max_hp: i32, curr_hp: i32) -> SegmentedBar<'a> {
+    SegmentedBar::new(
+        (screen_res.0 - screen_res.0 / 3 - 30) as i32,
+        20,
+        screen_res.0 / 3 - 50,
+        25,
+        max_hp,
+        curr_hp,
+        20,
+        Some(Color::RGB(255, 180, 100)),
+        None,
+    )
+}
+
+pub fn p2_energy_bar_init<'a>(screen_res: (u32, u32), max_energy: i32, curr_energy: i32) -> SegmentedBar<'a> {
+    SegmentedBar::new(
+        (screen_res.0 - screen_res.0 / 3 - 30) as i32,
+        60,
+        screen_res.0 / 3 - 50,
+        10,
+        max_energy,
+        curr_energy,
+        1,
+        Some(Color::RGB(100, 200, 255)),
+        None,
+    )
+}
+
+pub fn p2_item_list_init(screen_res: (u32, u32), player: &Player) -> WrappingList {
+    WrappingList::new(
+        Point::new(screen_res.0 as i32 - 210, 70),
+        200,
+        player.items.iter()
+            .map(|_item| {Rect::new(0,0,32,32)})
+            .collect::<Vec<Rect>>(), 
+        10
+    )
+}
+
 pub fn item_list_init(game_state_data: &GameStateData) -> WrappingList {
     WrappingList::new(
         Point::new(10, 70),
@@ -170,6 +230,30 @@ pub fn item_list_init(game_state_data: &GameStateData) -> WrappingList {
 }
 
 fn main() -> Result<(), String> {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(headless_pos) = args.iter().position(|arg| arg == "--headless") {
+        let frames = args.get(headless_pos + 1).and_then(|arg| arg.parse::<i32>().ok()).unwrap_or(3600);
+        let seed = match args.iter().position(|arg| arg == "--seed") {
+            Some(pos) => args.get(pos + 1).and_then(|arg| arg.parse::<u64>().ok()).unwrap_or(0),
+            None => 0,
+        };
+        let replay = match args.iter().position(|arg| arg == "--replay") {
+            Some(pos) => args.get(pos + 1).and_then(|path| InputRecording::load(Path::new(path))),
+            None => None,
+        };
+
+        headless::run_headless(frames, seed, replay);
+        return Ok(());
+    }
+    if let Some(report_pos) = args.iter().position(|arg| arg == "--telemetry-report") {
+        let path = match args.get(report_pos + 1) {
+            Some(path) => PathBuf::from(path),
+            None => controls::config_file_path(telemetry::TELEMETRY_FILE),
+        };
+        telemetry::report::print_report(&path);
+        return Ok(());
+    }
+
     println!("Starting Game");
 
     let sdl_context = sdl2::init()?;
@@ -180,19 +264,24 @@ fn main() -> Result<(), String> {
 
     let _mixer_context = init_sound();
 
-    let music =
-        music_player::load_from_file(Path::new("assets/musics/RetroFuture_Dirty.mp3")).unwrap();
-   // music_player::play_music(&music);
+    let settings = load_settings();
+    let mut audio = AudioManager::load("assets/sounds/sound_bank.json".to_string(), "assets/musics/music.json".to_string());
+    audio.set_volumes(&settings);
 
     let window = video_subsystem
-        .window("fighting game", 1280, 720)
+        .window("fighting game", settings.window_width, settings.window_height)
         .position_centered()
         .build()
         .expect("could not initialize video subsystem");
-    let mut canvas = window
-        .into_canvas()
+    let mut canvas_builder = window.into_canvas();
+    if settings.vsync {
+        canvas_builder = canvas_builder.present_vsync();
+    }
+    let mut canvas = canvas_builder
         .build()
         .expect("could not make a canvas");
+    canvas.set_logical_size(RENDER_RESOLUTION.0, RENDER_RESOLUTION.1).map_err(|e| e.to_string())?;
+    apply_video_settings(&settings, &mut canvas);
 
     canvas.set_blend_mode(BlendMode::Blend); //blend mode was added specifically to see the colliders
     let texture_creator = canvas.texture_creator();
@@ -216,20 +305,42 @@ fn main() -> Result<(), String> {
     let general_assets = CommonAssets::load(&texture_creator, &ttf_context);
     let mut game_state_data = GameStateData {
         event_success: false,
+        boss_defeated: false,
+        run_outcome: None,
+        run_stats: RunStats::default(),
+        profile: load_profile(),
+        meta: load_meta_config("assets/meta/meta_progression.json".to_string()),
+        ascension_config: load_ascension_config("assets/meta/ascension.json".to_string()),
+        ascension: 0,
+        run_modifiers: RunModifiers::default(),
+        telemetry: TelemetryLog::open(),
         player: None,
         hp_bar: None,
         energy_bar: None,
+        player2: None,
+        p2_hp_bar: None,
+        p2_energy_bar: None,
+
+        settings,
+        audio,
 
 
         events: load_events("assets/events/events.json".to_string()),
         items: load_items("assets/items/items.json".to_string()),
         effects: hash_effects(),
+        enemy_archetypes: load_enemy_archetypes("assets/enemies/enemies.json".to_string()),
+        bosses: load_bosses("assets/enemies/bosses.json".to_string()),
+        difficulty_curve: load_difficulty_curve("assets/difficulty.json".to_string()),
         enemy_animations: HashMap::new(),
+        character_definitions: HashMap::new(),
         
         seed: None,
-        map_rng: None,
+        seed_mode: SeedMode::Random,
+        rng_streams: RngStreams::new(0),
 
         curr_level: -1,
+        curr_act: 0,
+        curr_difficulty: 0,
 
         ui_assets: UIAssets::load(&texture_creator, &general_assets.fonts),
         general_assets,
@@ -242,6 +353,9 @@ fn main() -> Result<(), String> {
 
     while !state_stack.is_empty() {
         let scene =  state_stack.last_mut().unwrap();
+        if let Some(track) = scene.music_track() {
+            game_state_data.audio.music.play_track(track);
+        }
 
         match scene.run(
             &mut game_state_data,
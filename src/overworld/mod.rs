@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use sdl2::rect::{Point, Rect};
 use delaunator::{Point as DelPoint, triangulate};
 
-use crate::scenes::overworld_scene::OverworldScene;
+use crate::{game_logic::difficulty::DifficultyCurve, scenes::overworld_scene::OverworldScene};
 
 use self::node::{WorldNode, WorldNodeType};
 
@@ -86,7 +86,7 @@ pub fn overworld_change_connections(overworld: &mut OverworldScene, rng: &mut Sm
 }
 
 
-pub fn overworld_generation(area: Rect, graph_size: (i32, i32), full_conection: bool, rng: &mut SmallRng) -> Vec<WorldNode> {
+pub fn overworld_generation(area: Rect, graph_size: (i32, i32), full_conection: bool, difficulty_curve: &DifficultyCurve, act: u32, rng: &mut SmallRng) -> Vec<WorldNode> {
 
     let (graph_width, graph_height) = graph_size;
     let (cell_width, cell_height) = (area.width() as i32 / graph_width, area.height() as i32 / graph_height);
@@ -122,7 +122,7 @@ pub fn overworld_generation(area: Rect, graph_size: (i32, i32), full_conection:
             );
 
             overworld.push(WorldNode{
-                node_type: WorldNodeType::Level(0),
+                node_type: WorldNodeType::Level(difficulty_curve.level_difficulty(act, row_level)),
                 position: Point::new(position_cell.0, position_cell.1),
                 connect_to: HashSet::new(),
             });
@@ -235,14 +235,34 @@ fn populate_levels(overworld: &mut Vec<WorldNode>, rng: &mut SmallRng){
     }
 }
 
+//bosses fight at the hardest level difficulty of the act
+pub fn boss_difficulty(overworld: &Vec<WorldNode>, difficulty_curve: &DifficultyCurve, act: u32) -> i32 {
+    overworld.iter()
+        .filter_map(|node| {if let WorldNodeType::Level(n) = node.node_type { Some(n) } else { None }})
+        .max()
+        .unwrap_or(difficulty_curve.level_difficulty(act, 1))
+}
+
 pub fn remove_special_levels(overworld: &mut Vec<WorldNode>) {
     for node in overworld.iter_mut(){
-        if node.node_type != WorldNodeType::Boss || node.node_type != WorldNodeType::Start {
+        if node.node_type != WorldNodeType::Boss && node.node_type != WorldNodeType::Start {
             node.node_type = WorldNodeType::Level(1);
         }
     }
 }
 
+//stores turn into levels as hard as the closest level before them
+pub fn remove_stores(overworld: &mut Vec<WorldNode>) {
+    for i in 0..overworld.len() {
+        if overworld[i].node_type == WorldNodeType::Store {
+            let difficulty = overworld[..i].iter().rev()
+                .find_map(|node| {if let WorldNodeType::Level(n) = node.node_type { Some(n) } else { None }})
+                .unwrap_or(0);
+            overworld[i].node_type = WorldNodeType::Level(difficulty);
+        }
+    }
+}
+
 pub fn increase_difficulty(overworld: &mut Vec<WorldNode>) {
     for node in overworld.iter_mut(){
         if let WorldNodeType::Level(n) = node.node_type {
@@ -253,7 +273,7 @@ pub fn increase_difficulty(overworld: &mut Vec<WorldNode>) {
 
 pub fn all_events(overworld: &mut Vec<WorldNode>, rng: &mut SmallRng){
     for node in overworld.iter_mut(){
-        if node.node_type != WorldNodeType::Boss || node.node_type != WorldNodeType::Start {
+        if node.node_type != WorldNodeType::Boss && node.node_type != WorldNodeType::Start {
             let event_id = (rng.gen::<f64>() * 4 as f64) as u32;
             node.node_type = WorldNodeType::Event(event_id);
         }
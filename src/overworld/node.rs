@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use sdl2::rect::Point;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 pub enum WorldNodeType {
     Start,
     Boss,
@@ -2,7 +2,7 @@
 use rand::Rng;
 use sdl2::rect::Rect;
 
-use crate::game_logic::characters::player::Player;
+use crate::game_logic::{characters::player::Player, coop::players_center};
 
 
 const AMPLITUDE: i8 = 6;
@@ -12,6 +12,8 @@ pub struct Camera {
     pub rect: Rect,
     
     pub is_shaking: bool,
+    //screen shake can be turned off in the settings
+    pub shake_enabled: bool,
 
     pub shake_duration: i32,
     pub shake_frequency: i32,
@@ -32,6 +34,7 @@ impl Camera {
         Self {
             rect: Rect::new(x, y, width, height),
             is_shaking: false,
+            shake_enabled: true,
             
             shake_duration: duration,
             shake_frequency: frequency,
@@ -49,6 +52,9 @@ impl Camera {
     }
 
     pub fn shake(&mut self) {
+        if !self.shake_enabled {
+            return;
+        }
 
         let mut rng = rand::thread_rng();
 
@@ -69,8 +75,9 @@ impl Camera {
         self.is_shaking = true
     }
 
-    pub fn update(&mut self, level_size: i32, player: &Player, dt: f64) {
-        let mut proposed_x = player.position.x as i32 - self.rect.width() as i32 / 2;
+    //frames the middle point of everyone still standing, players are kept inside the screen so they cant drift apart
+    pub fn update(&mut self, level_size: i32, players: &[&Player], dt: f64) {
+        let mut proposed_x = players_center(players).x as i32 - self.rect.width() as i32 / 2;
         
         if proposed_x < 0 {
             proposed_x = 0;
@@ -75,15 +75,17 @@ pub fn render(
 ) -> Result<(), String> {
     
     
-    let screen_res = canvas.output_size()?;
+    let screen_res = canvas.logical_size();
 
     render_level(canvas, &game.levels, level_assets, &game.camera);
 
-    render_shadow(level_assets,
-        canvas,
-        Point::new(game.player.position.x as i32 , game.player.controller.ground_height as i32),  
-        screen_res,
-        &game.camera);
+    for player in game.players() {
+        render_shadow(level_assets,
+            canvas,
+            Point::new(player.position.x as i32 , player.controller.ground_height as i32),  
+            screen_res,
+            &game.camera);
+    }
 
     let shadow_positions = get_ground_pos_enemies(&mut game.enemies);
 
@@ -98,6 +100,9 @@ pub fn render(
     let mut entities_to_render = crate::ecs_system::enemy_systems::render_enemies(&mut game.enemies, enemy_assets);
     let data_to_render = game.player.render(p1_assets);
     entities_to_render.push(data_to_render);
+    if let Some(player2) = game.player2.as_mut() {
+        entities_to_render.push(player2.render(p1_assets));
+    }
 
     let mut items_on_ground = game.items_on_ground
         .iter_mut()
@@ -121,16 +126,22 @@ pub fn render(
         let screen_rect =
             world_to_screen(projectile.sprite, Point::new(projectile.position.x as i32, projectile.position.y as i32) , screen_res, Some(&game.camera));
 
-        let assets = p1_assets;
-        canvas.copy_ex(
-            projectile.render(assets),
-            projectile.sprite,
-            screen_rect,
-            0.0,
-            None,
-            projectile.flipped,
-            false,
-        )?;
+        //enemy projectiles have no sprites yet, they are drawn as a block
+        if projectile.animator.current_animation.is_none() {
+            canvas.set_draw_color(Color::RGB(255, 200, 60));
+            canvas.fill_rect(screen_rect)?;
+        } else {
+            let assets = p1_assets;
+            canvas.copy_ex(
+                projectile.render(assets),
+                projectile.sprite,
+                screen_rect,
+                0.0,
+                None,
+                projectile.flipped,
+                false,
+            )?;
+        }
 
         if debug {
             debug_rect(canvas, screen_rect.center(), screen_rect);
@@ -145,6 +156,9 @@ pub fn render(
             render_colliders(canvas, screen_res, &game.camera, &mut game.projectiles[i].colliders);
         }
         render_colliders(canvas, screen_res, &game.camera, &mut game.player.collision_manager.colliders);
+        if let Some(player2) = game.player2.as_mut() {
+            render_colliders(canvas, screen_res, &game.camera, &mut player2.collision_manager.colliders);
+        }
     }
 
     Ok(())
@@ -241,7 +255,7 @@ fn render_vfx(
             let screen_rect = world_to_screen(rect_size, vfx_position, screen_res, Some(camera));
 
             let (_frame, texture_id) = &level_assets
-                .hit_effect_animations
+                .vfx.animations
                 .get_mut(&vfx.name)
                 .unwrap()
                 .sprites[vfx.sprite_shown as usize];
@@ -18,7 +18,7 @@ pub fn render_event(canvas: &mut WindowCanvas,
     selected_option: usize,
     ) {
 
-    let (w, h) = canvas.output_size().unwrap();
+    let (w, h) = canvas.logical_size();
 
     let event_canvas = Rect::new(350,50, 600, 700);
     canvas.copy(&assets.backgrounds[0], Rect::new(0,0, 500, 700), event_canvas).unwrap();
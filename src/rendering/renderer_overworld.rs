@@ -11,7 +11,7 @@ pub fn render_overworld(canvas: &mut WindowCanvas,
     nodes: &Vec<WorldNode>, 
     map_area: &Rect) {
 
-    let (w, h) = canvas.output_size().unwrap();
+    let (w, h) = canvas.logical_size();
 
     for node in nodes.iter() {
         for &connections in node.connect_to.iter() {
@@ -1,11 +1,15 @@
-use sdl2::{pixels::Color, rect::{Point, Rect}, render::{Texture, TextureCreator, WindowCanvas}, ttf::Font, video::WindowContext};
+use sdl2::{pixels::Color, rect::{Point, Rect}, render::{Texture, TextureCreator, TextureQuery, WindowCanvas}, ttf::Font, video::WindowContext};
 
-use crate::{asset_management::asset_holders::{ItemAssets, UIAssets}, game_logic::{characters::player::Player, combo_string::manage_combo_resources::Combo}, ui::{ingame::{popup_ui::PopUp, segmented_bar_ui::SegmentedBar, wrapping_list_ui::WrappingList}, menus::button_ui::Button}};
+use crate::{asset_management::asset_holders::{ItemAssets, UIAssets}, game_logic::{characters::player::{EntityState, Player}, combo_string::manage_combo_resources::Combo, movement_controller::MAX_GUARD}, ui::{ingame::{boss_bar_ui::BossBar, popup_ui::PopUp, segmented_bar_ui::SegmentedBar, wrapping_list_ui::WrappingList}, menus::button_ui::Button}};
 
 pub fn active_item_ui() -> Rect{
     Rect::new(10, 0 , 64, 64)
 }
 
+pub fn p2_active_item_ui(screen_res: (u32, u32)) -> Rect{
+    Rect::new(screen_res.0 as i32 - 74, 0 , 64, 64)
+}
+
 pub fn text_gen<'a>(value: String, texture_creator: &'a TextureCreator<WindowContext>, font: &Font, color: Color) -> Texture<'a> {
     let title_surface = font
                 .render(&value)
@@ -88,6 +92,58 @@ pub fn render_combo(canvas: &mut WindowCanvas, combo: &Combo) {
     }
 }
 
+fn render_player_bars(canvas: &mut WindowCanvas, player: &Player, hp_bars: &SegmentedBar, energy_bars: &SegmentedBar, item_list: &WrappingList, item_assets: &ItemAssets) {
+    if hp_bars.curr_value > 0 {
+        canvas.set_draw_color(hp_bars.color.unwrap());
+        for hp_rect in hp_bars.render() {
+            canvas.draw_rect(hp_rect).unwrap();
+            canvas.fill_rect(hp_rect).unwrap();
+        }
+    }
+
+    //guard sits in the gap between the hp and energy bars, red while broken
+    let guard_width = (hp_bars.dimensions.0 as f64 * player.controller.guard / MAX_GUARD) as u32;
+    if guard_width > 0 || player.controller.state == EntityState::GuardBroken {
+        let guard_color = if player.controller.state == EntityState::GuardBroken { Color::RGB(200, 40, 40) } else { Color::RGB(240, 220, 100) };
+        canvas.set_draw_color(guard_color);
+        let guard_rect = Rect::new(hp_bars.point.x, hp_bars.point.y + hp_bars.dimensions.1 as i32 + 4, guard_width.max(1), 6);
+        canvas.fill_rect(guard_rect).unwrap();
+    }
+
+    if energy_bars.curr_value > 0 {
+        canvas.set_draw_color(energy_bars.color.unwrap());
+        for energy_rect in energy_bars.render() {
+            canvas.draw_rect(energy_rect).unwrap();
+            canvas.fill_rect(energy_rect).unwrap();
+        }
+    }
+
+    let item_list = item_list.render();
+    if player.items.len() > 0 {
+        for i in 0..player.items.len() {
+            let src_rect = item_assets.src_rects.get(&player.items[i]).unwrap();
+            let dst_rect = item_list[i];
+            canvas.copy(&item_assets.spritesheet, src_rect.clone(), dst_rect).unwrap();
+        }
+    }
+}
+
+//player 2 hud mirrors player 1 on the right side of the screen
+pub fn render_p2_ui(canvas: &mut WindowCanvas, 
+    player: &Player,
+    hp_bars: &SegmentedBar,
+    energy_bars: &SegmentedBar,
+    item_list: &WrappingList,
+    item_assets: &ItemAssets,
+    ) {
+    if let Some(active_item) = &player.active_item_key {
+        let src_rect = item_assets.src_rects.get(active_item).unwrap();
+        canvas.copy(&item_assets.spritesheet, src_rect.clone(), p2_active_item_ui(canvas.logical_size())).unwrap();
+    }
+
+    render_player_bars(canvas, player, hp_bars, energy_bars, item_list, item_assets);
+}
+
 pub fn render_ui<'a>(canvas: &mut WindowCanvas, 
     player: &Player,
     hp_bars: &SegmentedBar,
@@ -103,30 +159,7 @@ pub fn render_ui<'a>(canvas: &mut WindowCanvas,
             canvas.copy(&item_assets.spritesheet, src_rect.clone(), active_item_ui()).unwrap();
         }
     
-        if hp_bars.curr_value > 0 {
-            canvas.set_draw_color(hp_bars.color.unwrap());
-            for hp_rect in hp_bars.render() {
-                canvas.draw_rect(hp_rect).unwrap();
-                canvas.fill_rect(hp_rect).unwrap();
-            }
-        }
-
-        if energy_bars.curr_value > 0 {
-            canvas.set_draw_color(energy_bars.color.unwrap());
-            for energy_rect in energy_bars.render() {
-                canvas.draw_rect(energy_rect).unwrap();
-                canvas.fill_rect(energy_rect).unwrap();
-            }
-        }
-    
-        let item_list = item_list.render();
-        if player.items.len() > 0 {
-            for i in 0..player.items.len() {
-                let src_rect = item_assets.src_rects.get(&player.items[i]).unwrap();
-                let dst_rect = item_list[i];
-                canvas.copy(&item_assets.spritesheet, src_rect.clone(), dst_rect).unwrap();
-            }
-        }
+        render_player_bars(canvas, player, hp_bars, energy_bars, item_list, item_assets);
 
         //canvas.copy(texture, None, Rect)
 
@@ -147,4 +180,59 @@ pub fn render_ui<'a>(canvas: &mut WindowCanvas,
         }
 
         
-    }
\ No newline at end of file
+    }
+
+pub fn render_boss_bar(canvas: &mut WindowCanvas, boss_bar: &BossBar) {
+    for bar in boss_bar.bars.iter() {
+        if bar.curr_value > 0 {
+            canvas.set_draw_color(bar.color.unwrap());
+            for hp_rect in bar.render() {
+                canvas.draw_rect(hp_rect).unwrap();
+                canvas.fill_rect(hp_rect).unwrap();
+            }
+        }
+    }
+}
+
+//drawn over the frozen match, the menu on the left and the run so far on the right
+pub fn render_pause_ui<'a>(canvas: &mut WindowCanvas,
+    assets: &UIAssets,
+    title: &Texture<'a>,
+    rows: &Vec<Texture<'a>>,
+    selected_row: usize,
+    stats: &Vec<Texture<'a>>,
+    player: &Player,
+    item_list: &WrappingList,
+    item_assets: &ItemAssets,
+    ) {
+    let (w, h) = canvas.logical_size();
+    canvas.set_draw_color(Color::RGBA(0, 0, 0, 180));
+    canvas.fill_rect(Rect::new(0, 0, w, h)).unwrap();
+
+    let TextureQuery { width, height, .. } = title.query();
+    canvas.copy(title, None, Rect::new(150, 100, width, height)).unwrap();
+
+    let mut offset = 80;
+    for (i, row) in rows.iter().enumerate() {
+        let TextureQuery { width, height, .. } = row.query();
+        let target = Rect::new(150, 100 + offset, width, height);
+        if i == selected_row {
+            render_cursor_ui(canvas, assets, &target);
+        }
+        canvas.copy(row, None, target).unwrap();
+        offset += 40;
+    }
+
+    let mut offset = 0;
+    for stat in stats.iter() {
+        let TextureQuery { width, height, .. } = stat.query();
+        canvas.copy(stat, None, Rect::new(w as i32 / 2, 100 + offset, width, height)).unwrap();
+        offset += height as i32 + 4;
+    }
+
+    let item_rects = item_list.render();
+    for i in 0..player.items.len() {
+        let src_rect = item_assets.src_rects.get(&player.items[i]).unwrap();
+        canvas.copy(&item_assets.spritesheet, src_rect.clone(), item_rects[i]).unwrap();
+    }
+}
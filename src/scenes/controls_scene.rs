@@ -0,0 +1,154 @@
+use sdl2::{EventPump, event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+
+use crate::{GameStateData, Transition, asset_management::controls::{ControlsProfile, save_controls}, engine_traits::scene::Scene, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{render_cursor_ui, text_gen}, ui::menus::controls_menu_ui::{ControlsMenu, profile_name}};
+
+pub struct ControlsScene {
+    menu: ControlsMenu,
+}
+
+impl ControlsScene {
+    pub fn new() -> Self {
+        Self {
+            menu: ControlsMenu::new(),
+        }
+    }
+
+    fn gen_texts<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>, input_devices: &InputDevices) -> (Texture<'a>, Vec<Texture<'a>>, Option<Texture<'a>>) {
+        let font = game_state_data.general_assets.fonts.get("main_menu_font").unwrap();
+        let title = text_gen(
+            format!("< {} >", profile_name(self.menu.profile)),
+            texture_creator,
+            font,
+            Color::WHITE);
+
+        let rows = self.menu.rows(&input_devices.controls).into_iter()
+            .map(|row| {text_gen(row, texture_creator, font, Color::WHITE)})
+            .collect::<Vec<Texture>>();
+
+        let message = self.menu.message.as_ref().map(|message| {
+            text_gen(message.clone(), texture_creator, game_state_data.general_assets.fonts.get("basic_font").unwrap(), Color::YELLOW)
+        });
+
+        (title, rows, message)
+    }
+}
+
+impl Scene for ControlsScene {
+    fn run(
+        &mut self,
+        game_state_data: &mut GameStateData,
+        texture_creator: &TextureCreator<WindowContext>,
+        event_pump: &mut EventPump,
+        input_devices: &mut InputDevices,
+        canvas: &mut Canvas<Window>,
+    ) -> Transition {
+        let screen_res = canvas.logical_size();
+        let (mut title, mut rows, mut message) = self.gen_texts(game_state_data, texture_creator, input_devices);
+
+        loop {
+            let mut changed = false;
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit { .. } => return Transition::Quit,
+                    _ => {}
+                };
+                input::controller_handler::handle_new_controller(
+                    &input_devices.controller,
+                    &input_devices.joystick,
+                    &event,
+                    &mut input_devices.joys,
+                );
+
+                if self.menu.capturing {
+                    //capture the raw key or button so anything can be bound, even keys that are not mapped yet
+                    //on release, so the release doesnt go through the menu with the new bindings
+                    let captured = match event {
+                        Event::KeyUp { keycode: Some(Keycode::Escape), .. } => {
+                            self.menu.cancel_capture();
+                            changed = true;
+                            None
+                        },
+                        Event::KeyUp { keycode: Some(keycode), .. } => Some((ControlsProfile::Keyboard, keycode.to_string())),
+                        Event::JoyButtonUp { button_idx, .. } => Some((ControlsProfile::Gamepad, button_idx.to_string())),
+                        _ => None,
+                    };
+
+                    if let Some((profile, key)) = captured {
+                        if self.menu.capture(&mut input_devices.controls, profile, key) {
+                            save_controls(&input_devices.controls);
+                            changed = true;
+                        }
+                    }
+                    continue;
+                }
+
+                //arrows, enter and escape always work so broken bindings can still be fixed
+                let raw_input = match event {
+                    Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Left), .. } => Some((TranslatedInput::Horizontal(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Right), .. } => Some((TranslatedInput::Horizontal(1), true)),
+                    Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                    Event::KeyUp { keycode: Some(Keycode::Escape), .. } => return Transition::Pop,
+                    _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                        .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                };
+
+                if let Some((translated_input, is_pressed)) = raw_input {
+                    match translated_input {
+                        TranslatedInput::Vertical(direction) if is_pressed => {
+                            self.menu.move_selection(-direction);
+                            changed = true;
+                        },
+                        TranslatedInput::Horizontal(direction) if is_pressed && direction != 0 => {
+                            self.menu.switch_profile();
+                            changed = true;
+                        },
+                        TranslatedInput::Punch if !is_pressed => {
+                            if self.menu.is_back_selected() {
+                                return Transition::Pop;
+                            }
+                            self.menu.start_capture();
+                            changed = true;
+                        },
+                        TranslatedInput::Kick if !is_pressed => {
+                            return Transition::Pop;
+                        },
+                        _ => {}
+                    }
+                }
+            }
+
+            if changed {
+                let texts = self.gen_texts(game_state_data, texture_creator, input_devices);
+                title = texts.0;
+                rows = texts.1;
+                message = texts.2;
+            }
+
+            canvas.set_draw_color(Color::RGB(0, 85, 200));
+            canvas.clear();
+
+            let TextureQuery { width, height, .. } = title.query();
+            canvas.copy(&title, None, Rect::new((screen_res.0 as i32 - width as i32) / 2, 60, width, height)).unwrap();
+
+            let mut offset = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let TextureQuery { width, height, .. } = row.query();
+                let target = Rect::new(150, 140 + offset, width, height);
+                if i == self.menu.selected_row {
+                    render_cursor_ui(canvas, &game_state_data.ui_assets, &target);
+                }
+                canvas.copy(row, None, target).unwrap();
+                offset += 40;
+            }
+
+            if let Some(message) = &message {
+                let TextureQuery { width, height, .. } = message.query();
+                canvas.copy(message, None, Rect::new(150, 160 + offset, width, height)).unwrap();
+            }
+
+            canvas.present();
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::{cmp, time::Instant};
 
 use sdl2::{EventPump, event::Event, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator}, video::{Window, WindowContext}};
 
-use crate::{GameStateData, Transition, challenges::challenge::Challenge, engine_traits::scene::Scene, game_logic::{events::EventType, factories::{item_factory::{load_item_assets, load_items}, world_factory::load_overworld_assets}, items::Item, store::{StoreUI, get_store_item_list}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_event::render_event, renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::{render_ui, text_gen, text_gen_wrapped}}, ui::{ingame::popup_ui::{PopUp, new_item_popup, popup_fade}, menus::button_ui::Button}};
+use crate::{GameStateData, Transition, challenges::challenge::Challenge, engine_traits::scene::Scene, game_logic::{coop::give_item, events::EventType, factories::{item_factory::{load_item_assets, load_items}, world_factory::load_overworld_assets}, items::Item, store::{StoreUI, get_store_item_list}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_event::render_event, renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::{render_ui, text_gen, text_gen_wrapped}}, ui::{ingame::popup_ui::{PopUp, new_item_popup, popup_fade}, menus::button_ui::Button}};
 
 use super::match_scene::{MAX_UPDATES_AVOID_SPIRAL_OF_DEATH, MatchScene};
 
@@ -115,12 +115,11 @@ impl EventScene {
         game_state_data.player.as_mut().unwrap().currency -= event.rewards.as_ref().unwrap().currency as u32;
 
         for item_id in event.rewards.as_ref().unwrap().item_ids.iter() {
-            let mut item = game_state_data.items.get(item_id).unwrap().clone();
+            let item = game_state_data.items.get(item_id).unwrap().clone();
 
-            game_state_data.player.as_mut().unwrap().equip_item(
-                &mut item, 
-                &game_state_data.effects, 
-                &mut game_state_data.energy_bar.as_mut().unwrap())
+            give_item(game_state_data.settings.coop_loot, game_state_data.player.as_mut().unwrap(), game_state_data.player2.as_mut(), &item, &game_state_data.effects);
+            let player = game_state_data.player.as_ref().unwrap();
+            game_state_data.energy_bar.as_mut().unwrap().update_width(player.active_item_cost as i32, player.currency as i32);
         }
 
         self.status = EventStatus::Succeeded;
@@ -227,6 +226,10 @@ impl EventScene {
 
 impl<'a> Scene for EventScene {
     
+    fn music_track(&self) -> Option<&str> {
+        Some("event")
+    }
+
     fn run(
         &mut self,
         game_state_data: &mut GameStateData,
@@ -236,7 +239,7 @@ impl<'a> Scene for EventScene {
         canvas: &mut Canvas<Window>,
     ) -> Transition {
 
-        let (w, h) = canvas.output_size().unwrap();
+        let (w, h) = canvas.logical_size();
 
         if self.status != EventStatus::Start {
             if game_state_data.event_success {
@@ -257,12 +260,11 @@ impl<'a> Scene for EventScene {
         if self.status == EventStatus::Succeeded {
             
             for item_id in event.rewards.as_ref().unwrap().item_ids.iter() {
-                let mut item = game_state_data.items.get(item_id).unwrap().clone();
+                let item = game_state_data.items.get(item_id).unwrap().clone();
 
-                game_state_data.player.as_mut().unwrap().equip_item(
-                    &mut item, 
-                    &game_state_data.effects, 
-                    &mut game_state_data.energy_bar.as_mut().unwrap());
+                give_item(game_state_data.settings.coop_loot, game_state_data.player.as_mut().unwrap(), game_state_data.player2.as_mut(), &item, &game_state_data.effects);
+                let player = game_state_data.player.as_ref().unwrap();
+                game_state_data.energy_bar.as_mut().unwrap().update_width(player.active_item_cost as i32, player.currency as i32);
 
                 popup_content = Some(crate::ui::ingame::popup_ui::render_popup(texture_creator, 
                     &item.name, 
@@ -1,5 +1,6 @@
 use parry2d::na::Vector2;
-use sdl2::{pixels::Color, rect::Rect, render::Texture};
+use rand::{Rng, SeedableRng, prelude::SmallRng};
+use sdl2::{pixels::Color, rect::{Point, Rect}, render::Texture};
 use std::{collections::HashMap, rc::Rc, time::Instant};
 
 use sdl2::{
@@ -10,9 +11,8 @@ use sdl2::{
     EventPump,
 };
 
-use crate::{Transition, challenges::{ChallengeManager, challenge::Challenge}, collision::collision_detection::{calculate_hits}, debug_console::console::Console, ecs_system::enemy_systems::{update_animations_enemies, update_colliders_enemies, update_events, update_movement_enemies}, enemy_behaviour::update_behaviour_enemies, engine_types::{collider::ColliderType, simple_animator::init_combo_animation}, game_logic::{characters::{player::{EntityState}, player_input::{apply_input_state, process_input}}, combo_string::{ComboCounter, manage_combo_resources::{Combo, update_and_manage}}, effects::hash_effects, factories::{character_factory::load_character_anim_data, enemy_factory::load_enemy_ryu_assets, item_factory::load_items}, game::Game, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement}}, input::input_devices::InputDevices, level_generation::generate::{generate_levels, get_levels}, rendering::renderer_ui::{render_combo, render_ui, text_gen}, ui::ingame::popup_ui::{PopUp, new_item_popup, popup_fade}};
+use crate::{Transition, asset_management::{controls::config_file_path, profile::save_profile, rng_tables::LootTable, run_save::{PlayerSave, delete_run, restore_player}}, challenges::{ChallengeManager, challenge::Challenge}, debug_console::console::{Console, DebugAction, apply_debug_action}, ecs_system::enemy_components::AIType, engine_types::{simple_animator::init_combo_animation, sprite_data::SpriteData}, game_logic::{boss::{Boss, load_boss_arena}, rng_streams::RngStream, run_stats::{RunOutcome, RunStats}, meta_progression::milestone_counts, characters::player::{EntityState, Player}, coop::{PLAYER_2_ID, is_join_request, player_for_device, settle_currency}, combo_string::{ComboCounter, manage_combo_resources::{Combo, update_and_manage}}, effects::hash_effects, factories::{character_factory::{character_definition, load_character, load_character_anim_data}, enemy_factory::{DEFAULT_ENEMY_ARCHETYPE, load_enemy_ryu_assets}, item_factory::load_items}, game::{Game, StepContext}, inputs::{game_inputs::GameAction, input_cycle::AllInputManagement, input_recorder::InputRecording}}, input::{input_devices::InputDevices, translated_inputs::TranslatedInput}, level_generation::{Level, generate::{generate_levels, get_levels}}, rendering::renderer_ui::{render_boss_bar, render_combo, render_p2_ui, render_pause_ui, render_ui, text_gen}, scenes::{controls_scene::ControlsScene, settings_scene::SettingsScene}, telemetry::log_event, ui::{ingame::{boss_bar_ui::BossBar, popup_ui::{PopUp, new_item_popup, popup_fade}, wrapping_list_ui::WrappingList}, menus::pause_menu_ui::{PauseAction, PauseMenu, stats_rows}}};
 use crate::{
-    collision::collision_attack_resolution::detect_hit,
     engine_traits::scene::Scene,
     input::{self},
     rendering::{self, camera::Camera},
@@ -24,12 +24,25 @@ pub const MAX_UPDATES_AVOID_SPIRAL_OF_DEATH: i32 = 4;
 //Screen dimension constants
 const SCREEN_WIDTH: u32 = 1280;
 const SCREEN_HEIGHT: u32 = 720;
+//music intensity tops out with this many enemies alive
+const CROWDED_FIGHT_ENEMIES: f64 = 6.0;
+const LOW_HP_RATIO: f64 = 0.25;
+
+const BOSS_SPAWN_DISTANCE: f64 = 600.0;
+//seconds the death animation gets before the run summary
+const DEATH_SCREEN_DELAY: f64 = 2.0;
+
+pub const LAST_MATCH_REPLAY_FILE: &str = "last_match.replay";
 
 pub struct MatchScene {
     pub character: String,
     pub level_ids: Option<Vec<i32>>,
     pub challenges: Option<Vec<(i32, Challenge)>>,
+    pub boss: Option<String>,
     p1_inputs: AllInputManagement,
+    p2_inputs: AllInputManagement,
+    recording: Option<InputRecording>,
+    replay: Option<InputRecording>,
 }
 
 impl MatchScene {
@@ -42,12 +55,188 @@ impl MatchScene {
             character,
             level_ids,
             challenges,
+            boss: None,
+            p1_inputs: AllInputManagement::new(),
+            p2_inputs: AllInputManagement::new(),
+            recording: None,
+            replay: None,
+        }
+    }
+
+    pub fn new_boss(character: String, boss: String) -> Self {
+        Self {
+            character,
+            level_ids: None,
+            challenges: None,
+            boss: Some(boss),
+            p1_inputs: AllInputManagement::new(),
+            p2_inputs: AllInputManagement::new(),
+            recording: None,
+            replay: None,
+        }
+    }
+
+    pub fn new_replay(replay: InputRecording) -> Self {
+        Self {
+            character: replay.player.character_name.clone(),
+            level_ids: None,
+            challenges: None,
+            boss: replay.boss.clone(),
             p1_inputs: AllInputManagement::new(),
+            p2_inputs: AllInputManagement::new(),
+            recording: None,
+            replay: Some(replay),
+        }
+    }
+
+    //recorded so the replay spawns and hurts the same way, a replay itself ignores the debug keys
+    fn debug_action(&mut self, game: &mut Game, action: DebugAction, game_state_data: &GameStateData) {
+        if self.replay.is_some() {
+            return;
+        }
+        apply_debug_action(game, &action, &game_state_data.items, &game_state_data.enemy_archetypes, &game_state_data.enemy_animations);
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record_debug_action(game.current_frame + 1, action);
+        }
+    }
+
+    //keys pressed or released while the pause menu was up would stay stuck, everything starts released again
+    fn reset_inputs(&mut self, game: &Game) {
+        if self.replay.is_some() {
+            return;
+        }
+        self.p1_inputs = AllInputManagement::new();
+        self.p2_inputs = AllInputManagement::new();
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record_input_reset(game.current_frame + 1);
+        }
+    }
+
+    //a replay is not the run, abandoning one only stops it
+    fn abandon_run(&mut self, game: &Game, game_state_data: &mut GameStateData, run_state: Option<RunStateBackup>) -> Transition {
+        if run_state.is_some() {
+            return self.end_match(game, game_state_data, run_state);
+        }
+        self.recording = None;
+        record_milestone_progress(game_state_data, &game.stats);
+        game_state_data.run_stats.add(&game.stats);
+        delete_run();
+        game_state_data.run_outcome = Some(RunOutcome::Abandoned);
+        Transition::Pop
+    }
+
+    //everyone is dead, the overworld below swaps itself for the run summary
+    fn end_run(&mut self, game: &Game, game_state_data: &mut GameStateData, run_state: Option<RunStateBackup>) -> Transition {
+        if run_state.is_some() {
+            return self.end_match(game, game_state_data, run_state);
         }
+        let transition = self.end_match(game, game_state_data, run_state);
+        delete_run();
+        game_state_data.run_outcome = Some(RunOutcome::Died);
+        transition
+    }
+
+    fn end_match(&mut self, game: &Game, game_state_data: &mut GameStateData, run_state: Option<RunStateBackup>) -> Transition {
+        if let Some(run_state) = run_state {
+            //replays dont change the run, put back everything that was swapped
+            game_state_data.player = run_state.player;
+            game_state_data.seed = run_state.seed;
+            game_state_data.curr_act = run_state.curr_act;
+            game_state_data.curr_level = run_state.curr_level;
+            game_state_data.curr_difficulty = run_state.curr_difficulty;
+            game_state_data.ascension = run_state.ascension;
+            game_state_data.run_modifiers = game_state_data.ascension_config.modifiers(run_state.ascension);
+            game_state_data.general_assets.loot_tables = run_state.loot_tables;
+            let player = game_state_data.player.as_ref().unwrap();
+            game_state_data.hp_bar.as_mut().unwrap().update(player.character.hp, player.hp.0);
+        } else {
+            record_milestone_progress(game_state_data, &game.stats);
+            game_state_data.player = Some(game.player.clone());
+            game_state_data.player2 = game.player2.clone();
+            if let (Some(p1), Some(p2)) = (game_state_data.player.as_mut(), game_state_data.player2.as_mut()) {
+                settle_currency(game_state_data.settings.coop_loot, p1, p2);
+            }
+            if let Some(boss) = &game.boss {
+                game_state_data.boss_defeated = boss.defeated;
+            }
+            game_state_data.run_stats.add(&game.stats);
+            if let Some(recording) = self.recording.take() {
+                recording.save(&config_file_path(LAST_MATCH_REPLAY_FILE));
+            }
+        }
+        Transition::Pop
+    }
+}
+
+//how heated the fight is for the music, 0 when nothing is going on
+fn combat_intensity(game: &Game, combo: &Combo) -> f64 {
+    let enemies_alive = game.enemies.ai_type_components.iter()
+        .zip(game.enemies.health_components.iter())
+        .filter(|(ai, health)| {
+            matches!(ai, Some(AIType::Enemy)) && health.as_ref().map(|health| {health.0 > 0}).unwrap_or(false)
+        })
+        .count();
+    let crowd = (enemies_alive as f64 / CROWDED_FIGHT_ENEMIES).min(1f64);
+
+    let in_danger = game.players().iter().any(|player| {
+        (player.hp.0 as f64) < player.character.hp as f64 * LOW_HP_RATIO
+    });
+    let danger = if in_danger { 1f64 } else { 0f64 };
+
+    let combo_heat = combo.combo_level as f64 / (combo.combo_limit.len() - 1).max(1) as f64;
+
+    (crowd * 0.5 + danger * 0.4 + combo_heat * 0.3).min(1f64)
+}
+
+fn record_milestone_progress(game_state_data: &mut GameStateData, stats: &RunStats) {
+    for (counter, amount) in milestone_counts(stats) {
+        game_state_data.profile.add_progress(counter, amount);
     }
+    save_profile(&game_state_data.profile);
+}
+
+//bigger icons than the hud, laid out under the stats on the right half of the pause screen
+fn pause_item_list_init(screen_res: (u32, u32), player: &Player) -> WrappingList {
+    WrappingList::new(
+        Point::new(screen_res.0 as i32 / 2, 480),
+        screen_res.0 - 100,
+        player.items.iter()
+            .map(|_item| {Rect::new(0,0,48,48)})
+            .collect::<Vec<Rect>>(),
+        10
+    )
+}
+
+fn gen_pause_texts<'a>(pause_menu: &PauseMenu, player: &Player, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> (Texture<'a>, Vec<Texture<'a>>, Vec<Texture<'a>>) {
+    let font = game_state_data.general_assets.fonts.get("main_menu_font").unwrap();
+    let basic_font = game_state_data.general_assets.fonts.get("basic_font").unwrap();
+
+    let title = text_gen(pause_menu.title(), texture_creator, font, Color::WHITE);
+    let rows = pause_menu.rows().into_iter()
+        .map(|row| {text_gen(row, texture_creator, font, Color::WHITE)})
+        .collect::<Vec<Texture>>();
+    let stats = stats_rows(&player.character).into_iter()
+        .map(|row| {text_gen(row, texture_creator, basic_font, Color::WHITE)})
+        .collect::<Vec<Texture>>();
+
+    (title, rows, stats)
+}
+
+struct RunStateBackup {
+    player: Option<Player>,
+    seed: Option<u64>,
+    curr_act: u32,
+    curr_level: i32,
+    curr_difficulty: i32,
+    ascension: u32,
+    loot_tables: HashMap<String, LootTable>,
 }
 
 impl Scene for MatchScene {
+    fn music_track(&self) -> Option<&str> {
+        if self.boss.is_some() { Some("boss") } else { Some("level") }
+    }
+
     fn run(
         &mut self,
         game_state_data: &mut GameStateData,
@@ -63,16 +252,67 @@ impl Scene for MatchScene {
             command: "".to_string(),
         };
 
-        let (p1_assets, p1_data) = load_character_anim_data(texture_creator, &self.character);
+        let (p1_assets, p1_data) = load_character_anim_data(texture_creator, character_definition(&mut game_state_data.character_definitions, &self.character));
 
         let mut enemy_assets = HashMap::new();
         enemy_assets.insert("ryu", load_enemy_ryu_assets(texture_creator));
+        let enemy_sprite_data = enemy_assets.iter()
+            .map(|(&name, assets)| {(name.to_string(), assets.texture_data.clone())})
+            .collect::<HashMap<String, HashMap<String, SpriteData>>>();
+
+        let run_state = if let Some(replay) = &self.replay {
+            let run_state = RunStateBackup {
+                player: game_state_data.player.take(),
+                seed: game_state_data.seed,
+                curr_act: game_state_data.curr_act,
+                curr_level: game_state_data.curr_level,
+                curr_difficulty: game_state_data.curr_difficulty,
+                ascension: game_state_data.ascension,
+                loot_tables: game_state_data.general_assets.loot_tables.clone(),
+            };
+            game_state_data.seed = Some(replay.seed);
+            game_state_data.curr_act = replay.curr_act;
+            game_state_data.curr_level = replay.curr_level;
+            game_state_data.curr_difficulty = replay.difficulty;
+            game_state_data.ascension = replay.ascension;
+            game_state_data.run_modifiers = game_state_data.ascension_config.modifiers(replay.ascension);
+            game_state_data.general_assets.loot_tables = replay.loot_tables.clone();
+            game_state_data.player = Some(restore_player(&replay.player, game_state_data));
+            game_state_data.run_modifiers.register_hooks(game_state_data.player.as_mut().unwrap());
+            Some(run_state)
+        } else {
+            None
+        };
 
-        let levels = if let Some(levels) = &self.level_ids {
+        let level_seed = if let Some(replay) = &self.replay {
+            replay.level_seed
+        } else {
+            game_state_data.rng_streams.next(RngStream::LevelLayout).gen::<u64>()
+        };
+
+        let boss_definition = self.boss.as_ref().and_then(|boss| {game_state_data.bosses.get(boss)}).cloned();
+
+        let levels = if let Some(boss_definition) = &boss_definition {
+            vec![Level::new(&load_boss_arena(boss_definition, &game_state_data.level_assets.level_rooms), 0)]
+        } else if let Some(levels) = &self.level_ids {
             get_levels(&game_state_data.level_assets.level_rooms, levels)
         } else {
-            generate_levels(&game_state_data.level_assets.level_rooms, &mut game_state_data.map_rng.as_mut().unwrap())
+            generate_levels(&game_state_data.level_assets.level_rooms, &mut SmallRng::seed_from_u64(level_seed))
         };
+
+        if self.replay.is_none() {
+            self.recording = Some(InputRecording::new(
+                game_state_data.seed.unwrap(),
+                level_seed,
+                game_state_data.curr_act,
+                game_state_data.curr_level,
+                game_state_data.curr_difficulty,
+                game_state_data.ascension,
+                PlayerSave::new(game_state_data.player.as_ref().unwrap()),
+                game_state_data.general_assets.loot_tables.clone(),
+                self.boss.clone(),
+            ));
+        }
         
         let mut challenges = ChallengeManager::new();
         if let Some(challenge_ids) = &self.challenges {
@@ -88,33 +328,47 @@ impl Scene for MatchScene {
         );
 
         let mut game = Game::new(game_state_data.player.as_ref().unwrap().clone(), camera, levels);
-
-        game.player.position = Vector2::new(150.0f64, 150f64);
-
-        game.player
-            .animator
-            .play(game.player.controller.animations.animations.get("idle").unwrap().clone(), 1.0,false);
-
-        game.player.collision_manager.init_colliders(&game.player.animator);
-
-        let player = &mut game.player;
-        let mut start_level_events = player.events.on_start_level.clone();
-        for event_on_lvl_start in start_level_events.iter_mut() {
-            (event_on_lvl_start.0)(player, &mut game.enemies, -1, &mut event_on_lvl_start.1);
+        game.camera.shake_enabled = game_state_data.settings.screen_shake;
+        //replays only ever have player 1
+        if self.replay.is_none() {
+            game.player2 = game_state_data.player2.clone();
+        }
+        if game.player2.is_some() {
+            //the recording has no room for a second input stream, co-op matches are not saved
+            self.recording = None;
+        }
+        for player in game.players_mut() {
+            player.seed_combat_rng(game_state_data.seed.unwrap(), game_state_data.curr_act, game_state_data.curr_level);
         }
-        player.events.on_start_level = start_level_events;
+
+        game.start_level();
 
         let mut combo = Combo::new();
 
-        let screen_res = canvas.output_size().unwrap();
+        let screen_res = canvas.logical_size();
+
+        let mut boss_bar = None;
+        if let Some(boss_definition) = &boss_definition {
+            game.boss = Boss::spawn(
+                boss_definition, 
+                game_state_data.difficulty_curve.tier(game_state_data.curr_difficulty),
+                &game_state_data.enemy_archetypes, 
+                &game_state_data.enemy_animations, 
+                &mut game.enemies, 
+                game.player.position + Vector2::new(BOSS_SPAWN_DISTANCE, 0f64)
+            );
+            if let Some(boss) = &game.boss {
+                game_state_data.run_modifiers.apply_to_boss(boss, &mut game.enemies);
+                boss_bar = Some(BossBar::new(screen_res, &boss.definition));
+            }
+        }
 
         let mut popup_item = new_item_popup(screen_res);
         let mut popup_content: Option<Vec<Texture>> = None;
 
         let mut item_list = crate::item_list_init(&game_state_data);
+        let mut p2_item_list = game.player2.as_ref().map(|player2| {crate::p2_item_list_init(screen_res, player2)});
         
-        let mut hit_stop = 0;
-
         let mut previous_time = Instant::now();
         let logic_timestep: f64 = 0.016;
         let mut logic_time_accumulated: f64 = 0.0;
@@ -122,6 +376,13 @@ impl Scene for MatchScene {
 
         let mut debug_pause = false;
 
+        let mut death_time = 0.0;
+
+        let mut paused = false;
+        let mut pause_menu = PauseMenu::new();
+        let mut pause_texts = None;
+        let mut pause_item_list = pause_item_list_init(screen_res, &game.player);
+
         loop {
             let current_time = Instant::now();
             let delta_time = current_time.duration_since(previous_time);
@@ -130,12 +391,55 @@ impl Scene for MatchScene {
 
             previous_time = current_time;
 
-            if !debug_pause {
+            if !debug_pause && !paused {
                 logic_time_accumulated += delta_time_as_nanos;
             }
 
             // Handle events
+            let mut pause_action = None;
             'kb_events: for event in event_pump.poll_iter() {
+                if paused {
+                    input::controller_handler::handle_new_controller(
+                        &input_devices.controller,
+                        &input_devices.joystick,
+                        &event,
+                        &mut input_devices.joys,
+                    );
+
+                    let raw_input = match event {
+                        Event::Quit { .. } => return Transition::Quit,
+                        Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                        Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                        Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                        Event::KeyDown { keycode: Some(Keycode::Escape), .. } => Some((TranslatedInput::Kick, false)),
+                        _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                            .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                    };
+
+                    if let Some((translated_input, is_pressed)) = raw_input {
+                        match translated_input {
+                            TranslatedInput::Vertical(direction) if is_pressed => {
+                                pause_menu.move_selection(-direction);
+                                game_state_data.audio.play("scroll_level");
+                            },
+                            TranslatedInput::Punch if !is_pressed => {
+                                pause_action = pause_menu.confirm();
+                                game_state_data.audio.play("select_level");
+                            },
+                            TranslatedInput::Kick if !is_pressed => {
+                                pause_action = pause_menu.back();
+                            },
+                            _ => {}
+                        }
+                        pause_texts = None;
+                    }
+
+                    if pause_action.is_some() {
+                        break 'kb_events;
+                    }
+                    continue;
+                }
+
                 match event {
                     Event::Quit { .. } => return Transition::Quit,
                     Event::KeyDown {
@@ -143,13 +447,17 @@ impl Scene for MatchScene {
                         ..
                     } => {
                         if input == Keycode::Escape {
-                            game_state_data.player = Some(game.player.clone());
-                            return Transition::Pop;
+                            paused = true;
+                            logic_time_accumulated = 0.0;
+                            pause_menu.reset();
+                            pause_texts = None;
+                            pause_item_list = pause_item_list_init(screen_res, &game.player);
+                            continue;
                         }
                         
 
                         if input == Keycode::L {
-                            game.enemies.add_enemy(game.player.position + Vector2::new(500f64, 0f64), Rc::clone(game_state_data.enemy_animations.get("ryu").unwrap()));
+                            self.debug_action(&mut game, DebugAction::SpawnEnemy { archetype: DEFAULT_ENEMY_ARCHETYPE.to_string(), offset: 500f64 }, game_state_data);
                         }
                         if input == Keycode::P {
                             debug_pause ^= true;
@@ -157,19 +465,21 @@ impl Scene for MatchScene {
                             update_counter = 0;
                         }
                         if input == Keycode::M {
-                            game.player.currency += 1;
+                            self.debug_action(&mut game, DebugAction::AddCurrency(1), game_state_data);
                         }
                         if input == Keycode::Right && debug_pause {
                             logic_time_accumulated += logic_timestep;
                         }
                         if input == Keycode::C { //hurt self
-                            game.player.hp.0 -= 10;
+                            self.debug_action(&mut game, DebugAction::HurtPlayer(10), game_state_data);
                         }
 
                         if input == Keycode::Backslash {
                             console.toggle();
                         } else if input == Keycode::Return{
-                            console.run(&mut game, &game_state_data.items, game_state_data)
+                            if let Some(action) = console.run() {
+                                self.debug_action(&mut game, action, game_state_data);
+                            }
                         } else {
                             console.add(input);
                         }
@@ -190,15 +500,43 @@ impl Scene for MatchScene {
 
                 let raw_input = input::input_handler::rcv_input(&event, &input_devices.controls);
 
-                if let Some((_controller_id, translated_input, _is_pressed)) = raw_input {
+                if self.replay.is_some() {
+                    continue;
+                }
+
+                if game.player2.is_none() && is_join_request(&input_devices.joys, &event) {
+                    let mut player2 = load_character(
+                        character_definition(&mut game_state_data.character_definitions, &self.character),
+                        Point::new(200, 50),
+                        PLAYER_2_ID,
+                        Rc::clone(game_state_data.enemy_animations.get("player").unwrap())
+                    );
+                    player2.seed_combat_rng(game_state_data.seed.unwrap(), game_state_data.curr_act, game_state_data.curr_level);
+                    game_state_data.run_modifiers.register_hooks(&mut player2);
+                    game_state_data.p2_hp_bar = Some(crate::p2_hp_bar_init(screen_res, player2.character.hp, player2.hp.0));
+                    game_state_data.p2_energy_bar = Some(crate::p2_energy_bar_init(screen_res, 0, 0));
+                    p2_item_list = Some(crate::p2_item_list_init(screen_res, &player2));
+                    game.join_player2(player2);
+                    self.recording = None;
+                    continue;
+                }
 
-                    let inputs_for_current_frame = if let Some(&last_action) = self.p1_inputs.action_history.back() {last_action} else {0};
+                if let Some((controller_id, translated_input, _is_pressed)) = raw_input {
+                    let player_index = player_for_device(&input_devices.joys, controller_id, game.player2.is_some());
+
+                    let (inputs, facing_dir) = if player_index == 1 {
+                        (&mut self.p2_inputs, game.player2.as_ref().unwrap().controller.facing_dir)
+                    } else {
+                        (&mut self.p1_inputs, game.player.controller.facing_dir)
+                    };
+
+                    let inputs_for_current_frame = if let Some(&last_action) = inputs.action_history.back() {last_action} else {0};
                     let recent_input_as_game_action = GameAction::from_translated_input(
                         translated_input,
                         inputs_for_current_frame,
-                        game.player.controller.facing_dir,
+                        facing_dir,
                     );
-                    self.p1_inputs.input_new_frame ^= recent_input_as_game_action.unwrap() as i32;
+                    inputs.input_new_frame ^= recent_input_as_game_action.unwrap() as i32;
                 }
             }
 
@@ -209,130 +547,72 @@ impl Scene for MatchScene {
                     logic_time_accumulated = 0.0;
                 }
 
-                if hit_stop > 0 {
-                    hit_stop -= 1;
-                    logic_time_accumulated -= logic_timestep;
-                    break;
-                }
-
-                game.current_frame += 1;
-
-                if game.player.controller.state != EntityState::Dead
-                {
-                    if self.p1_inputs.input_new_frame != 0 {
-                        process_input(&mut game.player, &p1_data, &mut self.p1_inputs, &mut game.enemies);
+                if let Some(replay) = &self.replay {
+                    if replay.is_finished(game.current_frame + 1) {
+                        return self.end_match(&game, game_state_data, run_state);
+                    }
+                    if game.hit_stop == 0 {
+                        if replay.resets_inputs_at(game.current_frame + 1) {
+                            self.p1_inputs = AllInputManagement::new();
+                        }
+                        self.p1_inputs.input_new_frame = replay.input_at(game.current_frame + 1);
+                        for action in replay.debug_actions_at(game.current_frame + 1) {
+                            apply_debug_action(&mut game, action, &game_state_data.items, &game_state_data.enemy_archetypes, &game_state_data.enemy_animations);
+                        }
                     }
-
-                    apply_input_state(&mut game.player, &mut self.p1_inputs, &p1_data, &mut game.enemies);
                 }
-
-                self.p1_inputs.update_inputs_reset_timer();
-                self.p1_inputs.update_input_buffer_reset_time();
-
-                game.player.character_width = match game
-                    .player.collision_manager.colliders
-                    .iter()
-                    .filter(|&c| c.collider_type == ColliderType::Pushbox)
-                    .last()
-                {
-                    Some(point) => point.aabb.half_extents().x as f64,
-                    None => { game.player.character_width },
+                let frame_input = self.p1_inputs.input_new_frame;
+
+                let mut step_context = StepContext {
+                    p1_data: &p1_data,
+                    p1_sprite_data: &p1_assets.texture_data,
+                    enemy_sprite_data: &enemy_sprite_data,
+                    vfx: &game_state_data.level_assets.vfx,
+                    items: &game_state_data.items,
+                    effects: &game_state_data.effects,
+                    enemy_animations: &game_state_data.enemy_animations,
+                    enemy_archetypes: &game_state_data.enemy_archetypes,
+                    loot_tables: &mut game_state_data.general_assets.loot_tables,
+                    seed: game_state_data.seed.unwrap(),
+                    curr_act: game_state_data.curr_act,
+                    curr_level: game_state_data.curr_level,
+                    difficulty: game_state_data.difficulty_curve.tier(game_state_data.curr_difficulty),
+                    modifiers: &game_state_data.run_modifiers,
+                    friendly_fire: game_state_data.settings.friendly_fire,
+                    coop_loot: game_state_data.settings.coop_loot,
+                    challenges: &mut challenges,
+                    combo_counter: &mut combo.combo_counter,
                 };
+                let step_result = game.step(&mut self.p1_inputs, Some(&mut self.p2_inputs), &mut step_context, logic_timestep);
 
-                let mut player_update_events = game.player.events.on_update.clone();
-                for event in player_update_events.iter_mut() {
-                    event.0(&mut game.player, &mut game.enemies, -1, &mut event.1, logic_timestep);
+                for sound in game.sounds.drain(..) {
+                    game_state_data.audio.play(&sound);
                 }
-                game.player.events.on_update = player_update_events;
-
-                game.player.animator.update();
-                game.player.state_update(&p1_assets.texture_data);
-                game.player.update(
-                    &mut game.camera,
-                    logic_timestep,
-                    game.is_finished,
-                    game.player.character_width as i32,
-                    &game_state_data.general_assets
-                );
-                game.player.state_update(&p1_assets.texture_data);
-               
-                let player_position = if !game.player.controller.is_airborne {
-                    game.player.position
-                } else {
-                    Vector2::new(game.player.position.x, game.player.controller.ground_height as f64)
-                };
-                
-                let mut items_spawned = game.items_on_ground.clone();
-                items_spawned.iter_mut().for_each(|item_ground| {
-
-                    if (player_position - item_ground.position).magnitude() <= 50.0 {
-                        game.player.equip_item(&mut item_ground.item, &game_state_data.effects, &mut game_state_data.energy_bar.as_mut().unwrap());
-                        
-                        popup_content = Some(crate::ui::ingame::popup_ui::render_popup(texture_creator, 
-                            &item_ground.item.name, 
-                            &item_ground.item.description, 
-                            &game_state_data.general_assets.fonts.get("basic_font").unwrap(), 
-                            &mut popup_item));
-                        
-                        if let Some(chance_mod) = &item_ground.item.chance_mod {
-                            (chance_mod.modifier)(chance_mod.item_ids.clone(), chance_mod.chance_mod, &game.player.character, &mut game_state_data.general_assets.loot_tables);
-                        } else {
-                            for (_key, val) in game_state_data.general_assets.loot_tables.iter_mut() {
-                                val.items.retain(|x| x.item_id as i32 != item_ground.item.id);
-                                val.acc = val.items.iter().map(|i|{i.rarity}).sum();
-                            }
-                        }
-                    }
-
-                });
-
-                game.items_on_ground.retain(|item_ground| {
-                    return (player_position - item_ground.position).magnitude() >= 50.0;
-                });
-                   
-                update_animations_enemies(&mut game.enemies);
-                update_behaviour_enemies(&mut game.enemies, &mut game.player, logic_timestep);
-                update_movement_enemies(&mut game.enemies, &mut game.camera, logic_timestep, &game_state_data.general_assets);
-                update_events(&mut game.enemies, &mut game.player, logic_timestep);
-                update_colliders_enemies(&mut game.enemies, &enemy_assets);
-
-                let start_p1_pos = game.player.position.clone();
-
-                //TODO probably doesnt need to run unless there is a collision
-                game.projectiles.retain(|p| p.is_alive);
-                
-                for i in 0..game.projectiles.len(){
-                    if game.projectiles[i].player_owner == 2 {
-                        match detect_hit(&game.projectiles[i].colliders, &game.player.collision_manager.colliders) {
-                            Some((_point, _name)) => {
-                                break;
-                            }
-                            None => {}
-                        }
+                //replays would log the same run twice
+                for event in game.telemetry.drain(..) {
+                    if self.replay.is_none() {
+                        log_event(game_state_data, Some(game.current_frame), event);
                     }
                 }
-                
-                if game.player.position != start_p1_pos {
-                    Game::update_player_colliders_position_only(&mut game.player, start_p1_pos);
-                }
+                game_state_data.audio.music.set_intensity(combat_intensity(&game, &combo));
+                game_state_data.audio.music.update(logic_timestep);
 
-                calculate_hits( &mut game.player, 
-                    &mut game.enemies, 
-                    &mut game.hit_vfx, 
-                    &mut hit_stop, 
-                    logic_timestep, 
-                    &game_state_data.general_assets, 
-                    &game_state_data.level_assets, 
-                    &p1_data,
-                    &mut combo.combo_counter,
-                    &mut challenges,
-                    &mut game.camera);
+                if !step_result.advanced {
+                    logic_time_accumulated -= logic_timestep;
+                    break;
+                }
 
-                game.fx(&game_state_data.level_assets);
-                game.update_vfx(&game_state_data.level_assets);
+                if let Some(recording) = self.recording.as_mut() {
+                    recording.record(game.current_frame, frame_input);
+                }
 
-                game.camera.update(game.max_level_width(), &game.player, logic_timestep);
-                game.check_level_tags_and_apply(game_state_data);
+                for item in step_result.picked_items.iter() {
+                    popup_content = Some(crate::ui::ingame::popup_ui::render_popup(texture_creator, 
+                        &item.name, 
+                        &item.description, 
+                        &game_state_data.general_assets.fonts.get("basic_font").unwrap(), 
+                        &mut popup_item));
+                }
 
                 game_state_data.hp_bar.as_mut().unwrap().update(game.player.character.hp, game.player.hp.0);
                 if game.player.items.len() != item_list.rects.len() {
@@ -343,37 +623,73 @@ impl Scene for MatchScene {
                 }
 
                 game_state_data.energy_bar.as_mut().unwrap().update_width(game.player.active_item_cost as i32, game.player.currency as i32);
+                if let Some(player2) = &game.player2 {
+                    if game_state_data.p2_hp_bar.is_none() {
+                        game_state_data.p2_hp_bar = Some(crate::p2_hp_bar_init(screen_res, player2.character.hp, player2.hp.0));
+                        game_state_data.p2_energy_bar = Some(crate::p2_energy_bar_init(screen_res, player2.active_item_cost as i32, player2.currency as i32));
+                    }
+                    game_state_data.p2_hp_bar.as_mut().unwrap().update(player2.character.hp, player2.hp.0);
+                    game_state_data.p2_energy_bar.as_mut().unwrap().update_width(player2.active_item_cost as i32, player2.currency as i32);
+                    if let Some(p2_item_list) = p2_item_list.as_mut() {
+                        if player2.items.len() != p2_item_list.rects.len() {
+                            p2_item_list.update(player2.items.iter()
+                                .map(|_| {Rect::new(0,0,32,32)})
+                                .collect::<Vec<Rect>>()
+                            );
+                        }
+                    }
+                }
+                if let (Some(boss_bar), Some(boss)) = (boss_bar.as_mut(), &game.boss) {
+                    boss_bar.update(boss.hp(&game.enemies));
+                }
                 popup_fade(&mut popup_item, &mut popup_content, logic_timestep);
 
                 update_and_manage(logic_timestep, &mut combo, &texture_creator,&game_state_data);
 
-                game.is_finished = game.check_finished_level();
+                if step_result.left_level {
+                    game_state_data.event_success = challenges.get_result();
+                    return self.end_match(&game, game_state_data, run_state);
+                }
 
-                if game.player.controller.state != EntityState::Dead {
-                    if (game.player.position.x  as i32 - game.player.character_width as i32) < game.camera.rect.x() {
-                        game.player.position.x = (game.camera.rect.x() + game.player.character_width as i32) as f64;
+                if game.players().iter().all(|player| {player.controller.state == EntityState::Dead}) {
+                    death_time += logic_timestep;
+                    if death_time >= DEATH_SCREEN_DELAY {
+                        return self.end_run(&game, game_state_data, run_state);
                     }
-                
-      
-                        if (game.player.position.x as i32 + game.player.character_width as i32) > (game.camera.rect.x() + game.camera.rect.width() as i32) {
-                            if !game.is_finished {
-                                game.player.position.x = (game.camera.rect.x() + game.camera.rect.width() as i32 - game.player.character_width as i32) as f64;
-                            } else {
-                                if (game.player.position.x as i32 - (game.player.character_width as f32 * 1.5) as i32 ) > (game.camera.rect.x() + game.camera.rect.width() as i32) {
-                                    game_state_data.event_success = challenges.get_result();
-                                    game_state_data.player = Some(game.player.clone());
-                                    return Transition::Pop;
-                                }
-                            }
-                        }
-                    
                 }
 
                 logic_time_accumulated -= logic_timestep;
             }
 
+            //settings and controls run on top of the frozen match and come back here when closed
+            match pause_action {
+                Some(PauseAction::Resume) => {
+                    paused = false;
+                    self.reset_inputs(&game);
+                },
+                Some(PauseAction::Settings) => {
+                    if let Transition::Quit = SettingsScene::new().run(game_state_data, texture_creator, event_pump, input_devices, canvas) {
+                        return Transition::Quit;
+                    }
+                    game.camera.shake_enabled = game_state_data.settings.screen_shake;
+                    self.reset_inputs(&game);
+                },
+                Some(PauseAction::Controls) => {
+                    if let Transition::Quit = ControlsScene::new().run(game_state_data, texture_creator, event_pump, input_devices, canvas) {
+                        return Transition::Quit;
+                    }
+                    self.reset_inputs(&game);
+                },
+                Some(PauseAction::AbandonRun) => return self.abandon_run(&game, game_state_data, run_state),
+                None => {},
+            }
+
+            if paused && pause_texts.is_none() {
+                pause_texts = Some(gen_pause_texts(&pause_menu, &game.player, game_state_data, texture_creator));
+            }
+
             // Render
-            if update_counter > 0 {
+            if update_counter > 0 || paused {
                 canvas.clear();
                 
                 rendering::renderer::render(
@@ -384,12 +700,16 @@ impl Scene for MatchScene {
                     &mut game_state_data.level_assets,
                     &game_state_data.item_assets,
                    // &end_game_match,
-                    false,
+                    game_state_data.settings.show_hitboxes,
                 )
                 .unwrap();
 
                 render_combo(canvas, &combo);
 
+                if let Some(boss_bar) = &boss_bar {
+                    render_boss_bar(canvas, boss_bar);
+                }
+
                 render_ui(canvas, 
                     &game.player,
                     &game_state_data.hp_bar.as_ref().unwrap(),
@@ -399,7 +719,24 @@ impl Scene for MatchScene {
                     Some(&popup_item),
                     &popup_content
                     );
+
+                if let (Some(player2), Some(p2_hp_bar), Some(p2_energy_bar), Some(p2_item_list)) = (&game.player2, &game_state_data.p2_hp_bar, &game_state_data.p2_energy_bar, &p2_item_list) {
+                    render_p2_ui(canvas, player2, p2_hp_bar, p2_energy_bar, p2_item_list, &game_state_data.item_assets);
+                }
                 
+                if let (true, Some((title, rows, stats))) = (paused, &pause_texts) {
+                    render_pause_ui(canvas,
+                        &game_state_data.ui_assets,
+                        title,
+                        rows,
+                        pause_menu.selected_row,
+                        stats,
+                        &game.player,
+                        &pause_item_list,
+                        &game_state_data.item_assets,
+                    );
+                }
+
                 console.render(texture_creator, canvas, &game_state_data.general_assets.fonts.get("basic_font").unwrap());
                 
                 canvas.present(); 
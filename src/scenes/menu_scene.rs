@@ -1,7 +1,6 @@
 use std::rc::Rc;
 
-use crate::{GameStateData, Transition, asset_management::asset_loader::asset_loader::load_texture, game_logic::{factories::{character_factory::{load_character, load_character_animations}, enemy_factory::load_enemy_ryu_animations}, items::loot_table_effects::stop_attack_spawn}, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{text_gen, text_gen_wrapped}};
-use rand::{Rng, SeedableRng, prelude::SmallRng};
+use crate::{GameStateData, Transition, asset_management::{asset_loader::asset_loader::load_texture, rng_tables::load_item_table, run_save::{load_run, restore_player, restore_player2}}, game_logic::{rng_streams::{RngStream, RngStreams}, run_seed::SeedMode, run_stats::RunStats, meta_progression::{apply_item_unlocks, equip_loadout}, factories::{character_factory::{character_definition, load_character, load_character_animations}, enemy_factory::load_enemy_ryu_animations}, items::loot_table_effects::stop_attack_spawn}, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{text_gen, text_gen_wrapped}, telemetry::{TelemetryEvent, log_event, new_run_id}};
 use sdl2::{EventPump, event::Event, pixels::Color, rect::{Point, Rect}, render::{Canvas, TextureCreator, TextureQuery}, surface::Surface, ttf::Font, video::{Window, WindowContext}};
 
 //character select
@@ -9,7 +8,7 @@ use crate::engine_traits::scene::Scene;
 
 
 use crate::rendering::renderer_ui::render_cursor_ui;
-use super::overworld_scene::OverworldScene;
+use super::{controls_scene::ControlsScene, new_run_scene::NewRunScene, overworld_scene::OverworldScene, settings_scene::SettingsScene, unlocks_scene::UnlocksScene};
 
 macro_rules! rect(
     ($x:expr, $y:expr, $w:expr, $h:expr) => (
@@ -41,15 +40,7 @@ impl MenuScene {
     }
 }
 
-fn start_game(screen_res: (u32, u32), game_state_data: & mut GameStateData) -> Transition {
-    game_state_data.enemy_animations.insert("player".to_string(), Rc::new(load_character_animations("foxgirl")));
-    game_state_data.player = Some(load_character(
-        "foxgirl",
-        Point::new(200, 50),
-        1,
-        Rc::clone(game_state_data.enemy_animations.get("player").unwrap())
-    ));
-
+fn init_run_state(screen_res: (u32, u32), game_state_data: & mut GameStateData) {
     let hp_bars = crate::hp_bar_init(
         screen_res,
         game_state_data.player.as_ref().unwrap().character.hp,
@@ -65,22 +56,125 @@ fn start_game(screen_res: (u32, u32), game_state_data: & mut GameStateData) -> T
     game_state_data.hp_bar = Some(hp_bars);
     game_state_data.energy_bar = Some(energy_bars);
 
+    game_state_data.player2 = None;
+    game_state_data.p2_hp_bar = None;
+    game_state_data.run_outcome = None;
+    game_state_data.run_stats = RunStats::default();
+    game_state_data.p2_energy_bar = None;
+
     game_state_data.enemy_animations.insert("ryu".to_string(), Rc::new(load_enemy_ryu_animations()));
+}
+
+pub fn start_game(screen_res: (u32, u32), game_state_data: & mut GameStateData, seed: u64, seed_mode: SeedMode) -> Transition {
+    let definition = character_definition(&mut game_state_data.character_definitions, "foxgirl");
+    game_state_data.enemy_animations.insert("player".to_string(), Rc::new(load_character_animations(definition)));
+    game_state_data.player = Some(load_character(
+        definition,
+        Point::new(200, 50),
+        1,
+        Rc::clone(game_state_data.enemy_animations.get("player").unwrap())
+    ));
+
+    init_run_state(screen_res, game_state_data);
+
+    game_state_data.ascension = game_state_data.profile.ascension.min(game_state_data.ascension_config.max_level());
+    game_state_data.run_modifiers = game_state_data.ascension_config.modifiers(game_state_data.ascension);
+
+    equip_loadout(&game_state_data.meta, &game_state_data.profile, game_state_data.player.as_mut().unwrap(), &game_state_data.items, &game_state_data.effects);
+    game_state_data.run_modifiers.register_hooks(game_state_data.player.as_mut().unwrap());
 
     let mut overworld = OverworldScene::new();
     
-    let seed = 123234223481761;
-    
     game_state_data.seed = Some(seed);
-    game_state_data.map_rng = Some(SmallRng::seed_from_u64(seed));
-    overworld.init(screen_res, false, game_state_data.map_rng.as_mut().unwrap());
+    game_state_data.seed_mode = seed_mode;
+    game_state_data.rng_streams = RngStreams::new(seed);
+    game_state_data.curr_act = 0;
+    overworld.init(screen_res, false, &game_state_data.difficulty_curve, game_state_data.curr_act, &game_state_data.run_modifiers, &mut game_state_data.rng_streams.next(RngStream::Map));
     
+    //the tables are used up during a run, every new one starts from the full pool and the current unlocks
+    game_state_data.general_assets.loot_tables = load_item_table("assets/items/loot_tables.json".to_string());
+    apply_item_unlocks(&game_state_data.meta, &game_state_data.profile, &mut game_state_data.general_assets.loot_tables);
     stop_attack_spawn(vec![4,5,6,7,8,9,10,11,12,15], 0, &game_state_data.player.as_ref().unwrap().character, &mut game_state_data.general_assets.loot_tables);
+    game_state_data.run_modifiers.apply_to_loot_tables(&game_state_data.items, &game_state_data.player.as_ref().unwrap().character, &mut game_state_data.general_assets.loot_tables);
+
+    game_state_data.telemetry.run_id = new_run_id();
+    let loadout = game_state_data.profile.loadout.clone();
+    let ascension = game_state_data.ascension;
+    log_event(game_state_data, None, TelemetryEvent::RunStarted { seed, loadout, ascension });
 
     return Transition::Change(Box::new(overworld));
 } 
 
+fn continue_game(screen_res: (u32, u32), game_state_data: & mut GameStateData) -> Option<Transition> {
+    let run_save = load_run()?;
+
+    let definition = character_definition(&mut game_state_data.character_definitions, &run_save.player.character_name);
+    game_state_data.enemy_animations.insert("player".to_string(), Rc::new(load_character_animations(definition)));
+    game_state_data.player = Some(load_character(
+        definition,
+        Point::new(200, 50),
+        1,
+        Rc::clone(game_state_data.enemy_animations.get("player").unwrap())
+    ));
+
+    init_run_state(screen_res, game_state_data);
+
+    game_state_data.ascension = run_save.ascension;
+    game_state_data.run_modifiers = game_state_data.ascension_config.modifiers(run_save.ascension);
+
+    game_state_data.player = Some(restore_player(&run_save.player, game_state_data));
+    game_state_data.run_modifiers.register_hooks(game_state_data.player.as_mut().unwrap());
+
+    if let Some(player2_save) = &run_save.player2 {
+        let mut player2 = restore_player2(player2_save, game_state_data);
+        game_state_data.run_modifiers.register_hooks(&mut player2);
+        game_state_data.player2 = Some(player2);
+    }
+
+    game_state_data.seed = Some(run_save.seed);
+    game_state_data.seed_mode = run_save.seed_mode;
+    game_state_data.rng_streams = RngStreams::restore(run_save.seed, run_save.rng_draws.clone());
+    game_state_data.curr_level = run_save.curr_level;
+    game_state_data.curr_act = run_save.curr_act;
+    game_state_data.general_assets.loot_tables = run_save.loot_tables.clone();
+    game_state_data.run_stats = run_save.run_stats.clone();
+    game_state_data.telemetry.run_id = run_save.run_id;
+
+    let overworld = OverworldScene::from_save(screen_res, &run_save.overworld);
+
+    //kept above the menu like a new run, so leaving the run pops back to it
+    Some(Transition::Push(Box::new(overworld)))
+}
+
+const CONTINUE_BTN: i32 = 1;
+
+impl MenuScene {
+    //continue is skipped over when there is no run to continue
+    fn move_selection(&mut self, dir: i32, n_buttons: i32, has_run_save: bool) {
+        self.selected_btn = ((self.selected_btn + dir) % n_buttons + n_buttons) % n_buttons;
+        if self.selected_btn == CONTINUE_BTN && !has_run_save {
+            self.selected_btn = ((self.selected_btn + dir) % n_buttons + n_buttons) % n_buttons;
+        }
+    }
+
+    fn confirm(&mut self, screen_res: (u32, u32), game_state_data: & mut GameStateData) -> Option<Transition> {
+        match self.selected_btn {
+            0 => Some(Transition::Push(Box::new(NewRunScene::new()))),
+            CONTINUE_BTN => continue_game(screen_res, game_state_data),
+            2 => Some(Transition::Push(Box::new(ControlsScene::new()))),
+            3 => Some(Transition::Push(Box::new(SettingsScene::new()))),
+            4 => Some(Transition::Push(Box::new(UnlocksScene::new()))),
+            6 => Some(Transition::Quit),
+            _ => None
+        }
+    }
+}
+
 impl Scene for MenuScene {
+    fn music_track(&self) -> Option<&str> {
+        Some("menu")
+    }
+
     fn run(
         &mut self,
         game_state_data: & mut GameStateData,
@@ -92,7 +186,7 @@ impl Scene for MenuScene {
         let mut offset = 0;
         let mut text_buttons = Vec::new();
 
-        let screen_res = canvas.output_size().unwrap();
+        let screen_res = canvas.logical_size();
 
         let main_menu_background = if self.curr_screen == MenuScreen::MainMenu {
             Some(load_texture(texture_creator, "assets/stages/main_menu.png"))
@@ -100,14 +194,19 @@ impl Scene for MenuScene {
             None
         };
 
-        let btn_text = vec!["New Game", "Load Game", "Settings", "Credits", "Quit"];
+        let btn_text = vec!["New Game", "Continue", "Controls", "Settings", "Unlocks", "Credits", "Quit"];
+        let has_run_save = load_run().is_some();
+        if self.selected_btn == CONTINUE_BTN && !has_run_save {
+            self.selected_btn = 0;
+        }
 
-        for text in btn_text.iter() {
+        for (i, text) in btn_text.iter().enumerate() {
+            let color = if i as i32 == CONTINUE_BTN && !has_run_save { Color::GREY } else { Color::WHITE };
             let btn_text_texture = text_gen(
                 text.to_string(),
                 texture_creator, 
                 game_state_data.general_assets.fonts.get("main_menu_font").unwrap(), 
-                Color::WHITE);
+                color);
 
 
             let TextureQuery { width, height, .. } = btn_text_texture.query();
@@ -138,7 +237,7 @@ impl Scene for MenuScene {
 
                 if let Some(mouse_pos) = mouse_pos {
                     for (i, (_, btn_rect)) in text_buttons.iter().enumerate(){
-                        if input::handle_mouse_click::check_mouse_within_rect(mouse_pos, &btn_rect) {
+                        if input::handle_mouse_click::check_mouse_within_rect(mouse_pos, &btn_rect) && (i as i32 != CONTINUE_BTN || has_run_save) {
                             self.selected_btn = i as i32;
                         }
                     }
@@ -146,7 +245,9 @@ impl Scene for MenuScene {
 
                 if let Some((is_click_down, _)) = mouse_click {
                     if !is_click_down {
-                        return start_game(screen_res, game_state_data);
+                        if let Some(transition) = self.confirm(screen_res, game_state_data) {
+                            return transition;
+                        }
                     }
                 }
 
@@ -154,18 +255,15 @@ impl Scene for MenuScene {
                     let (_id, translated_input, is_pressed) = raw_input.unwrap();
                    
                     if translated_input == TranslatedInput::Vertical(1) && is_pressed {
-                        self.selected_btn = (((self.selected_btn - 1)
-                            % btn_text.len() as i32)
-                            + btn_text.len() as i32)
-                            % btn_text.len() as i32;
+                        self.move_selection(-1, btn_text.len() as i32, has_run_save);
                     } else if translated_input == TranslatedInput::Vertical(-1) && is_pressed  {
-                        self.selected_btn = (self.selected_btn + 1) % btn_text.len() as i32;
+                        self.move_selection(1, btn_text.len() as i32, has_run_save);
                     
                     } else if translated_input == TranslatedInput::Punch {
                         //confirm
                         if !is_pressed {
-                            if self.selected_btn == 0 {
-                                return start_game(screen_res, game_state_data);
+                            if let Some(transition) = self.confirm(screen_res, game_state_data) {
+                                return transition;
                             }
                         }
                     } else if translated_input == TranslatedInput::Kick {
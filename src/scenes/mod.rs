@@ -2,4 +2,9 @@ pub mod match_scene;
 pub mod menu_scene;
 pub mod overworld_scene;
 pub mod store_scene;
-pub mod event_scene;
\ No newline at end of file
+pub mod event_scene;
+pub mod controls_scene;
+pub mod new_run_scene;
+pub mod settings_scene;
+pub mod run_summary_scene;
+pub mod unlocks_scene;
@@ -0,0 +1,217 @@
+use sdl2::{EventPump, event::Event, keyboard::{Keycode, Mod}, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+
+use crate::{GameStateData, Transition, asset_management::profile::save_profile, engine_traits::scene::Scene, game_logic::run_seed::{MAX_SEED_TEXT_LENGTH, SeedMode, daily_seed, date_string, parse_seed, random_seed, today}, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{render_cursor_ui, text_gen}};
+
+use super::menu_scene::start_game;
+
+const RANDOM_ROW: usize = 0;
+const DAILY_ROW: usize = 1;
+const CUSTOM_ROW: usize = 2;
+const ASCENSION_ROW: usize = 3;
+const BACK_ROW: usize = 4;
+const N_ROWS: usize = 5;
+
+//picks the seed and the ascension of a new run before going to the overworld
+pub struct NewRunScene {
+    selected_row: usize,
+    seed_text: String,
+}
+
+impl NewRunScene {
+    pub fn new() -> Self {
+        Self {
+            selected_row: RANDOM_ROW,
+            seed_text: String::new(),
+        }
+    }
+
+    fn add_text(&mut self, text: &str) {
+        for c in text.trim().chars() {
+            if self.seed_text.len() < MAX_SEED_TEXT_LENGTH && c.is_ascii_alphanumeric() {
+                self.seed_text.push(c);
+            }
+        }
+    }
+
+    fn change_ascension(&mut self, game_state_data: &mut GameStateData, direction: i32) {
+        let max_level = game_state_data.ascension_config.max_level() as i32;
+        let level = (game_state_data.profile.ascension as i32 + direction).max(0).min(max_level);
+        if level as u32 != game_state_data.profile.ascension {
+            game_state_data.profile.ascension = level as u32;
+            save_profile(&game_state_data.profile);
+        }
+    }
+
+    fn confirm(&mut self, screen_res: (u32, u32), game_state_data: &mut GameStateData) -> Option<Transition> {
+        match self.selected_row {
+            RANDOM_ROW => Some(start_game(screen_res, game_state_data, random_seed(), SeedMode::Random)),
+            DAILY_ROW => {
+                let day = today();
+                Some(start_game(screen_res, game_state_data, daily_seed(day), SeedMode::Daily(day)))
+            },
+            CUSTOM_ROW => {
+                let seed = parse_seed(&self.seed_text)?;
+                Some(start_game(screen_res, game_state_data, seed, SeedMode::Custom))
+            },
+            BACK_ROW => Some(Transition::Pop),
+            _ => None,
+        }
+    }
+
+    fn gen_rows<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> Vec<Texture<'a>> {
+        let cursor = if self.selected_row == CUSTOM_ROW { "_" } else { "" };
+        let rows = vec![
+            "Random seed".to_string(),
+            format!("Daily seed {}", date_string(today())),
+            format!("Custom seed: {}{}", self.seed_text, cursor),
+            format!("< Ascension {} >", game_state_data.profile.ascension),
+            "Back".to_string(),
+        ];
+
+        rows.into_iter()
+            .map(|row| {text_gen(row, texture_creator, game_state_data.general_assets.fonts.get("main_menu_font").unwrap(), Color::WHITE)})
+            .collect::<Vec<Texture>>()
+    }
+
+    //one line per tier the current ascension turns on
+    fn gen_modifier_rows<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> Vec<Texture<'a>> {
+        game_state_data.ascension_config.tiers.iter()
+            .take(game_state_data.profile.ascension as usize)
+            .enumerate()
+            .map(|(i, tier)| {text_gen(format!("{} {} - {}", i + 1, tier.name, tier.description), texture_creator, game_state_data.general_assets.fonts.get("basic_font").unwrap(), Color::WHITE)})
+            .collect::<Vec<Texture>>()
+    }
+}
+
+impl Scene for NewRunScene {
+    fn run(
+        &mut self,
+        game_state_data: &mut GameStateData,
+        texture_creator: &TextureCreator<WindowContext>,
+        event_pump: &mut EventPump,
+        input_devices: &mut InputDevices,
+        canvas: &mut Canvas<Window>,
+    ) -> Transition {
+        let screen_res = canvas.logical_size();
+        let video = canvas.window().subsystem().clone();
+        video.text_input().start();
+
+        let hint = text_gen(
+            "type or paste (ctrl+v) a seed to replay a run".to_string(),
+            texture_creator,
+            game_state_data.general_assets.fonts.get("basic_font").unwrap(),
+            Color::WHITE);
+        let mut rows = self.gen_rows(game_state_data, texture_creator);
+        let mut modifier_rows = self.gen_modifier_rows(game_state_data, texture_creator);
+
+        loop {
+            let mut changed = false;
+            let mut transition = None;
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit { .. } => return Transition::Quit,
+                    _ => {}
+                };
+                input::controller_handler::handle_new_controller(
+                    &input_devices.controller,
+                    &input_devices.joystick,
+                    &event,
+                    &mut input_devices.joys,
+                );
+
+                let typing = self.selected_row == CUSTOM_ROW;
+                if typing {
+                    match &event {
+                        Event::TextInput { text, .. } => {
+                            self.add_text(text);
+                            changed = true;
+                        },
+                        Event::KeyDown { keycode: Some(Keycode::V), keymod, .. } if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) => {
+                            if let Ok(clipboard) = video.clipboard().clipboard_text() {
+                                self.add_text(&clipboard);
+                                changed = true;
+                            }
+                        },
+                        Event::KeyDown { keycode: Some(Keycode::Backspace), .. } => {
+                            self.seed_text.pop();
+                            changed = true;
+                        },
+                        _ => {}
+                    }
+                }
+
+                //while typing the keyboard bindings would fire on every letter, so only the arrows and enter move around
+                let raw_input = match event {
+                    Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Left), .. } => Some((TranslatedInput::Horizontal(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Right), .. } => Some((TranslatedInput::Horizontal(1), true)),
+                    Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                    Event::KeyUp { keycode: Some(Keycode::Escape), .. } => {
+                        transition = Some(Transition::Pop);
+                        None
+                    },
+                    Event::KeyDown { .. } | Event::KeyUp { .. } if typing => None,
+                    _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                        .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                };
+
+                if let Some((translated_input, is_pressed)) = raw_input {
+                    match translated_input {
+                        TranslatedInput::Vertical(direction) if is_pressed && direction != 0 => {
+                            self.selected_row = ((self.selected_row as i32 - direction + N_ROWS as i32) % N_ROWS as i32) as usize;
+                            changed = true;
+                        },
+                        TranslatedInput::Horizontal(direction) if is_pressed && direction != 0 && self.selected_row == ASCENSION_ROW => {
+                            self.change_ascension(game_state_data, direction);
+                            changed = true;
+                        },
+                        TranslatedInput::Punch if !is_pressed => {
+                            transition = self.confirm(screen_res, game_state_data);
+                        },
+                        TranslatedInput::Kick if !is_pressed && !typing => {
+                            transition = Some(Transition::Pop);
+                        },
+                        _ => {}
+                    }
+                }
+            }
+
+            if let Some(transition) = transition {
+                video.text_input().stop();
+                return transition;
+            }
+
+            if changed {
+                rows = self.gen_rows(game_state_data, texture_creator);
+                modifier_rows = self.gen_modifier_rows(game_state_data, texture_creator);
+            }
+
+            canvas.set_draw_color(Color::RGB(0, 85, 200));
+            canvas.clear();
+
+            let mut offset = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let TextureQuery { width, height, .. } = row.query();
+                let target = Rect::new(150, 200 + offset, width, height);
+                if i == self.selected_row {
+                    render_cursor_ui(canvas, &game_state_data.ui_assets, &target);
+                }
+                canvas.copy(row, None, target).unwrap();
+                offset += 45;
+            }
+
+            let TextureQuery { width, height, .. } = hint.query();
+            canvas.copy(&hint, None, Rect::new(150, 220 + offset, width, height)).unwrap();
+
+            let mut offset = 0;
+            for modifier_row in modifier_rows.iter() {
+                let TextureQuery { width, height, .. } = modifier_row.query();
+                canvas.copy(modifier_row, None, Rect::new(screen_res.0 as i32 / 2, 200 + offset, width, height)).unwrap();
+                offset += 30;
+            }
+
+            canvas.present();
+        }
+    }
+}
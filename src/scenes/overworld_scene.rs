@@ -1,12 +1,12 @@
 use std::time::Instant;
 
-use rand::prelude::SmallRng;
-use sdl2::{EventPump, event::Event, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator}, ttf::Font, video::{Window, WindowContext}};
+use rand::{Rng, prelude::SmallRng};
+use sdl2::{EventPump, event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, ttf::Font, video::{Window, WindowContext}};
 
-use crate::{GameStateData, Transition, asset_management::{sound::audio_player::play_sound}, engine_traits::scene::Scene, game_logic::{effects::hash_effects, factories::{item_factory::load_item_assets, world_factory::load_overworld_assets}, items::Item, store::{StoreUI}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::render_ui}, ui::ingame::popup_ui::{PopUp, new_item_popup, popup_fade}};
+use crate::{GameStateData, Transition, asset_management::{controls::config_file_path, profile::save_profile, run_save::{OverworldSave, RunSave, delete_run, save_run}}, engine_traits::scene::Scene, game_logic::{ascension::RunModifiers, difficulty::DifficultyCurve, effects::hash_effects, inputs::input_recorder::InputRecording, factories::{item_factory::load_item_assets, world_factory::load_overworld_assets}, items::Item, rng_streams::RngStream, run_seed::seed_label, run_stats::RunOutcome, meta_progression::finish_run, store::{StoreUI}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{boss_difficulty, node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::{render_ui, text_gen}}, ui::ingame::popup_ui::{PopUp, new_item_popup, popup_fade}, telemetry::{TelemetryEvent, log_event}};
 
 
-use super::{event_scene::EventScene, match_scene::{MAX_UPDATES_AVOID_SPIRAL_OF_DEATH, MatchScene}, store_scene::StoreScene};
+use super::{event_scene::EventScene, match_scene::{LAST_MATCH_REPLAY_FILE, MAX_UPDATES_AVOID_SPIRAL_OF_DEATH, MatchScene}, run_summary_scene::RunSummaryScene, store_scene::StoreScene};
 
 pub struct OverworldScene {
     pub rect: Rect,
@@ -29,11 +29,23 @@ impl OverworldScene {
         }
     } 
 
-    pub fn init(&mut self, (w, h): (u32, u32), full_conection: bool, seeded_rng: &mut SmallRng) {
+    pub fn init(&mut self, (w, h): (u32, u32), full_conection: bool, difficulty_curve: &DifficultyCurve, act: u32, modifiers: &RunModifiers, seeded_rng: &mut SmallRng) {
         let map_area = Rect::new(400, 100, w-800, h-200);
         self.full_conection = full_conection;
         self.rect = map_area;
-        self.nodes = overworld_generation(map_area, (5, 6), full_conection, seeded_rng);
+        self.nodes = overworld_generation(map_area, (5, 6), full_conection, difficulty_curve, act, seeded_rng);
+        modifiers.apply_to_overworld(&mut self.nodes, seeded_rng);
+    }
+
+    pub fn from_save((w, h): (u32, u32), overworld_save: &OverworldSave) -> Self {
+        Self {
+            rect: Rect::new(400, 100, w-800, h-200),
+            full_conection: overworld_save.full_conection,
+            nodes: overworld_save.to_nodes(),
+            player_node_pos: overworld_save.player_node_pos,
+            next_node: 0,
+            connect_to_index: 0,
+        }
     }
 
     pub fn change_exploration_level(&mut self, rng: &mut SmallRng, full_conection: bool) {
@@ -43,8 +55,10 @@ impl OverworldScene {
         }
     }
 
-    pub fn regenerate_overworld(&mut self, seeded_rng: &mut SmallRng) {
-        self.nodes = overworld_generation(self.rect, (5, 6), self.full_conection, seeded_rng);
+    pub fn regenerate_overworld(&mut self, difficulty_curve: &DifficultyCurve, act: u32, modifiers: &RunModifiers, seeded_rng: &mut SmallRng) {
+        self.nodes = overworld_generation(self.rect, (5, 6), self.full_conection, difficulty_curve, act, seeded_rng);
+        modifiers.apply_to_overworld(&mut self.nodes, seeded_rng);
+        self.player_node_pos = 0;
     }
     
 
@@ -55,7 +69,7 @@ impl OverworldScene {
         self.connect_to_index =  (1 + self.connect_to_index) % connecting_to.len();
 
         if old_connect_to != self.connect_to_index {
-            play_sound(game_state_data.general_assets.sound_effects.get("scroll_level").unwrap());
+            game_state_data.audio.play("scroll_level");
         }
         
         self.next_node = connecting_to
@@ -65,8 +79,31 @@ impl OverworldScene {
     }
 }
 
+//the profile gets what the run earned before the summary shows it
+fn end_run(outcome: RunOutcome, game_state_data: &mut GameStateData) -> RunSummaryScene {
+    log_run_ended(outcome, game_state_data);
+    let rewards = finish_run(&game_state_data.meta, &mut game_state_data.profile, &game_state_data.run_stats, outcome);
+    save_profile(&game_state_data.profile);
+    RunSummaryScene::new(outcome, rewards, game_state_data)
+}
+
+fn log_run_ended(outcome: RunOutcome, game_state_data: &mut GameStateData) {
+    let stats = &game_state_data.run_stats;
+    let event = TelemetryEvent::RunEnded {
+        outcome,
+        floors_cleared: stats.floors_cleared,
+        enemies_killed: stats.enemies_killed,
+        time: stats.time,
+    };
+    log_event(game_state_data, None, event);
+}
+
 impl<'a> Scene for OverworldScene {
     
+    fn music_track(&self) -> Option<&str> {
+        Some("overworld")
+    }
+
     fn run(
         &mut self,
         game_state_data: &mut GameStateData,
@@ -76,7 +113,30 @@ impl<'a> Scene for OverworldScene {
         canvas: &mut Canvas<Window>,
     ) -> Transition {
 
-        let (w, h) = canvas.output_size().unwrap();
+        match game_state_data.run_outcome.take() {
+            Some(RunOutcome::Abandoned) => {
+                log_run_ended(RunOutcome::Abandoned, game_state_data);
+                finish_run(&game_state_data.meta, &mut game_state_data.profile, &game_state_data.run_stats, RunOutcome::Abandoned);
+                save_profile(&game_state_data.profile);
+                return Transition::Pop;
+            },
+            Some(outcome) => return Transition::Change(Box::new(end_run(outcome, game_state_data))),
+            None => {},
+        }
+
+        if game_state_data.boss_defeated {
+            game_state_data.boss_defeated = false;
+            if game_state_data.curr_act + 1 < game_state_data.difficulty_curve.acts {
+                game_state_data.curr_act += 1;
+                self.regenerate_overworld(&game_state_data.difficulty_curve, game_state_data.curr_act, &game_state_data.run_modifiers, &mut game_state_data.rng_streams.next(RngStream::Map));
+            } else {
+                //the last act boss is down, the run is won
+                delete_run();
+                return Transition::Change(Box::new(end_run(RunOutcome::Won, game_state_data)));
+            }
+        }
+
+        let (w, h) = canvas.logical_size();
         let map_area = Rect::new(400, 100, w-800, h-200);
 
         let assets = load_overworld_assets(&texture_creator);
@@ -86,6 +146,13 @@ impl<'a> Scene for OverworldScene {
 
         let item_list = item_list_init(&game_state_data);
 
+        let seed_text = text_gen(
+            format!("{} (C to copy)", seed_label(game_state_data.seed.unwrap(), game_state_data.seed_mode)),
+            texture_creator,
+            game_state_data.general_assets.fonts.get("basic_font").unwrap(),
+            Color::WHITE);
+        let TextureQuery { width: seed_width, height: seed_height, .. } = seed_text.query();
+
         self.connect_to_index = 0;
         let connecting_to = &self.nodes[self.player_node_pos as usize].connect_to;
         self.next_node = connecting_to
@@ -94,11 +161,15 @@ impl<'a> Scene for OverworldScene {
             .collect::<Vec<usize>>()[self.connect_to_index];
 
         let mut map_events =  game_state_data.player.as_ref().unwrap().events.on_overworld_map.clone();
+        let mut map_rng = game_state_data.rng_streams.next(RngStream::Map);
         for map_event in map_events.iter_mut() {
-            (map_event.0)(game_state_data.player.as_mut().unwrap(), self, game_state_data.map_rng.as_mut().unwrap(), &mut map_event.1);
+            (map_event.0)(game_state_data.player.as_mut().unwrap(), self, &mut map_rng, &mut map_event.1);
         }
         game_state_data.player.as_mut().unwrap().events.on_overworld_map = map_events;
 
+        let run_save = RunSave::new(game_state_data, self);
+        save_run(&run_save);
+
         let mut previous_time = Instant::now();
         let logic_timestep: f64 = 0.016;
         let mut logic_time_accumulated: f64 = 0.0;
@@ -109,6 +180,25 @@ impl<'a> Scene for OverworldScene {
             for event in event_pump.poll_iter() {
                 match event {
                     Event::Quit { .. } => return Transition::Quit,
+                    Event::KeyDown {
+                        keycode: Some(Keycode::C),
+                        ..
+                    } => {
+                        //share the seed so someone else can play the same run
+                        let seed = game_state_data.seed.unwrap().to_string();
+                        if let Err(e) = canvas.window().subsystem().clipboard().set_clipboard_text(&seed) {
+                            println!("failed to copy seed {:?}", e);
+                        }
+                    }
+                    Event::KeyDown {
+                        keycode: Some(Keycode::R),
+                        ..
+                    } => {
+                        //replay the last recorded match
+                        if let Some(replay) = InputRecording::load(&config_file_path(LAST_MATCH_REPLAY_FILE)) {
+                            return Transition::Push(Box::new(MatchScene::new_replay(replay)));
+                        }
+                    }
                     _ => {}
                 };
                 input::controller_handler::handle_new_controller(
@@ -130,10 +220,15 @@ impl<'a> Scene for OverworldScene {
                     }
                     if !is_pressed {
                         if translated_input == TranslatedInput::Punch {
-                            play_sound(game_state_data.general_assets.sound_effects.get("select_level").unwrap());
-                            if let WorldNodeType::Level(_) = self.nodes[self.next_node].node_type {
+                            game_state_data.audio.play("select_level");
+                            let node_type = self.nodes[self.next_node].node_type;
+                            if node_type != WorldNodeType::Start {
+                                log_event(game_state_data, None, TelemetryEvent::NodeChoice { node: self.next_node, node_type });
+                            }
+                            if let WorldNodeType::Level(difficulty) = self.nodes[self.next_node].node_type {
                                 self.player_node_pos = self.next_node;
                                 game_state_data.curr_level = self.player_node_pos as i32;
+                                game_state_data.curr_difficulty = difficulty;
                                 return Transition::Push(Box::new(MatchScene::new("foxgirl".to_string(), None, None)));
                             }
 
@@ -143,6 +238,17 @@ impl<'a> Scene for OverworldScene {
                                 return Transition::Push(Box::new(StoreScene{}));
                             }
 
+                            if let WorldNodeType::Boss = self.nodes[self.next_node].node_type {
+                                self.player_node_pos = self.next_node;
+                                game_state_data.curr_level = self.player_node_pos as i32;
+                                game_state_data.curr_difficulty = boss_difficulty(&self.nodes, &game_state_data.difficulty_curve, game_state_data.curr_act);
+
+                                let mut boss_names = game_state_data.bosses.keys().cloned().collect::<Vec<String>>();
+                                boss_names.sort();
+                                let boss = boss_names[game_state_data.rng_streams.next(RngStream::Map).gen_range(0..boss_names.len())].clone();
+                                return Transition::Push(Box::new(MatchScene::new_boss("foxgirl".to_string(), boss)));
+                            }
+
                             if let WorldNodeType::Event(id) = self.nodes[self.next_node].node_type {
                                 self.player_node_pos = self.next_node;
                                 game_state_data.curr_level = self.player_node_pos as i32;
@@ -199,6 +305,8 @@ impl<'a> Scene for OverworldScene {
                 &popup_content
                 );
 
+            canvas.copy(&seed_text, None, Rect::new(10, (h - seed_height - 10) as i32, seed_width, seed_height)).unwrap();
+
             canvas.present();
         }
     }
@@ -0,0 +1,161 @@
+use sdl2::{EventPump, event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+
+use crate::{GameStateData, Transition, engine_traits::scene::Scene, game_logic::{meta_progression::RunRewards, run_seed::{SeedMode, seed_label}, run_stats::{RunOutcome, RunStats, time_label}}, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{render_cursor_ui, text_gen}};
+
+use super::menu_scene::start_game;
+
+const RETRY_ROW: usize = 0;
+const MENU_ROW: usize = 1;
+const N_ROWS: usize = 2;
+
+//shown once a run is over, the run save is already gone by then
+pub struct RunSummaryScene {
+    outcome: RunOutcome,
+    stats: RunStats,
+    rewards: RunRewards,
+    seed: u64,
+    seed_mode: SeedMode,
+    selected_row: usize,
+}
+
+impl RunSummaryScene {
+    pub fn new(outcome: RunOutcome, rewards: RunRewards, game_state_data: &GameStateData) -> Self {
+        Self {
+            outcome,
+            stats: game_state_data.run_stats.clone(),
+            rewards,
+            seed: game_state_data.seed.unwrap(),
+            seed_mode: game_state_data.seed_mode,
+            selected_row: RETRY_ROW,
+        }
+    }
+
+    fn stat_rows(&self) -> Vec<String> {
+        let mut rows = vec![
+            format!("{:<18}{}", "Floors cleared", self.stats.floors_cleared),
+            format!("{:<18}{}", "Enemies killed", self.stats.enemies_killed),
+            format!("{:<18}{}", "Damage dealt", self.stats.damage_dealt),
+            format!("{:<18}{}", "Damage taken", self.stats.damage_taken),
+            format!("{:<18}{}", "Best combo", self.stats.best_combo),
+            format!("{:<18}{}", "Items collected", self.stats.items_collected),
+            format!("{:<18}{}", "Currency earned", self.stats.currency_earned),
+            format!("{:<18}{}", "Time", time_label(self.stats.time)),
+            seed_label(self.seed, self.seed_mode),
+            format!("{:<18}+{}", "Meta currency", self.rewards.currency),
+        ];
+        for milestone in self.rewards.milestones.iter() {
+            rows.push(format!("Milestone reached: {}", milestone));
+        }
+        rows
+    }
+
+    fn gen_rows<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> Vec<Texture<'a>> {
+        vec!["Retry this seed".to_string(), "Main menu".to_string()].into_iter()
+            .map(|row| {text_gen(row, texture_creator, game_state_data.general_assets.fonts.get("main_menu_font").unwrap(), Color::WHITE)})
+            .collect::<Vec<Texture>>()
+    }
+
+    fn confirm(&mut self, screen_res: (u32, u32), game_state_data: &mut GameStateData) -> Option<Transition> {
+        match self.selected_row {
+            RETRY_ROW => Some(start_game(screen_res, game_state_data, self.seed, self.seed_mode)),
+            MENU_ROW => Some(Transition::Pop),
+            _ => None,
+        }
+    }
+}
+
+impl Scene for RunSummaryScene {
+    fn music_track(&self) -> Option<&str> {
+        Some("menu")
+    }
+
+    fn run(
+        &mut self,
+        game_state_data: &mut GameStateData,
+        texture_creator: &TextureCreator<WindowContext>,
+        event_pump: &mut EventPump,
+        input_devices: &mut InputDevices,
+        canvas: &mut Canvas<Window>,
+    ) -> Transition {
+        let screen_res = canvas.logical_size();
+
+        let title = text_gen(
+            match self.outcome {
+                RunOutcome::Won => "Victory",
+                _ => "Game Over",
+            }.to_string(),
+            texture_creator,
+            game_state_data.general_assets.fonts.get("main_menu_font").unwrap(),
+            Color::WHITE);
+        let stats = self.stat_rows().into_iter()
+            .map(|row| {text_gen(row, texture_creator, game_state_data.general_assets.fonts.get("basic_font").unwrap(), Color::WHITE)})
+            .collect::<Vec<Texture>>();
+        let rows = self.gen_rows(game_state_data, texture_creator);
+
+        loop {
+            let mut transition = None;
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit { .. } => return Transition::Quit,
+                    _ => {}
+                };
+                input::controller_handler::handle_new_controller(
+                    &input_devices.controller,
+                    &input_devices.joystick,
+                    &event,
+                    &mut input_devices.joys,
+                );
+
+                let raw_input = match event {
+                    Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                    Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                    _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                        .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                };
+
+                if let Some((translated_input, is_pressed)) = raw_input {
+                    match translated_input {
+                        TranslatedInput::Vertical(direction) if is_pressed && direction != 0 => {
+                            self.selected_row = ((self.selected_row as i32 - direction + N_ROWS as i32) % N_ROWS as i32) as usize;
+                        },
+                        TranslatedInput::Punch if !is_pressed => {
+                            transition = self.confirm(screen_res, game_state_data);
+                        },
+                        _ => {}
+                    }
+                }
+            }
+
+            if let Some(transition) = transition {
+                return transition;
+            }
+
+            canvas.set_draw_color(Color::RGB(0, 85, 200));
+            canvas.clear();
+
+            let TextureQuery { width, height, .. } = title.query();
+            canvas.copy(&title, None, Rect::new(150, 80, width, height)).unwrap();
+
+            let mut offset = 0;
+            for stat in stats.iter() {
+                let TextureQuery { width, height, .. } = stat.query();
+                canvas.copy(stat, None, Rect::new(150, 160 + offset, width, height)).unwrap();
+                offset += height as i32 + 6;
+            }
+
+            let mut offset = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let TextureQuery { width, height, .. } = row.query();
+                let target = Rect::new(150, screen_res.1 as i32 - 160 + offset, width, height);
+                if i == self.selected_row {
+                    render_cursor_ui(canvas, &game_state_data.ui_assets, &target);
+                }
+                canvas.copy(row, None, target).unwrap();
+                offset += 45;
+            }
+
+            canvas.present();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use sdl2::{EventPump, event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+
+use crate::{GameStateData, Transition, asset_management::settings::{apply_video_settings, save_settings}, engine_traits::scene::Scene, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{render_cursor_ui, text_gen}, ui::menus::settings_menu_ui::{SettingsChange, SettingsMenu, SettingsRow}};
+
+pub struct SettingsScene {
+    menu: SettingsMenu,
+}
+
+impl SettingsScene {
+    pub fn new() -> Self {
+        Self {
+            menu: SettingsMenu::new(),
+        }
+    }
+
+    fn gen_texts<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> (Vec<Texture<'a>>, Option<Texture<'a>>) {
+        let rows = self.menu.rows(&game_state_data.settings).into_iter()
+            .map(|row| {text_gen(row, texture_creator, game_state_data.general_assets.fonts.get("main_menu_font").unwrap(), Color::WHITE)})
+            .collect::<Vec<Texture>>();
+
+        let message = self.menu.message.as_ref().map(|message| {
+            text_gen(message.clone(), texture_creator, game_state_data.general_assets.fonts.get("basic_font").unwrap(), Color::YELLOW)
+        });
+
+        (rows, message)
+    }
+
+    fn change(&mut self, game_state_data: &mut GameStateData, canvas: &mut Canvas<Window>, direction: i32) {
+        match self.menu.change(&mut game_state_data.settings, direction) {
+            Some(SettingsChange::Audio) => game_state_data.audio.set_volumes(&game_state_data.settings),
+            Some(SettingsChange::Video) => apply_video_settings(&game_state_data.settings, canvas),
+            Some(SettingsChange::Other) => {},
+            None => return,
+        }
+        save_settings(&game_state_data.settings);
+    }
+}
+
+impl Scene for SettingsScene {
+    fn run(
+        &mut self,
+        game_state_data: &mut GameStateData,
+        texture_creator: &TextureCreator<WindowContext>,
+        event_pump: &mut EventPump,
+        input_devices: &mut InputDevices,
+        canvas: &mut Canvas<Window>,
+    ) -> Transition {
+        let (mut rows, mut message) = self.gen_texts(game_state_data, texture_creator);
+
+        loop {
+            let mut changed = false;
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit { .. } => return Transition::Quit,
+                    _ => {}
+                };
+                input::controller_handler::handle_new_controller(
+                    &input_devices.controller,
+                    &input_devices.joystick,
+                    &event,
+                    &mut input_devices.joys,
+                );
+
+                let raw_input = match event {
+                    Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Left), .. } => Some((TranslatedInput::Horizontal(-1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Right), .. } => Some((TranslatedInput::Horizontal(1), true)),
+                    Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                    Event::KeyUp { keycode: Some(Keycode::Escape), .. } => return Transition::Pop,
+                    _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                        .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                };
+
+                if let Some((translated_input, is_pressed)) = raw_input {
+                    match translated_input {
+                        TranslatedInput::Vertical(direction) if is_pressed => {
+                            self.menu.move_selection(-direction);
+                            changed = true;
+                        },
+                        TranslatedInput::Horizontal(direction) if is_pressed && direction != 0 => {
+                            self.change(game_state_data, canvas, direction);
+                            changed = true;
+                        },
+                        TranslatedInput::Punch if !is_pressed => {
+                            if self.menu.selected() == SettingsRow::Back {
+                                return Transition::Pop;
+                            }
+                            self.change(game_state_data, canvas, 1);
+                            changed = true;
+                        },
+                        TranslatedInput::Kick if !is_pressed => {
+                            return Transition::Pop;
+                        },
+                        _ => {}
+                    }
+                }
+            }
+
+            if changed {
+                let texts = self.gen_texts(game_state_data, texture_creator);
+                rows = texts.0;
+                message = texts.1;
+            }
+
+            canvas.set_draw_color(Color::RGB(0, 85, 200));
+            canvas.clear();
+
+            let mut offset = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let TextureQuery { width, height, .. } = row.query();
+                let target = Rect::new(150, 100 + offset, width, height);
+                if i == self.menu.selected_row {
+                    render_cursor_ui(canvas, &game_state_data.ui_assets, &target);
+                }
+                canvas.copy(row, None, target).unwrap();
+                offset += 40;
+            }
+
+            if let Some(message) = &message {
+                let TextureQuery { width, height, .. } = message.query();
+                canvas.copy(message, None, Rect::new(150, 120 + offset, width, height)).unwrap();
+            }
+
+            canvas.present();
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::{cmp, time::Instant};
 
 use sdl2::{EventPump, event::Event, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator}, ttf::Font, video::{Window, WindowContext}};
 
-use crate::{GameStateData, Transition, engine_traits::scene::Scene, game_logic::{effects::hash_effects, factories::{item_factory::{load_item_assets, load_items}, world_factory::load_overworld_assets}, items::Item, store::{StoreUI, get_store_item_list}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::render_ui}, ui::ingame::popup_ui::{PopUp, new_item_popup, popup_fade}};
+use crate::{GameStateData, Transition, engine_traits::scene::Scene, game_logic::{coop::{buyer, give_item, player_for_device}, effects::hash_effects, factories::{item_factory::{load_item_assets, load_items}, world_factory::load_overworld_assets}, items::Item, rng_streams::RngStream, store::{StoreUI, get_store_item_list}}, hp_bar_init, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, item_list_init, overworld::{node::{WorldNode, WorldNodeType}, overworld_generation, overworld_change_connections}, rendering::{renderer_overworld::render_overworld, renderer_store::render_store, renderer_ui::render_ui}, ui::ingame::popup_ui::{PopUp, new_item_popup, popup_fade}, telemetry::{TelemetryEvent, log_event}};
 
 use super::match_scene::{MAX_UPDATES_AVOID_SPIRAL_OF_DEATH};
 
@@ -42,6 +42,10 @@ impl StoreScene {
 
 impl<'a> Scene for StoreScene {
     
+    fn music_track(&self) -> Option<&str> {
+        Some("store")
+    }
+
     fn run(
         &mut self,
         game_state_data: &mut GameStateData,
@@ -51,11 +55,11 @@ impl<'a> Scene for StoreScene {
         canvas: &mut Canvas<Window>,
     ) -> Transition {
 
-        let (w, h) = canvas.output_size().unwrap();
+        let (w, h) = canvas.logical_size();
 
         let mut store_struct = StoreUI::new((w, h));
-        let item_room_seed = game_state_data.seed.unwrap() + (game_state_data.curr_level as u64);
-        store_struct.items = get_store_item_list(item_room_seed, game_state_data.general_assets.loot_tables.get("store_table").unwrap());
+        let mut store_rng = game_state_data.rng_streams.keyed(RngStream::Store, &[game_state_data.curr_act as u64, game_state_data.curr_level as u64]);
+        store_struct.items = get_store_item_list(&mut store_rng, game_state_data.general_assets.loot_tables.get("store_table").unwrap());
 
         let mut store_item_prices = Some(StoreScene::create_price_textures(texture_creator, 
             &game_state_data.general_assets.fonts.get("basic_font").unwrap(), 
@@ -96,7 +100,8 @@ impl<'a> Scene for StoreScene {
                 let raw_input = input::input_handler::rcv_input(&event, &input_devices.controls);
 
                 if raw_input.is_some() {
-                    let (_id, translated_input, is_pressed) = raw_input.unwrap();
+                    let (id, translated_input, is_pressed) = raw_input.unwrap();
+                    let player_index = player_for_device(&input_devices.joys, id, game_state_data.player2.is_some());
                     if is_pressed {
                         if let TranslatedInput::Horizontal(x) = translated_input {
                             StoreScene::iterate_over_store_items(&mut store.as_mut().unwrap(), x)
@@ -110,9 +115,11 @@ impl<'a> Scene for StoreScene {
                                 if store_ui.selected_item < store_ui.items.len() {
                                     if store_ui.items.len() > 0 {
 
-                                        let mut bought_item = game_state_data.items.get(&(store_ui.items[store_ui.selected_item] as i32)).unwrap().clone();
+                                        let bought_item = game_state_data.items.get(&(store_ui.items[store_ui.selected_item] as i32)).unwrap().clone();
 
-                                        if game_state_data.player.as_ref().unwrap().currency >= bought_item.price {
+                                        let loot = game_state_data.settings.coop_loot;
+                                        let can_pay = buyer(loot, player_index, game_state_data.player.as_mut().unwrap(), game_state_data.player2.as_mut()).currency >= bought_item.price;
+                                        if can_pay {
 
                                             store_ui.items.remove(store_ui.selected_item);
                                             store_ui.item_rects.remove(store_ui.selected_item);
@@ -124,9 +131,19 @@ impl<'a> Scene for StoreScene {
                                             let new_selected = if store_ui.selected_item == 0 {store_ui.selected_item} else {store_ui.selected_item-1};
                                             store_ui.selected_item = cmp::max(0,cmp::min(store_ui.items.len(), new_selected));
 
-                                            let player = game_state_data.player.as_mut().unwrap();
-                                            player.currency = cmp::max(0, player.currency - bought_item.price);
-                                            player.equip_item(&mut bought_item, &game_state_data.effects, &mut game_state_data.energy_bar.as_mut().unwrap());
+                                            let paying_player = buyer(loot, player_index, game_state_data.player.as_mut().unwrap(), game_state_data.player2.as_mut());
+                                            paying_player.currency = cmp::max(0, paying_player.currency - bought_item.price);
+                                            log_event(game_state_data, None, TelemetryEvent::Purchase { item_id: bought_item.id, price: bought_item.price });
+
+                                            //player 2 only owns what they paid for when loot is split
+                                            let (owner, other) = if player_index == 1 && game_state_data.player2.is_some() {
+                                                (game_state_data.player2.as_mut().unwrap(), game_state_data.player.as_mut())
+                                            } else {
+                                                (game_state_data.player.as_mut().unwrap(), game_state_data.player2.as_mut())
+                                            };
+                                            give_item(loot, owner, other, &bought_item, &game_state_data.effects);
+                                            let player = game_state_data.player.as_ref().unwrap();
+                                            game_state_data.energy_bar.as_mut().unwrap().update_width(player.active_item_cost as i32, player.currency as i32);
                                     
                                             popup_content = Some(crate::ui::ingame::popup_ui::render_popup(texture_creator, 
                                                 &bought_item.name, 
@@ -0,0 +1,134 @@
+use sdl2::{EventPump, event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::{Canvas, Texture, TextureCreator, TextureQuery}, video::{Window, WindowContext}};
+
+use crate::{GameStateData, Transition, asset_management::profile::save_profile, engine_traits::scene::Scene, input::{self, input_devices::InputDevices, translated_inputs::TranslatedInput}, rendering::renderer_ui::{render_cursor_ui, text_gen}, ui::menus::unlocks_menu_ui::{UnlocksMenu, UnlocksRow, milestone_rows}};
+
+pub struct UnlocksScene {
+    menu: UnlocksMenu,
+}
+
+impl UnlocksScene {
+    pub fn new() -> Self {
+        Self {
+            menu: UnlocksMenu::new(),
+        }
+    }
+
+    fn gen_texts<'a>(&self, game_state_data: &GameStateData, texture_creator: &'a TextureCreator<WindowContext>) -> (Texture<'a>, Vec<Texture<'a>>, Vec<Texture<'a>>, Option<Texture<'a>>) {
+        let font = game_state_data.general_assets.fonts.get("main_menu_font").unwrap();
+        let basic_font = game_state_data.general_assets.fonts.get("basic_font").unwrap();
+
+        let title = text_gen(self.menu.title(&game_state_data.profile), texture_creator, font, Color::WHITE);
+        let rows = self.menu.rows(&game_state_data.meta, &game_state_data.profile, &game_state_data.items).into_iter()
+            .map(|row| {text_gen(row, texture_creator, basic_font, Color::WHITE)})
+            .collect::<Vec<Texture>>();
+        let milestones = milestone_rows(&game_state_data.meta, &game_state_data.profile).into_iter()
+            .map(|row| {text_gen(row, texture_creator, basic_font, Color::WHITE)})
+            .collect::<Vec<Texture>>();
+
+        let message = self.menu.message.as_ref().map(|message| {
+            text_gen(message.clone(), texture_creator, basic_font, Color::YELLOW)
+        });
+
+        (title, rows, milestones, message)
+    }
+}
+
+impl Scene for UnlocksScene {
+    fn run(
+        &mut self,
+        game_state_data: &mut GameStateData,
+        texture_creator: &TextureCreator<WindowContext>,
+        event_pump: &mut EventPump,
+        input_devices: &mut InputDevices,
+        canvas: &mut Canvas<Window>,
+    ) -> Transition {
+        let screen_res = canvas.logical_size();
+        let (mut title, mut rows, mut milestones, mut message) = self.gen_texts(game_state_data, texture_creator);
+
+        loop {
+            let mut changed = false;
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit { .. } => return Transition::Quit,
+                    _ => {}
+                };
+                input::controller_handler::handle_new_controller(
+                    &input_devices.controller,
+                    &input_devices.joystick,
+                    &event,
+                    &mut input_devices.joys,
+                );
+
+                let raw_input = match event {
+                    Event::KeyDown { keycode: Some(Keycode::Up), .. } => Some((TranslatedInput::Vertical(1), true)),
+                    Event::KeyDown { keycode: Some(Keycode::Down), .. } => Some((TranslatedInput::Vertical(-1), true)),
+                    Event::KeyUp { keycode: Some(Keycode::Return), .. } => Some((TranslatedInput::Punch, false)),
+                    Event::KeyUp { keycode: Some(Keycode::Escape), .. } => return Transition::Pop,
+                    _ => input::input_handler::rcv_input(&event, &input_devices.controls)
+                        .map(|(_id, translated_input, is_pressed)| {(translated_input, is_pressed)}),
+                };
+
+                if let Some((translated_input, is_pressed)) = raw_input {
+                    match translated_input {
+                        TranslatedInput::Vertical(direction) if is_pressed => {
+                            self.menu.move_selection(&game_state_data.meta, -direction);
+                            changed = true;
+                        },
+                        TranslatedInput::Punch if !is_pressed => {
+                            if self.menu.selected(&game_state_data.meta) == UnlocksRow::Back {
+                                return Transition::Pop;
+                            }
+                            if self.menu.confirm(&game_state_data.meta, &mut game_state_data.profile) {
+                                save_profile(&game_state_data.profile);
+                            }
+                            changed = true;
+                        },
+                        TranslatedInput::Kick if !is_pressed => {
+                            return Transition::Pop;
+                        },
+                        _ => {}
+                    }
+                }
+            }
+
+            if changed {
+                let texts = self.gen_texts(game_state_data, texture_creator);
+                title = texts.0;
+                rows = texts.1;
+                milestones = texts.2;
+                message = texts.3;
+            }
+
+            canvas.set_draw_color(Color::RGB(0, 85, 200));
+            canvas.clear();
+
+            let TextureQuery { width, height, .. } = title.query();
+            canvas.copy(&title, None, Rect::new((screen_res.0 as i32 - width as i32) / 2, 60, width, height)).unwrap();
+
+            let mut offset = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let TextureQuery { width, height, .. } = row.query();
+                let target = Rect::new(100, 140 + offset, width, height);
+                if i == self.menu.selected_row {
+                    render_cursor_ui(canvas, &game_state_data.ui_assets, &target);
+                }
+                canvas.copy(row, None, target).unwrap();
+                offset += 30;
+            }
+
+            if let Some(message) = &message {
+                let TextureQuery { width, height, .. } = message.query();
+                canvas.copy(message, None, Rect::new(100, 160 + offset, width, height)).unwrap();
+            }
+
+            let mut offset = 0;
+            for milestone in milestones.iter() {
+                let TextureQuery { width, height, .. } = milestone.query();
+                canvas.copy(milestone, None, Rect::new(screen_res.0 as i32 / 2, 140 + offset, width, height)).unwrap();
+                offset += 30;
+            }
+
+            canvas.present();
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{GameStateData, asset_management::controls::config_file_path, game_logic::run_stats::RunOutcome, overworld::node::WorldNodeType};
+
+pub mod report;
+
+pub const TELEMETRY_FILE: &str = "telemetry.jsonl";
+
+//what the designers want to balance with, one json object per line
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    RunStarted { seed: u64, loadout: String, #[serde(default)] ascension: u32 },
+    //player hits on enemies, player vs player hits only show up as damage taken
+    Hit { player: i32, attack: String, damage: i32, enemy: String },
+    Block { player: i32, attack: String, blocked_by_player: bool, enemy: String },
+    //player is whoever hit the enemy last, none if no player touched it
+    Kill { #[serde(default)] player: Option<i32>, enemy: String },
+    //source is the enemy archetype or "player" for friendly fire
+    DamageTaken { player: i32, amount: i32, source: String },
+    ItemPickup { player: i32, item_id: i32 },
+    Purchase { item_id: i32, price: u32 },
+    NodeChoice { node: usize, node_type: WorldNodeType },
+    Death { player: i32 },
+    RunEnded { outcome: RunOutcome, floors_cleared: u32, enemies_killed: u32, time: f64 },
+}
+
+#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TelemetryRecord {
+    pub run: u64,
+    pub act: u32,
+    pub level: i32,
+    //simulation frame of the match, none outside of matches
+    pub frame: Option<i32>,
+    pub time: u64,
+    #[serde(flatten)]
+    pub event: TelemetryEvent,
+}
+
+//appends to one file for every run, records are told apart by the run id
+pub struct TelemetryLog {
+    file: Option<File>,
+    pub run_id: u64,
+}
+
+impl TelemetryLog {
+    pub fn open() -> Self {
+        let path = config_file_path(TELEMETRY_FILE);
+        let file = match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                println!("telemetry disabled, cannot open {:?} {:?}", path, e);
+                None
+            }
+        };
+        Self {
+            file,
+            run_id: 0,
+        }
+    }
+
+    pub fn log(&mut self, act: u32, level: i32, frame: Option<i32>, event: TelemetryEvent) {
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => return,
+        };
+        let record = TelemetryRecord {
+            run: self.run_id,
+            act,
+            level,
+            frame,
+            time: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            event,
+        };
+        if let Err(e) = writeln!(file, "{}", serde_json::to_string(&record).unwrap()) {
+            println!("failed to write telemetry {:?}", e);
+        }
+    }
+}
+
+//runs started in the same millisecond would share an id, nobody starts runs that fast
+pub fn new_run_id() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+}
+
+pub fn log_event(game_state_data: &mut GameStateData, frame: Option<i32>, event: TelemetryEvent) {
+    let (act, level) = (game_state_data.curr_act, game_state_data.curr_level);
+    game_state_data.telemetry.log(act, level, frame, event);
+}
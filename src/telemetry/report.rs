@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::game_logic::run_stats::RunOutcome;
+
+use super::{TelemetryEvent, TelemetryRecord};
+
+#[derive(Default)]
+struct RunReport {
+    outcome: Option<RunOutcome>,
+    floors_cleared: u32,
+    time: f64,
+    //whatever hurt each player last, blamed for their next death
+    last_damage_source: HashMap<i32, String>,
+}
+
+#[derive(Default)]
+struct EnemyReport {
+    hits_on_players: u32,
+    damage_to_players: i64,
+    damage_taken: i64,
+    kills: u32,
+    player_deaths: u32,
+}
+
+#[derive(Default)]
+struct ItemReport {
+    pickups: u32,
+    purchases: u32,
+    spent: u64,
+    runs: HashSet<u64>,
+}
+
+#[derive(Default)]
+struct AttackReport {
+    hits: u32,
+    damage: i64,
+    blocked: u32,
+}
+
+//summarises every run in a telemetry file, run with --telemetry-report [file]
+pub fn print_report(path: &Path) {
+    println!("loading {:?}", path);
+    let json_lines = match fs::read_to_string(path) {
+        Ok(json_lines) => json_lines,
+        Err(e) => {
+            println!("cannot read telemetry file {:?}: {}", path, e);
+            return;
+        },
+    };
+    //a crash can leave half a line at the end, those are skipped
+    let records = json_lines.lines()
+        .filter_map(|line| {serde_json::from_str::<TelemetryRecord>(line).ok()})
+        .collect::<Vec<TelemetryRecord>>();
+
+    let mut runs: HashMap<u64, RunReport> = HashMap::new();
+    let mut enemies: BTreeMap<String, EnemyReport> = BTreeMap::new();
+    let mut items: BTreeMap<i32, ItemReport> = BTreeMap::new();
+    let mut attacks: BTreeMap<String, AttackReport> = BTreeMap::new();
+    let mut node_choices: BTreeMap<String, u32> = BTreeMap::new();
+
+    for record in records.iter() {
+        let run = runs.entry(record.run).or_default();
+        match &record.event {
+            TelemetryEvent::RunStarted { .. } => {},
+            TelemetryEvent::Hit { attack, damage, enemy, .. } => {
+                let attack_report = attacks.entry(attack.clone()).or_default();
+                attack_report.hits += 1;
+                attack_report.damage += *damage as i64;
+                enemies.entry(enemy.clone()).or_default().damage_taken += *damage as i64;
+            },
+            TelemetryEvent::Block { attack, blocked_by_player, enemy, .. } => {
+                //only the player attacks matter here, enemy attacks are covered by damage taken
+                if !blocked_by_player {
+                    attacks.entry(attack.clone()).or_default().blocked += 1;
+                }
+                enemies.entry(enemy.clone()).or_default();
+            },
+            TelemetryEvent::Kill { enemy, .. } => {
+                enemies.entry(enemy.clone()).or_default().kills += 1;
+            },
+            TelemetryEvent::DamageTaken { player, amount, source } => {
+                let enemy_report = enemies.entry(source.clone()).or_default();
+                enemy_report.hits_on_players += 1;
+                enemy_report.damage_to_players += *amount as i64;
+                run.last_damage_source.insert(*player, source.clone());
+            },
+            TelemetryEvent::ItemPickup { item_id, .. } => {
+                let item_report = items.entry(*item_id).or_default();
+                item_report.pickups += 1;
+                item_report.runs.insert(record.run);
+            },
+            TelemetryEvent::Purchase { item_id, price } => {
+                let item_report = items.entry(*item_id).or_default();
+                item_report.purchases += 1;
+                item_report.spent += *price as u64;
+                item_report.runs.insert(record.run);
+            },
+            TelemetryEvent::NodeChoice { node_type, .. } => {
+                let node_name = format!("{:?}", node_type);
+                //levels carry their difficulty, group them all together
+                let node_name = node_name.split('(').next().unwrap().to_string();
+                *node_choices.entry(node_name).or_insert(0) += 1;
+            },
+            TelemetryEvent::Death { player } => {
+                if let Some(source) = run.last_damage_source.get(player) {
+                    enemies.entry(source.clone()).or_default().player_deaths += 1;
+                }
+            },
+            TelemetryEvent::RunEnded { outcome, floors_cleared, time, .. } => {
+                run.outcome = Some(*outcome);
+                run.floors_cleared = *floors_cleared;
+                run.time = *time;
+            },
+        }
+    }
+
+    let finished = runs.values().filter(|run| {run.outcome.is_some()}).collect::<Vec<&RunReport>>();
+    let count_outcome = |outcome: RunOutcome| {finished.iter().filter(|run| {run.outcome == Some(outcome)}).count()};
+    let won = count_outcome(RunOutcome::Won);
+    println!("{} records, {} runs, {} finished", records.len(), runs.len(), finished.len());
+    println!("won {} died {} abandoned {}", won, count_outcome(RunOutcome::Died), count_outcome(RunOutcome::Abandoned));
+    if !finished.is_empty() {
+        let n_finished = finished.len() as f64;
+        println!("win rate {:.1}%", won as f64 / n_finished * 100.0);
+        println!("average floors {:.1}", finished.iter().map(|run| {run.floors_cleared as f64}).sum::<f64>() / n_finished);
+        println!("average run time {:.0}s", finished.iter().map(|run| {run.time}).sum::<f64>() / n_finished);
+    }
+
+    println!();
+    println!("{:<16}{:>10}{:>14}{:>12}{:>8}{:>8}", "enemy", "hits", "dmg to p", "dmg taken", "kills", "deaths");
+    for (name, enemy) in enemies.iter() {
+        println!("{:<16}{:>10}{:>14}{:>12}{:>8}{:>8}", name, enemy.hits_on_players, enemy.damage_to_players, enemy.damage_taken, enemy.kills, enemy.player_deaths);
+    }
+
+    println!();
+    println!("{:<24}{:>8}{:>12}{:>8}", "attack", "hits", "avg dmg", "blocked");
+    for (name, attack) in attacks.iter() {
+        println!("{:<24}{:>8}{:>12.1}{:>8}", name, attack.hits, attack.damage as f64 / attack.hits.max(1) as f64, attack.blocked);
+    }
+
+    println!();
+    println!("{:<8}{:>8}{:>10}{:>8}{:>10}", "item", "picked", "bought", "spent", "win rate");
+    for (item_id, item) in items.iter() {
+        let item_runs = item.runs.iter()
+            .filter_map(|run| {runs.get(run).and_then(|run| {run.outcome})})
+            .collect::<Vec<RunOutcome>>();
+        let win_rate = if item_runs.is_empty() {
+            "-".to_string()
+        } else {
+            format!("{:.1}%", item_runs.iter().filter(|&&outcome| {outcome == RunOutcome::Won}).count() as f64 / item_runs.len() as f64 * 100.0)
+        };
+        println!("{:<8}{:>8}{:>10}{:>8}{:>10}", item_id, item.pickups, item.purchases, item.spent, win_rate);
+    }
+
+    println!();
+    for (node_type, count) in node_choices.iter() {
+        println!("{:<8} chosen {}", node_type, count);
+    }
+}
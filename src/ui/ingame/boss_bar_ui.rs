@@ -0,0 +1,61 @@
+use sdl2::pixels::Color;
+
+use crate::game_logic::boss::BossDefinition;
+
+use super::segmented_bar_ui::SegmentedBar;
+
+const PHASE_COLORS: [(u8, u8, u8); 3] = [(255, 170, 60), (255, 100, 60), (200, 40, 40)];
+
+//one SegmentedBar per phase laid side by side, the first phase is on the right so the whole bar drains right to left
+pub struct BossBar<'a> {
+    pub bars: Vec<SegmentedBar<'a>>,
+    //hp where each phase bar starts counting, same order as bars
+    lower_bounds: Vec<i32>,
+}
+
+impl<'a> BossBar<'a> {
+    pub fn new(screen_res: (u32, u32), definition: &BossDefinition) -> Self {
+        let total_width = screen_res.0 / 2;
+        let mut x = (screen_res.0 / 4) as i32;
+        let y = screen_res.1 as i32 - 60;
+
+        let mut bars = Vec::new();
+        let mut lower_bounds = Vec::new();
+        for (phase_index, phase) in definition.phases.iter().enumerate().rev() {
+            let upper = (phase.hp_threshold * definition.hp as f64) as i32;
+            let lower = match definition.phases.get(phase_index + 1) {
+                Some(next_phase) => (next_phase.hp_threshold * definition.hp as f64) as i32,
+                None => 0,
+            };
+            let range = std::cmp::max(upper - lower, 1);
+            let width = (total_width as f64 * range as f64 / definition.hp as f64) as u32;
+            let (r, g, b) = PHASE_COLORS[phase_index % PHASE_COLORS.len()];
+
+            bars.push(SegmentedBar::new(
+                x,
+                y,
+                width,
+                20,
+                range,
+                range,
+                std::cmp::max(range / 10, 1),
+                Some(Color::RGB(r, g, b)),
+                None,
+            ));
+            lower_bounds.push(lower);
+            x += width as i32 + 10;
+        }
+
+        Self {
+            bars,
+            lower_bounds,
+        }
+    }
+
+    pub fn update(&mut self, hp: i32) {
+        for (bar, &lower) in self.bars.iter_mut().zip(self.lower_bounds.iter()) {
+            let max_value = bar.max_value;
+            bar.update(max_value, (hp - lower).max(0).min(max_value));
+        }
+    }
+}
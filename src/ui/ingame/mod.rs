@@ -3,4 +3,5 @@ pub mod segmented_bar_ui;
 pub mod segmented_continuous_bar_ui;
 pub mod vertical_list_ui;
 pub mod wrapping_list_ui;
-pub mod popup_ui;
\ No newline at end of file
+pub mod popup_ui;
+pub mod boss_bar_ui;
\ No newline at end of file
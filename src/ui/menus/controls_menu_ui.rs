@@ -0,0 +1,112 @@
+use crate::{asset_management::controls::{Controls, ControlsProfile, GAMEPAD_REBINDABLE, KEYBOARD_REBINDABLE}, input::translated_inputs::TranslatedInput};
+
+pub struct ControlsMenu {
+    pub profile: ControlsProfile,
+    //rows are the rebindable inputs of the profile followed by the back row
+    pub selected_row: usize,
+    //waiting for the next key or button to bind to the selected row
+    pub capturing: bool,
+    pub message: Option<String>,
+}
+
+impl ControlsMenu {
+    pub fn new() -> Self {
+        Self {
+            profile: ControlsProfile::Keyboard,
+            selected_row: 0,
+            capturing: false,
+            message: None,
+        }
+    }
+
+    pub fn rebindable(&self) -> &'static [TranslatedInput] {
+        match self.profile {
+            ControlsProfile::Keyboard => &KEYBOARD_REBINDABLE,
+            ControlsProfile::Gamepad => &GAMEPAD_REBINDABLE,
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.rebindable().len() + 1
+    }
+
+    pub fn is_back_selected(&self) -> bool {
+        self.selected_row == self.rebindable().len()
+    }
+
+    pub fn move_selection(&mut self, direction: i32) {
+        let n_rows = self.n_rows() as i32;
+        self.selected_row = (((self.selected_row as i32 + direction) % n_rows + n_rows) % n_rows) as usize;
+    }
+
+    pub fn switch_profile(&mut self) {
+        self.profile = match self.profile {
+            ControlsProfile::Keyboard => ControlsProfile::Gamepad,
+            ControlsProfile::Gamepad => ControlsProfile::Keyboard,
+        };
+        self.selected_row = 0;
+        self.capturing = false;
+        self.message = None;
+    }
+
+    pub fn start_capture(&mut self) {
+        if !self.is_back_selected() {
+            self.capturing = true;
+            self.message = Some("press a key, escape cancels".to_string());
+        }
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capturing = false;
+        self.message = None;
+    }
+
+    //returns true when the bindings changed and need saving
+    pub fn capture(&mut self, controls: &mut Controls, profile: ControlsProfile, key: String) -> bool {
+        if !self.capturing || profile != self.profile {
+            return false;
+        }
+        self.capturing = false;
+
+        let input = self.rebindable()[self.selected_row];
+        self.message = match controls.rebind(self.profile, key.clone(), input) {
+            Some(conflict) => Some(format!("{} was on {}, swapped with {}", key, input_name(conflict), input_name(input))),
+            None => Some(format!("{} bound to {}", key, input_name(input))),
+        };
+        true
+    }
+
+    pub fn rows(&self, controls: &Controls) -> Vec<String> {
+        let mut rows = self.rebindable().iter().enumerate().map(|(i, input)| {
+            let keys = if self.capturing && i == self.selected_row {
+                "...".to_string()
+            } else {
+                let keys = controls.keys_bound_to(self.profile, *input);
+                if keys.is_empty() { "unbound".to_string() } else { keys.join(" / ") }
+            };
+            format!("{:<12}{}", input_name(*input), keys)
+        }).collect::<Vec<String>>();
+        rows.push("Back".to_string());
+        rows
+    }
+}
+
+pub fn input_name(input: TranslatedInput) -> &'static str {
+    match input {
+        TranslatedInput::Punch => "Punch",
+        TranslatedInput::Kick => "Kick",
+        TranslatedInput::Jump => "Jump",
+        TranslatedInput::Block => "Block",
+        TranslatedInput::Vertical(1) => "Up",
+        TranslatedInput::Vertical(_) => "Down",
+        TranslatedInput::Horizontal(-1) => "Left",
+        TranslatedInput::Horizontal(_) => "Right",
+    }
+}
+
+pub fn profile_name(profile: ControlsProfile) -> &'static str {
+    match profile {
+        ControlsProfile::Keyboard => "Keyboard",
+        ControlsProfile::Gamepad => "Gamepad",
+    }
+}
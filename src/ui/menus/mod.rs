@@ -1 +1,5 @@
 pub mod button_ui;
+pub mod controls_menu_ui;
+pub mod pause_menu_ui;
+pub mod settings_menu_ui;
+pub mod unlocks_menu_ui;
@@ -0,0 +1,125 @@
+use crate::game_logic::characters::Character;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PauseRow {
+    Resume,
+    Settings,
+    Controls,
+    AbandonRun,
+}
+
+pub const PAUSE_ROWS: [PauseRow; 4] = [
+    PauseRow::Resume,
+    PauseRow::Settings,
+    PauseRow::Controls,
+    PauseRow::AbandonRun,
+];
+
+//abandoning asks again, the confirmation rows replace the normal ones
+const CONFIRM_ROWS: usize = 2;
+const CONFIRM_NO: usize = 0;
+
+pub enum PauseAction {
+    Resume,
+    Settings,
+    Controls,
+    AbandonRun,
+}
+
+pub struct PauseMenu {
+    pub selected_row: usize,
+    pub confirming_abandon: bool,
+}
+
+impl PauseMenu {
+    pub fn new() -> Self {
+        Self {
+            selected_row: 0,
+            confirming_abandon: false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.selected_row = 0;
+        self.confirming_abandon = false;
+    }
+
+    pub fn move_selection(&mut self, direction: i32) {
+        let n_rows = if self.confirming_abandon { CONFIRM_ROWS } else { PAUSE_ROWS.len() } as i32;
+        self.selected_row = (((self.selected_row as i32 + direction) % n_rows + n_rows) % n_rows) as usize;
+    }
+
+    pub fn confirm(&mut self) -> Option<PauseAction> {
+        if self.confirming_abandon {
+            if self.selected_row == CONFIRM_NO {
+                self.confirming_abandon = false;
+                self.selected_row = PAUSE_ROWS.iter().position(|&row| {row == PauseRow::AbandonRun}).unwrap();
+                return None;
+            }
+            return Some(PauseAction::AbandonRun);
+        }
+
+        match PAUSE_ROWS[self.selected_row] {
+            PauseRow::Resume => Some(PauseAction::Resume),
+            PauseRow::Settings => Some(PauseAction::Settings),
+            PauseRow::Controls => Some(PauseAction::Controls),
+            PauseRow::AbandonRun => {
+                self.confirming_abandon = true;
+                self.selected_row = CONFIRM_NO;
+                None
+            },
+        }
+    }
+
+    //backing out of the confirmation goes to the pause rows, backing out of those resumes
+    pub fn back(&mut self) -> Option<PauseAction> {
+        if self.confirming_abandon {
+            self.confirming_abandon = false;
+            self.selected_row = PAUSE_ROWS.iter().position(|&row| {row == PauseRow::AbandonRun}).unwrap();
+            return None;
+        }
+        Some(PauseAction::Resume)
+    }
+
+    pub fn title(&self) -> String {
+        if self.confirming_abandon {
+            "Abandon the run? All progress is lost".to_string()
+        } else {
+            "Paused".to_string()
+        }
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        if self.confirming_abandon {
+            return vec!["No".to_string(), "Yes, abandon".to_string()];
+        }
+
+        PAUSE_ROWS.iter().map(|row| {
+            match row {
+                PauseRow::Resume => "Resume",
+                PauseRow::Settings => "Settings",
+                PauseRow::Controls => "Controls",
+                PauseRow::AbandonRun => "Abandon Run",
+            }.to_string()
+        }).collect()
+    }
+}
+
+pub fn stats_rows(character: &Character) -> Vec<String> {
+    vec![
+        character.name.clone(),
+        format!("{:<18}{}", "Speed", character.speed),
+        format!("{:<18}{}", "Dash speed", character.dash_speed),
+        format!("{:<18}{}", "Jump height", character.jump_height),
+        format!("{:<18}{}", "Double jump", yes_no(character.can_double_jump)),
+        format!("{:<18}{}", "Air dash", yes_no(character.can_air_dash)),
+        format!("{:<18}{}/{}", "Punch string", character.punch_string_curr, character.punch_string_max),
+        format!("{:<18}{}/{}", "Kick string", character.kick_string_curr, character.kick_string_max),
+        format!("{:<18}{}/{}", "Air punch string", character.airborne_punch_string_curr, character.airborne_punch_string_max),
+        format!("{:<18}{}/{}", "Air kick string", character.airborne_kick_string_curr, character.airborne_kick_string_max),
+    ]
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "Yes" } else { "No" }
+}
@@ -0,0 +1,155 @@
+use crate::{asset_management::settings::{Settings, VOLUME_STEP, WINDOW_SIZES}, game_logic::coop::CoopLoot};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SettingsRow {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiVolume,
+    VoiceVolume,
+    WindowSize,
+    Fullscreen,
+    Vsync,
+    ScreenShake,
+    ShowHitboxes,
+    FriendlyFire,
+    CoopLoot,
+    Back,
+}
+
+pub const SETTINGS_ROWS: [SettingsRow; 13] = [
+    SettingsRow::MasterVolume,
+    SettingsRow::MusicVolume,
+    SettingsRow::SfxVolume,
+    SettingsRow::UiVolume,
+    SettingsRow::VoiceVolume,
+    SettingsRow::WindowSize,
+    SettingsRow::Fullscreen,
+    SettingsRow::Vsync,
+    SettingsRow::ScreenShake,
+    SettingsRow::ShowHitboxes,
+    SettingsRow::FriendlyFire,
+    SettingsRow::CoopLoot,
+    SettingsRow::Back,
+];
+
+//what has to be pushed to sdl after a change, the rest is read where it is used
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SettingsChange {
+    Audio,
+    Video,
+    Other,
+}
+
+pub struct SettingsMenu {
+    pub selected_row: usize,
+    pub message: Option<String>,
+}
+
+impl SettingsMenu {
+    pub fn new() -> Self {
+        Self {
+            selected_row: 0,
+            message: None,
+        }
+    }
+
+    pub fn selected(&self) -> SettingsRow {
+        SETTINGS_ROWS[self.selected_row]
+    }
+
+    pub fn move_selection(&mut self, direction: i32) {
+        let n_rows = SETTINGS_ROWS.len() as i32;
+        self.selected_row = (((self.selected_row as i32 + direction) % n_rows + n_rows) % n_rows) as usize;
+    }
+
+    //left/right step values, confirm flips toggles
+    pub fn change(&mut self, settings: &mut Settings, direction: i32) -> Option<SettingsChange> {
+        self.message = None;
+        match self.selected() {
+            SettingsRow::MasterVolume => {
+                settings.master_volume = step_volume(settings.master_volume, direction);
+                Some(SettingsChange::Audio)
+            },
+            SettingsRow::MusicVolume => {
+                settings.music_volume = step_volume(settings.music_volume, direction);
+                Some(SettingsChange::Audio)
+            },
+            SettingsRow::SfxVolume => {
+                settings.sfx_volume = step_volume(settings.sfx_volume, direction);
+                Some(SettingsChange::Audio)
+            },
+            SettingsRow::UiVolume => {
+                settings.ui_volume = step_volume(settings.ui_volume, direction);
+                Some(SettingsChange::Audio)
+            },
+            SettingsRow::VoiceVolume => {
+                settings.voice_volume = step_volume(settings.voice_volume, direction);
+                Some(SettingsChange::Audio)
+            },
+            SettingsRow::WindowSize => {
+                let n_sizes = WINDOW_SIZES.len() as i32;
+                let index = ((settings.window_size_index() as i32 + direction) % n_sizes + n_sizes) % n_sizes;
+                settings.set_window_size(index as usize);
+                Some(SettingsChange::Video)
+            },
+            SettingsRow::Fullscreen => {
+                settings.fullscreen ^= true;
+                Some(SettingsChange::Video)
+            },
+            SettingsRow::Vsync => {
+                settings.vsync ^= true;
+                self.message = Some("vsync changes after a restart".to_string());
+                Some(SettingsChange::Other)
+            },
+            SettingsRow::ScreenShake => {
+                settings.screen_shake ^= true;
+                Some(SettingsChange::Other)
+            },
+            SettingsRow::ShowHitboxes => {
+                settings.show_hitboxes ^= true;
+                Some(SettingsChange::Other)
+            },
+            SettingsRow::FriendlyFire => {
+                settings.friendly_fire ^= true;
+                Some(SettingsChange::Other)
+            },
+            SettingsRow::CoopLoot => {
+                settings.coop_loot = match settings.coop_loot {
+                    CoopLoot::Shared => CoopLoot::Split,
+                    CoopLoot::Split => CoopLoot::Shared,
+                };
+                Some(SettingsChange::Other)
+            },
+            SettingsRow::Back => None,
+        }
+    }
+
+    pub fn rows(&self, settings: &Settings) -> Vec<String> {
+        SETTINGS_ROWS.iter().map(|row| {
+            match row {
+                SettingsRow::MasterVolume => format!("{:<16}< {} >", "Master volume", settings.master_volume),
+                SettingsRow::MusicVolume => format!("{:<16}< {} >", "Music volume", settings.music_volume),
+                SettingsRow::SfxVolume => format!("{:<16}< {} >", "Sfx volume", settings.sfx_volume),
+                SettingsRow::UiVolume => format!("{:<16}< {} >", "Menu volume", settings.ui_volume),
+                SettingsRow::VoiceVolume => format!("{:<16}< {} >", "Voice volume", settings.voice_volume),
+                SettingsRow::WindowSize => format!("{:<16}< {}x{} >", "Window size", settings.window_width, settings.window_height),
+                SettingsRow::Fullscreen => format!("{:<16}{}", "Fullscreen", on_off(settings.fullscreen)),
+                SettingsRow::Vsync => format!("{:<16}{}", "Vsync", on_off(settings.vsync)),
+                SettingsRow::ScreenShake => format!("{:<16}{}", "Screen shake", on_off(settings.screen_shake)),
+                SettingsRow::ShowHitboxes => format!("{:<16}{}", "Show hitboxes", on_off(settings.show_hitboxes)),
+                SettingsRow::FriendlyFire => format!("{:<16}{}", "Friendly fire", on_off(settings.friendly_fire)),
+                SettingsRow::CoopLoot => format!("{:<16}< {:?} >", "Co-op loot", settings.coop_loot),
+                SettingsRow::Back => "Back".to_string(),
+            }
+        }).collect()
+    }
+}
+
+fn step_volume(volume: i32, direction: i32) -> i32 {
+    (volume + direction * VOLUME_STEP).max(0).min(100)
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::{asset_management::profile::Profile, game_logic::{items::Item, meta_progression::MetaConfig}};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum UnlocksRow {
+    Loadout(usize),
+    Item(usize),
+    Back,
+}
+
+pub struct UnlocksMenu {
+    pub selected_row: usize,
+    pub message: Option<String>,
+}
+
+impl UnlocksMenu {
+    pub fn new() -> Self {
+        Self {
+            selected_row: 0,
+            message: None,
+        }
+    }
+
+    //loadouts first, then the item unlocks, then back
+    pub fn row_list(&self, config: &MetaConfig) -> Vec<UnlocksRow> {
+        (0..config.loadouts.len()).map(UnlocksRow::Loadout)
+            .chain((0..config.item_unlocks.len()).map(UnlocksRow::Item))
+            .chain(std::iter::once(UnlocksRow::Back))
+            .collect()
+    }
+
+    pub fn selected(&self, config: &MetaConfig) -> UnlocksRow {
+        self.row_list(config)[self.selected_row]
+    }
+
+    pub fn move_selection(&mut self, config: &MetaConfig, direction: i32) {
+        let n_rows = self.row_list(config).len() as i32;
+        self.selected_row = (((self.selected_row as i32 + direction) % n_rows + n_rows) % n_rows) as usize;
+    }
+
+    //owned loadouts get picked, anything else is bought if there is enough currency
+    //returns true when the profile changed
+    pub fn confirm(&mut self, config: &MetaConfig, profile: &mut Profile) -> bool {
+        self.message = None;
+        match self.selected(config) {
+            UnlocksRow::Loadout(i) => {
+                let loadout = &config.loadouts[i];
+                if !profile.has_loadout(&loadout.id) {
+                    if !profile.spend(loadout.cost) {
+                        self.message = Some("not enough currency".to_string());
+                        return false;
+                    }
+                    profile.unlocked_loadouts.insert(loadout.id.clone());
+                }
+                profile.loadout = loadout.id.clone();
+                true
+            },
+            UnlocksRow::Item(i) => {
+                let unlock = &config.item_unlocks[i];
+                if profile.unlocked_items.contains(&unlock.item_id) {
+                    return false;
+                }
+                if !profile.spend(unlock.cost) {
+                    self.message = Some("not enough currency".to_string());
+                    return false;
+                }
+                profile.unlocked_items.insert(unlock.item_id);
+                self.message = Some("the item shows up from the next run".to_string());
+                true
+            },
+            UnlocksRow::Back => false,
+        }
+    }
+
+    pub fn title(&self, profile: &Profile) -> String {
+        format!("Meta currency: {}", profile.meta_currency)
+    }
+
+    pub fn rows(&self, config: &MetaConfig, profile: &Profile, items: &HashMap<i32, Item>) -> Vec<String> {
+        self.row_list(config).into_iter().map(|row| {
+            match row {
+                UnlocksRow::Loadout(i) => {
+                    let loadout = &config.loadouts[i];
+                    let state = if profile.loadout == loadout.id {
+                        "equipped".to_string()
+                    } else if profile.has_loadout(&loadout.id) {
+                        "owned".to_string()
+                    } else {
+                        loadout.cost.to_string()
+                    };
+                    format!("{:<24}{}", format!("Loadout {}", loadout.name), state)
+                },
+                UnlocksRow::Item(i) => {
+                    let unlock = &config.item_unlocks[i];
+                    let name = items.get(&unlock.item_id)
+                        .map(|item| {item.name.clone()})
+                        .unwrap_or(format!("item {}", unlock.item_id));
+                    let state = if profile.unlocked_items.contains(&unlock.item_id) {
+                        "unlocked".to_string()
+                    } else {
+                        unlock.cost.to_string()
+                    };
+                    format!("{:<24}{}", format!("Item {}", name), state)
+                },
+                UnlocksRow::Back => "Back".to_string(),
+            }
+        }).collect()
+    }
+}
+
+pub fn milestone_rows(config: &MetaConfig, profile: &Profile) -> Vec<String> {
+    config.milestones.iter().map(|milestone| {
+        if profile.completed_milestones.contains(&milestone.id) {
+            format!("{} - {} (done)", milestone.name, milestone.description)
+        } else {
+            format!("{} - {} ({}/{})", milestone.name, milestone.description, profile.progress(milestone.counter).min(milestone.target), milestone.target)
+        }
+    }).collect()
+}